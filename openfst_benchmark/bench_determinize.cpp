@@ -0,0 +1,35 @@
+#include <string>
+#include <iostream>
+
+#include "fst/fstlib.h"
+#include "./utils.h"
+
+using namespace std;
+using namespace fst;
+using std::chrono::high_resolution_clock;
+
+int main(int argc, char **argv) {
+    auto n_warm_ups = stoi(argv[1]);
+    auto n_iters = stoi(argv[2]);
+    const string path_in = argv[3];
+    const string path_out = argv[4];
+    const string path_report_md = argv[5];
+    const string det_type_s = argv[6];
+
+    DeterminizeOptions<StdArc> opts;
+    if (det_type_s == "nonfunctional") {
+        opts.type = DETERMINIZE_NONFUNCTIONAL;
+    } else if (det_type_s == "disambiguate") {
+        opts.type = DETERMINIZE_DISAMBIGUATE;
+    } else {
+        opts.type = DETERMINIZE_FUNCTIONAL;
+    }
+
+    cout << "Running benchmark for algorithm determinize" << endl;
+    UNARY_ALGO_BENCH({
+        auto rfst = new VectorFst<StdArc>();
+        Determinize(*fst, rfst, opts);
+        delete fst;
+        fst = rfst;
+    })
+}
@@ -0,0 +1,45 @@
+use failure::{format_err, Fallible};
+
+use rustfst::prelude::*;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+pub struct DeterminizeAlgorithm {
+    path_in: String,
+    det_type: DeterminizeType,
+    path_out: String,
+}
+
+impl UnaryFstAlgorithm for DeterminizeAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "determinize".to_string()
+    }
+
+    fn run_algorithm(&self, fst: VectorFst<TropicalWeight>) -> Fallible<VectorFst<TropicalWeight>> {
+        determinize(&fst, self.det_type.clone())
+    }
+}
+
+impl DeterminizeAlgorithm {
+    pub fn new(path_in: &str, det_type: &str, path_out: &str) -> Fallible<Self> {
+        let det_type = match det_type {
+            "functional" => DeterminizeType::DeterminizeFunctional,
+            "nonfunctional" => DeterminizeType::DeterminizeNonFunctional,
+            "disambiguate" => DeterminizeType::DeterminizeDisambiguate,
+            _ => return Err(format_err!("Unknown det_type : {}", det_type)),
+        };
+        Ok(Self {
+            path_in: path_in.to_string(),
+            det_type,
+            path_out: path_out.to_string(),
+        })
+    }
+}
@@ -1,5 +1,6 @@
 pub mod arcsort;
 pub mod connect;
+pub mod determinize;
 pub mod invert;
 pub mod map;
 pub mod minimize;
@@ -7,5 +8,6 @@ pub mod project;
 pub mod push;
 pub mod reverse;
 pub mod rm_final_epsilon;
+pub mod shortest_distance;
 pub mod shortest_path;
 pub mod topsort;
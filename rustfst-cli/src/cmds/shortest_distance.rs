@@ -0,0 +1,49 @@
+use rustfst::prelude::*;
+
+use failure::Fallible;
+
+use crate::unary_fst_algorithm::UnaryFstAlgorithm;
+
+/// Computes the shortest distance from the initial state and stores the distance of each state
+/// as its final weight, which makes the result comparable with `bench_shortestdistance`.
+pub struct ShortestDistanceAlgorithm {
+    path_in: String,
+    path_out: String,
+}
+
+impl UnaryFstAlgorithm for ShortestDistanceAlgorithm {
+    fn get_path_in(&self) -> &str {
+        self.path_in.as_str()
+    }
+
+    fn get_path_out(&self) -> &str {
+        self.path_out.as_str()
+    }
+
+    fn get_algorithm_name(&self) -> String {
+        "shortest distance".to_string()
+    }
+
+    fn run_algorithm(
+        &self,
+        mut fst: VectorFst<TropicalWeight>,
+    ) -> Fallible<VectorFst<TropicalWeight>> {
+        let distances = shortest_distance(&fst, false)?;
+        for s in 0..fst.num_states() {
+            match distances.get(s) {
+                Some(d) if !d.is_zero() => fst.set_final(s, d.clone())?,
+                _ => fst.delete_final_weight(s)?,
+            }
+        }
+        Ok(fst)
+    }
+}
+
+impl ShortestDistanceAlgorithm {
+    pub fn new(path_in: &str, path_out: &str) -> Self {
+        Self {
+            path_in: path_in.to_string(),
+            path_out: path_out.to_string(),
+        }
+    }
+}
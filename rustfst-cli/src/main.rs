@@ -6,6 +6,7 @@ use log::error;
 
 use crate::cmds::arcsort::ArcsortAlgorithm;
 use crate::cmds::connect::ConnectAlgorithm;
+use crate::cmds::determinize::DeterminizeAlgorithm;
 use crate::cmds::invert::InvertAlgorithm;
 use crate::cmds::map::MapAlgorithm;
 use crate::cmds::minimize::MinimizeAlgorithm;
@@ -13,6 +14,7 @@ use crate::cmds::project::ProjectFstAlgorithm;
 use crate::cmds::push::PushAlgorithm;
 use crate::cmds::reverse::ReverseAlgorithm;
 use crate::cmds::rm_final_epsilon::RmFinalEpsilonAlgorithm;
+use crate::cmds::shortest_distance::ShortestDistanceAlgorithm;
 use crate::cmds::shortest_path::ShortestPathAlgorithm;
 use crate::cmds::topsort::TopsortAlgorithm;
 use crate::pretty_errors::ExitFailure;
@@ -139,6 +141,24 @@ fn main() {
         .arg(Arg::with_name("remove_common_affix").long("remove_common_affix"));
     app = app.subcommand(one_in_one_out_options(push_cmd));
 
+    // Determinize
+    let determinize_cmd = SubCommand::with_name("determinize")
+        .about("Determinization algorithm.")
+        .arg(
+            Arg::with_name("det_type")
+                .help("Type of determinization.")
+                .long("det_type")
+                .takes_value(true)
+                .possible_values(&["functional", "nonfunctional", "disambiguate"])
+                .default_value("functional"),
+        );
+    app = app.subcommand(one_in_one_out_options(determinize_cmd));
+
+    // Shortest Distance
+    let shortest_distance_cmd = SubCommand::with_name("shortestdistance")
+        .about("Shortest Distance algorithm, the distances being stored as final weights.");
+    app = app.subcommand(one_in_one_out_options(shortest_distance_cmd));
+
     let matches = app.get_matches();
 
     let env = env_logger::Env::default().filter_or(env_logger::DEFAULT_FILTER_ENV, "debug");
@@ -223,6 +243,17 @@ fn handle(matches: clap::ArgMatches) -> Result<(), ExitFailure> {
             m.is_present("remove_common_affix"),
         )
         .run_cli_or_bench(m),
+        ("determinize", Some(m)) => DeterminizeAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("det_type").unwrap(),
+            m.value_of("out.fst").unwrap(),
+        )
+        .and_then(|algo| algo.run_cli_or_bench(m)),
+        ("shortestdistance", Some(m)) => ShortestDistanceAlgorithm::new(
+            m.value_of("in.fst").unwrap(),
+            m.value_of("out.fst").unwrap(),
+        )
+        .run_cli_or_bench(m),
         (s, _) => Err(format_err!("Unknown subcommand {}.", s)),
     }
     .map_err(|e| e.into())
@@ -0,0 +1,28 @@
+from rustfst_python_bench.utils import check_fst_equals
+
+
+class DeterminizeAlgorithm:
+
+    def __init__(self, det_type="functional"):
+        self.det_type = det_type
+
+    @classmethod
+    def openfst_cli(cls):
+        return "fstdeterminize"
+
+    @classmethod
+    def rustfst_subcommand(cls):
+        return "determinize"
+
+    def get_openfst_bench_cli(self):
+        return "bench_determinize", [self.det_type]
+
+    def get_cli_args(self):
+        return f"--det_type={self.det_type}"
+
+    @classmethod
+    def get_parameters(cls):
+        return [cls(det_type="functional")]
+
+    def check_correctness(self, path_res_openfst, path_res_rustfst):
+        check_fst_equals(path_res_openfst, path_res_rustfst)
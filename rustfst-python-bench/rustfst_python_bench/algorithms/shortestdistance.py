@@ -0,0 +1,27 @@
+class ShortestDistanceAlgorithm:
+
+    def __init__(self):
+        pass
+
+    @classmethod
+    def openfst_cli(cls):
+        return "fstshortestdistance"
+
+    @classmethod
+    def rustfst_subcommand(cls):
+        return "shortestdistance"
+
+    def get_openfst_bench_cli(self):
+        return "bench_shortestdistance", []
+
+    def get_cli_args(self):
+        return ""
+
+    @classmethod
+    def get_parameters(cls):
+        return [cls()]
+
+    def check_correctness(self, path_res_openfst, path_res_rustfst):
+        # fstshortestdistance writes the distances as text whereas rustfst-cli stores them as
+        # the final weights of the FST.
+        pass
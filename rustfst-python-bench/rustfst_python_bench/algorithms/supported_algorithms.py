@@ -1,11 +1,13 @@
 from rustfst_python_bench.algorithms.arcsort import ArcSortAlgorithm
 from rustfst_python_bench.algorithms.connect import ConnectAlgorithm
+from rustfst_python_bench.algorithms.determinize import DeterminizeAlgorithm
 from rustfst_python_bench.algorithms.invert import InvertAlgorithm
 from rustfst_python_bench.algorithms.project import ProjectAlgorithm
 from rustfst_python_bench.algorithms.minimize import MinimizeAlgorithm
 from rustfst_python_bench.algorithms.push import PushAlgorithm
 from rustfst_python_bench.algorithms.reverse import ReverseAlgorithm
 from rustfst_python_bench.algorithms.rm_final_epsilon import RmFinalEpsilonAlgorithm
+from rustfst_python_bench.algorithms.shortestdistance import ShortestDistanceAlgorithm
 from rustfst_python_bench.algorithms.shortestpath import ShortestPathAlgorithm
 from rustfst_python_bench.algorithms.map import MapAlgorithm
 
@@ -28,6 +30,7 @@ class SupportedAlgorithms(object):
 
 SupportedAlgorithms.register("arcsort", ArcSortAlgorithm)
 SupportedAlgorithms.register("connect", ConnectAlgorithm)
+SupportedAlgorithms.register("determinize", DeterminizeAlgorithm)
 SupportedAlgorithms.register("invert", InvertAlgorithm)
 SupportedAlgorithms.register("map", MapAlgorithm)
 # SupportedAlgorithms.register("minimize", MinimizeAlgorithm)
@@ -35,5 +38,6 @@ SupportedAlgorithms.register("project", ProjectAlgorithm)
 # SupportedAlgorithms.register("push", PushAlgorithm)
 SupportedAlgorithms.register("reverse", ReverseAlgorithm)
 # SupportedAlgorithms.register("rmfinalepsilon", RmFinalEpsilonAlgorithm)
+SupportedAlgorithms.register("shortestdistance", ShortestDistanceAlgorithm)
 SupportedAlgorithms.register("shortestpath", ShortestPathAlgorithm)
 
@@ -0,0 +1,75 @@
+import argparse
+import io
+import os
+import subprocess
+import tempfile
+
+from rustfst_python_bench.constants import OPENFST_BINS
+
+
+def parse():
+    parser = argparse.ArgumentParser(
+        description="Script to generate a grid FST with small integer weights, to bench shortestdistance"
+    )
+
+    parser.add_argument(
+        "path_out_fst",
+        type=str,
+        help="Path to the generated fst",
+    )
+
+    parser.add_argument(
+        "-s", "--size",
+        type=int,
+        help="Number of rows and columns of the grid",
+        default=500
+    )
+
+    parser.add_argument(
+        "-m", "--max_weight",
+        type=int,
+        help="Largest arc weight",
+        default=10
+    )
+
+    args = parser.parse_args()
+
+    return args
+
+
+def write_grid(f, size, max_weight):
+    # Each cell is linked to its four neighbours, the weights following a fixed pattern so that
+    # the generated FST is reproducible.
+    for i in range(size):
+        for j in range(size):
+            s = i * size + j
+            w = (i * 7 + j * 3) % (max_weight + 1)
+            if j + 1 < size:
+                f.write(f"{s}\t{s + 1}\t1\t1\t{w}\n")
+                f.write(f"{s + 1}\t{s}\t2\t2\t{max_weight - w}\n")
+            if i + 1 < size:
+                f.write(f"{s}\t{s + size}\t3\t3\t{max_weight - w}\n")
+                f.write(f"{s + size}\t{s}\t4\t4\t{w}\n")
+    f.write(f"{size * size - 1}\n")
+
+
+def generate(path_out_fst, size, max_weight):
+    cli = os.path.join(OPENFST_BINS, "fstcompile")
+
+    with tempfile.TemporaryDirectory() as tmpdirname:
+        path_text_fst = os.path.join(tmpdirname, "grid.txt")
+
+        with io.open(path_text_fst, mode="w") as f:
+            write_grid(f, size, max_weight)
+
+        cmd = f"{cli} {path_text_fst} {path_out_fst}"
+        subprocess.check_call([cmd], shell=True)
+
+
+def main():
+    args = parse()
+    generate(args.path_out_fst, args.size, args.max_weight)
+
+
+if __name__ == '__main__':
+    main()
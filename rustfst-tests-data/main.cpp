@@ -38,6 +38,15 @@ string fst_to_string(const fst::VectorFst<A>& a) {
     return sstrm.str();
 }
 
+template<class F>
+void compute_fst_print(const F& raw_fst, json& j) {
+    std::stringstream sstrm;
+    // Default options of the fstprint binary : the weights equal to One() are omitted
+    fst::FstPrinter<typename F::Arc> fstprinter(raw_fst, NULL, NULL, NULL, false, false, "\t");
+    fstprinter.Print(&sstrm, string("<rustfst>"));
+    j["fstprint"]["result"] = sstrm.str();
+}
+
 template<class F>
 void compute_fst_invert(const F& raw_fst, json& j) {
     auto fst_out = *raw_fst.Copy();
@@ -502,6 +511,9 @@ void compute_fst_data(const fst::VectorFst<A>& raw_fst, const string fst_name) {
     std::ofstream strm_aligned((fst_name + "/raw_const_aligned.fst").c_str(), std::ios_base::out | std::ios_base::binary);
     raw_const_fst.Write(strm_aligned, write_opts);
 
+    std::cout << "Print" << std::endl;
+    compute_fst_print(raw_fst, data);
+
     std::cout << "Invert" << std::endl;
     compute_fst_invert(raw_fst, data);
 
@@ -0,0 +1,135 @@
+use std::collections::BTreeSet;
+
+use crate::fst_traits::ExpandedFst;
+use crate::{Label, EPS_LABEL};
+
+/// Returns the set of the non-epsilon input labels used by the arcs of an FST.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::input_labels;
+/// let fst : VectorFst<IntegerWeight> = fst![3, 1, 3 => 2, 0];
+///
+/// assert_eq!(input_labels(&fst).into_iter().collect::<Vec<_>>(), vec![1, 3]);
+/// ```
+pub fn input_labels<F: ExpandedFst>(fst: &F) -> BTreeSet<Label> {
+    let mut labels = BTreeSet::new();
+    for s in fst.states_iter() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            if arc.ilabel != EPS_LABEL {
+                labels.insert(arc.ilabel);
+            }
+        }
+    }
+    labels
+}
+
+/// Returns the set of the non-epsilon output labels used by the arcs of an FST.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::output_labels;
+/// let fst : VectorFst<IntegerWeight> = fst![3, 1, 3 => 2, 0];
+///
+/// assert_eq!(output_labels(&fst).into_iter().collect::<Vec<_>>(), vec![2]);
+/// ```
+pub fn output_labels<F: ExpandedFst>(fst: &F) -> BTreeSet<Label> {
+    let mut labels = BTreeSet::new();
+    for s in fst.states_iter() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            if arc.olabel != EPS_LABEL {
+                labels.insert(arc.olabel);
+            }
+        }
+    }
+    labels
+}
+
+/// Returns whether the output labels of `fst_1` and the input labels of `fst_2` have at least
+/// one non-epsilon label in common. When they don't, the composition of `fst_1` with `fst_2`
+/// can't have any path going through a non-epsilon arc, which usually reveals mismatched
+/// alphabets.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::composable;
+/// let fst_1 : VectorFst<IntegerWeight> = fst![1, 2 => 3, 4];
+/// let fst_2 : VectorFst<IntegerWeight> = fst![4 => 5];
+/// let fst_3 : VectorFst<IntegerWeight> = fst![1 => 2];
+///
+/// assert!(composable(&fst_1, &fst_2));
+/// assert!(!composable(&fst_1, &fst_3));
+/// ```
+pub fn composable<F1: ExpandedFst, F2: ExpandedFst>(fst_1: &F1, fst_2: &F2) -> bool {
+    !output_labels(fst_1).is_disjoint(&input_labels(fst_2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_alphabets() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, TropicalWeight::one())?;
+        fst.add_arc(s0, Arc::new(4, 7, TropicalWeight::one(), s1))?;
+        fst.add_arc(s0, Arc::new(0, 5, TropicalWeight::one(), s0))?;
+        fst.add_arc(s1, Arc::new(2, 0, TropicalWeight::one(), s0))?;
+        fst.add_arc(s1, Arc::new(4, 5, TropicalWeight::one(), s1))?;
+
+        assert_eq!(input_labels(&fst), vec![2, 4].into_iter().collect());
+        assert_eq!(output_labels(&fst), vec![5, 7].into_iter().collect());
+        Ok(())
+    }
+
+    #[test]
+    fn test_composable() -> Fallible<()> {
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(2);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, TropicalWeight::one())?;
+        fst_1.add_arc(0, Arc::new(1, 2, TropicalWeight::one(), 1))?;
+        fst_1.add_arc(0, Arc::new(3, 0, TropicalWeight::one(), 1))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(2);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, TropicalWeight::one())?;
+        fst_2.add_arc(0, Arc::new(0, 4, TropicalWeight::one(), 1))?;
+        fst_2.add_arc(0, Arc::new(5, 6, TropicalWeight::one(), 1))?;
+
+        // Epsilons aren't part of the alphabets
+        assert!(!composable(&fst_1, &fst_2));
+        assert!(!composable(&fst_1, &VectorFst::<TropicalWeight>::new()));
+
+        fst_2.add_arc(1, Arc::new(2, 7, TropicalWeight::one(), 1))?;
+        assert!(composable(&fst_1, &fst_2));
+        assert!(!composable(&fst_2, &fst_1));
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
 use crate::semirings::Semiring;
 use crate::Arc;
+use crate::EpsilonConfig;
 use crate::EPS_LABEL;
 
 /// Base trait to restrict which arcs are traversed in an FST.
@@ -43,3 +44,36 @@ impl<S: Semiring> ArcFilter<S> for OutputEpsilonArcFilter {
         arc.olabel == EPS_LABEL
     }
 }
+
+/// True for (input/output) epsilon arcs, epsilon being defined by an `EpsilonConfig`.
+pub struct ConfigEpsilonArcFilter {
+    pub config: EpsilonConfig,
+}
+
+impl<S: Semiring> ArcFilter<S> for ConfigEpsilonArcFilter {
+    fn keep(&self, arc: &Arc<S>) -> bool {
+        self.config.is_epsilon(arc.ilabel) && self.config.is_epsilon(arc.olabel)
+    }
+}
+
+/// True for input epsilon arcs, epsilon being defined by an `EpsilonConfig`.
+pub struct ConfigInputEpsilonArcFilter {
+    pub config: EpsilonConfig,
+}
+
+impl<S: Semiring> ArcFilter<S> for ConfigInputEpsilonArcFilter {
+    fn keep(&self, arc: &Arc<S>) -> bool {
+        self.config.is_epsilon(arc.ilabel)
+    }
+}
+
+/// True for output epsilon arcs, epsilon being defined by an `EpsilonConfig`.
+pub struct ConfigOutputEpsilonArcFilter {
+    pub config: EpsilonConfig,
+}
+
+impl<S: Semiring> ArcFilter<S> for ConfigOutputEpsilonArcFilter {
+    fn keep(&self, arc: &Arc<S>) -> bool {
+        self.config.is_epsilon(arc.olabel)
+    }
+}
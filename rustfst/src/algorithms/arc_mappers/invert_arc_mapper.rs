@@ -0,0 +1,27 @@
+use std::mem::swap;
+
+use failure::Fallible;
+
+use crate::algorithms::{ArcMapper, FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::Semiring;
+use crate::Arc;
+
+/// Mapper that swaps the input and output labels of the arcs.
+pub struct InvertArcMapper {}
+
+impl<S: Semiring> ArcMapper<S> for InvertArcMapper {
+    fn arc_map(&mut self, arc: &mut Arc<S>) -> Fallible<()> {
+        swap(&mut arc.ilabel, &mut arc.olabel);
+        Ok(())
+    }
+
+    fn final_arc_map(&mut self, _final_arc: &mut FinalArc<S>) -> Fallible<()> {
+        Ok(())
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+arc_mapper_to_weight_convert_mapper!(InvertArcMapper);
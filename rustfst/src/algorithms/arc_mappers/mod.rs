@@ -3,6 +3,7 @@ mod arc_mapper_to_weight_convert_mapper;
 
 mod identity_arc_mapper;
 mod input_epsilon_mapper;
+mod invert_arc_mapper;
 mod invert_weight_mapper;
 mod output_epsilon_mapper;
 mod plus_mapper;
@@ -12,6 +13,7 @@ mod times_mapper;
 
 pub use self::identity_arc_mapper::IdentityArcMapper;
 pub use self::input_epsilon_mapper::InputEpsilonMapper;
+pub use self::invert_arc_mapper::InvertArcMapper;
 pub use self::invert_weight_mapper::InvertWeightMapper;
 pub use self::output_epsilon_mapper::OutputEpsilonMapper;
 pub use self::plus_mapper::PlusMapper;
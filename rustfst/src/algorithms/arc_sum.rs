@@ -2,6 +2,33 @@ use crate::fst_traits::{ExpandedFst, MutableFst};
 
 /// Plus-Sum weights of arcs leaving the same state, going to the same state
 /// and with the same input and output labels.
+///
+/// No arc is created or relabeled, hence an input-deterministic FST stays input-deterministic.
+/// The output of `determinize` has no duplicated arcs and is left unchanged.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::arc_sum;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, TropicalWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::new(1.0), s1))?;
+/// fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::new(3.0), s1))?;
+///
+/// arc_sum(&mut fst);
+///
+/// assert_eq!(fst.num_arcs(s0)?, 1);
+/// # Ok(())
+/// # }
+/// ```
 pub fn arc_sum<F: MutableFst + ExpandedFst>(ifst: &mut F) {
     unsafe {
         for s in 0..ifst.num_states() {
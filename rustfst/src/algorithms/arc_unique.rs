@@ -5,7 +5,7 @@ use crate::fst_traits::MutableFst;
 use crate::semirings::Semiring;
 use crate::Arc;
 
-pub(crate) fn arc_compare<W: Semiring>(arc_1: &Arc<W>, arc_2: &Arc<W>) -> Ordering {
+pub(crate) fn arc_compare<W: Semiring, M>(arc_1: &Arc<W, M>, arc_2: &Arc<W, M>) -> Ordering {
     if arc_1.ilabel < arc_2.ilabel {
         return Ordering::Less;
     }
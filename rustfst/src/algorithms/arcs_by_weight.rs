@@ -0,0 +1,101 @@
+use std::cmp::Ordering;
+
+use failure::Fallible;
+
+use crate::fst_path::natural_cmp;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::{Semiring, SemiringProperties};
+use crate::StateId;
+
+/// Returns all the arcs of an FST, referenced by their state and their position among the arcs
+/// of that state, sorted from the best to the worst weight according to the natural order of
+/// the semiring (`w1 < w2` iff `w1 + w2 = w1` and `w1 != w2`). The order of arcs with the same
+/// weight is the order of the FST.
+///
+/// This is meant for greedy pruning strategies removing or keeping arcs by weight. An error is
+/// returned if the semiring doesn't have the path property, its natural order not being total.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::arcs_by_weight;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(3.0), s1))?;
+/// fst.add_arc(s0, Arc::new(2, 2, TropicalWeight::new(1.0), s1))?;
+/// fst.add_arc(s1, Arc::new(3, 3, TropicalWeight::new(2.0), s0))?;
+///
+/// assert_eq!(arcs_by_weight(&fst)?, vec![(s0, 1), (s1, 0), (s0, 0)]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn arcs_by_weight<F: ExpandedFst>(fst: &F) -> Fallible<Vec<(StateId, usize)>> {
+    ensure!(
+        F::W::properties().contains(SemiringProperties::PATH),
+        "Arcs can only be sorted by weight over a path semiring"
+    );
+
+    let mut arcs = vec![];
+    for s in 0..fst.num_states() {
+        for (idx, arc) in unsafe { fst.arcs_iter_unchecked(s) }.enumerate() {
+            arcs.push((s, idx, &arc.weight));
+        }
+    }
+
+    let mut error = None;
+    arcs.sort_by(|(_, _, w1), (_, _, w2)| {
+        natural_cmp(*w1, *w2).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    if let Some(e) = error {
+        return Err(e);
+    }
+
+    Ok(arcs.into_iter().map(|(s, idx, _)| (s, idx)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, MutableFst};
+    use crate::semirings::{LogWeight, TropicalWeight};
+
+    #[test]
+    fn test_arcs_by_weight() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(2.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(0.5), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(1.5), 2))?;
+        fst.add_arc(1, Arc::new(4, 4, TropicalWeight::new(0.5), 0))?;
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(3.0), 2))?;
+
+        let arcs = arcs_by_weight(&fst)?;
+        assert_eq!(arcs, vec![(0, 1), (1, 1), (1, 0), (0, 0), (2, 0)]);
+
+        let weights: Vec<_> = arcs
+            .iter()
+            .map(|(s, idx)| fst.arcs_iter(*s).unwrap().nth(*idx).unwrap().weight)
+            .collect();
+        assert!(weights.windows(2).all(|w| w[0].value() <= w[1].value()));
+
+        let mut fst = VectorFst::new();
+        fst.add_states(1);
+        fst.add_arc(0, Arc::new(1, 1, LogWeight::new(1.0), 0))?;
+        assert!(arcs_by_weight(&fst).is_err());
+        Ok(())
+    }
+}
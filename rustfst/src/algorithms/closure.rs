@@ -1,29 +1,92 @@
 use crate::arc::Arc;
-use crate::fst_traits::{CoreFst, FinalStatesIterator, MutableFst};
+use crate::fst_traits::{FinalStatesIterator, MutableFst};
 use crate::semirings::Semiring;
 use crate::EPS_LABEL;
 
+#[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
+/// Different types of concatenative closure.
+pub enum ClosureType {
+    /// Kleene star : the empty string is accepted as well.
+    ClosureStar,
+    /// Kleene plus : at least one repetition is required.
+    ClosurePlus,
+}
+
 /// This operation computes the concatenative closure.
 /// If A transduces string `x` to `y` with weight `a`,
 /// then the closure transduces `x` to `y` with weight `a`,
 /// `xx` to `yy` with weight `a ⊗ a`, `xxx` to `yyy` with weight `a ⊗ a ⊗ a`, etc.
-pub fn closure_plus<F>(fst: &mut F)
+/// With `ClosureStar`, the empty string is transduced to itself with weight `1` as well.
+///
+/// A new start state is added along with an epsilon arc to the former start state. Each final
+/// state gets an epsilon arc back to the former start state, weighted by its final weight. With
+/// `ClosureStar`, the new start state is final.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst};
+/// # use rustfst::algorithms::{closure, ClosureType};
+/// let mut fst : VectorFst<IntegerWeight> = fst![1 => 2];
+///
+/// closure(&mut fst, ClosureType::ClosureStar);
+///
+/// assert_eq!(fst.num_states(), 3);
+/// assert_eq!(fst.final_weight(fst.start().unwrap()).unwrap(), Some(&IntegerWeight::one()));
+/// ```
+pub fn closure<W, F>(fst: &mut F, closure_type: ClosureType)
 where
-    F: MutableFst,
+    W: Semiring,
+    F: MutableFst<W = W>,
 {
+    let start_state = fst.start();
+
     // Add an epsilon arc from each final states to the start state
-    if let Some(start_state) = fst.start() {
-        let final_states_id: Vec<_> = fst.final_states_iter().map(|u| u.state_id).collect();
-        for final_state_id in final_states_id {
+    if let Some(start_state_id) = start_state {
+        let final_states: Vec<_> = fst
+            .final_states_iter()
+            .map(|u| (u.state_id, u.final_weight.clone()))
+            .collect();
+        for (final_state_id, final_weight) in final_states {
             fst.add_arc(
                 final_state_id,
-                Arc::new(EPS_LABEL, EPS_LABEL, <F as CoreFst>::W::one(), start_state),
+                Arc::new(EPS_LABEL, EPS_LABEL, final_weight, start_state_id),
             )
             .unwrap();
         }
+    } else if closure_type == ClosureType::ClosurePlus {
+        return;
+    }
+
+    // Add a new start state, final to allow empty path with ClosureStar
+    let new_start_state_id = fst.add_state();
+    fst.set_start(new_start_state_id).unwrap();
+    if closure_type == ClosureType::ClosureStar {
+        fst.set_final(new_start_state_id, W::one()).unwrap();
+    }
+    if let Some(start_state_id) = start_state {
+        fst.add_arc(
+            new_start_state_id,
+            Arc::new(EPS_LABEL, EPS_LABEL, W::one(), start_state_id),
+        )
+        .unwrap();
     }
 }
 
+/// This operation computes the concatenative closure.
+/// If A transduces string `x` to `y` with weight `a`,
+/// then the closure transduces `x` to `y` with weight `a`,
+/// `xx` to `yy` with weight `a ⊗ a`, `xxx` to `yyy` with weight `a ⊗ a ⊗ a`, etc.
+pub fn closure_plus<F>(fst: &mut F)
+where
+    F: MutableFst,
+{
+    closure(fst, ClosureType::ClosurePlus)
+}
+
 /// This operation computes the concatenative closure.
 /// If A transduces string `x` to `y` with weight `a`,
 /// then the closure transduces `x` to `y` with weight `a`,
@@ -33,22 +96,78 @@ pub fn closure_star<F>(fst: &mut F)
 where
     F: MutableFst,
 {
-    closure_plus(fst);
+    closure(fst, ClosureType::ClosureStar)
+}
 
-    // Add a new start state to allow empty path
-    let start_state = fst.start();
-    if let Some(start_state_id) = start_state {
-        let new_start_state_id = fst.add_state();
-        fst.set_start(new_start_state_id).unwrap();
-        fst.add_arc(
-            new_start_state_id,
-            Arc::new(
-                EPS_LABEL,
-                EPS_LABEL,
-                <F as CoreFst>::W::one(),
-                start_state_id,
-            ),
-        )
-        .unwrap();
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use failure::Fallible;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::IntegerWeight;
+    use crate::FstPath;
+
+    /// Paths of the closure of an FST accepting `1` with weight 6 and `2` with weight 3, made of
+    /// at most two repetitions.
+    fn closure_paths(closure_type: ClosureType) -> Fallible<HashSet<FstPath<IntegerWeight>>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.set_final(1, IntegerWeight::new(3))?;
+        fst.add_arc(0, Arc::new(1, 1, IntegerWeight::new(2), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, IntegerWeight::one(), 1))?;
+
+        closure(&mut fst, closure_type);
+        // Each repetition is made of an epsilon arc and a labeled arc
+        Ok(fst.paths_iter_with_max_depth(4).collect())
+    }
+
+    fn repetitions_paths() -> HashSet<FstPath<IntegerWeight>> {
+        let mut paths = HashSet::new();
+        paths.insert(FstPath::new(vec![1], vec![1], IntegerWeight::new(6)));
+        paths.insert(FstPath::new(vec![2], vec![2], IntegerWeight::new(3)));
+        paths.insert(FstPath::new(vec![1, 1], vec![1, 1], IntegerWeight::new(36)));
+        paths.insert(FstPath::new(vec![1, 2], vec![1, 2], IntegerWeight::new(18)));
+        paths.insert(FstPath::new(vec![2, 1], vec![2, 1], IntegerWeight::new(18)));
+        paths.insert(FstPath::new(vec![2, 2], vec![2, 2], IntegerWeight::new(9)));
+        paths
+    }
+
+    #[test]
+    fn test_closure_star() -> Fallible<()> {
+        let mut paths_ref = repetitions_paths();
+        paths_ref.insert(FstPath::new(vec![], vec![], IntegerWeight::one()));
+
+        assert_eq!(closure_paths(ClosureType::ClosureStar)?, paths_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_closure_plus() -> Fallible<()> {
+        assert_eq!(
+            closure_paths(ClosureType::ClosurePlus)?,
+            repetitions_paths()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_closure_empty_fst() -> Fallible<()> {
+        let mut fst = VectorFst::<IntegerWeight>::new();
+        closure(&mut fst, ClosureType::ClosurePlus);
+        assert_eq!(fst, VectorFst::new());
+
+        closure(&mut fst, ClosureType::ClosureStar);
+        let paths: Vec<_> = fst.paths_iter().collect();
+        assert_eq!(
+            paths,
+            vec![FstPath::new(vec![], vec![], IntegerWeight::one())]
+        );
+        Ok(())
     }
 }
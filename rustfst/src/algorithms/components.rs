@@ -0,0 +1,88 @@
+use crate::fst_traits::ExpandedFst;
+use crate::StateId;
+
+/// Returns the representative of the set containing `s`, compressing the path along the way.
+fn find(parents: &mut [StateId], s: StateId) -> StateId {
+    let mut root = s;
+    while parents[root] != root {
+        root = parents[root];
+    }
+    let mut s = s;
+    while parents[s] != root {
+        let next = parents[s];
+        parents[s] = root;
+        s = next;
+    }
+    root
+}
+
+/// Returns the number of weakly connected components of an FST, i.e the number of connected
+/// components of the undirected graph obtained by ignoring the direction of the arcs.
+///
+/// More than one component means that the FST contains disjoint sub-FSTs.
+///
+/// # Example
+///
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::num_connected_components;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::<IntegerWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_state();
+/// fst.add_arc(s1, Arc::new(3, 5, IntegerWeight::one(), s0)).unwrap();
+///
+/// assert_eq!(num_connected_components(&fst), 2);
+/// ```
+pub fn num_connected_components<F: ExpandedFst>(fst: &F) -> usize {
+    let num_states = fst.num_states();
+    let mut parents: Vec<StateId> = (0..num_states).collect();
+    let mut num_components = num_states;
+    for s in 0..num_states {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            let root1 = find(&mut parents, s);
+            let root2 = find(&mut parents, arc.nextstate);
+            if root1 != root2 {
+                parents[root1] = root2;
+                num_components -= 1;
+            }
+        }
+    }
+    num_components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_num_connected_components_two_islands() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(6);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, TropicalWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::one(), 0))?;
+
+        // Second island made of states 3, 4 and 5, whose arcs all converge to state 4.
+        fst.add_arc(3, Arc::new(1, 1, TropicalWeight::one(), 4))?;
+        fst.add_arc(5, Arc::new(1, 1, TropicalWeight::one(), 4))?;
+
+        assert_eq!(num_connected_components(&fst), 2);
+
+        fst.add_arc(4, Arc::new(1, 1, TropicalWeight::one(), 2))?;
+        assert_eq!(num_connected_components(&fst), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,208 @@
+use std::hash::Hash;
+
+use failure::Fallible;
+
+use crate::fst_traits::Fst;
+use crate::semirings::Semiring;
+use crate::{Arc, StateId, EPS_LABEL};
+
+/// Base trait to restrict which pairs of arcs are matched by
+/// [`compose_with_filter`](../fn.compose_with_filter.html), to avoid redundant epsilon paths.
+///
+/// Each state of the composition is a pair of states of the input FSTs along with a filter
+/// state. An FST can stay in its state while the other one moves on an epsilon, as if each of
+/// its states had an implicit epsilon self-loop : such a move is described by `None` in place of
+/// the arc of the FST staying in its state.
+pub trait ComposeFilter<W: Semiring> {
+    /// State of the filter, part of the states of the composition.
+    type FilterState: Clone + Eq + Hash;
+
+    /// Filter state of the start state of the composition.
+    fn start(&self) -> Self::FilterState;
+
+    /// Sets the state of the composition from which the next arcs are filtered.
+    fn set_state<F1, F2>(
+        &mut self,
+        fst_1: &F1,
+        s1: StateId,
+        fst_2: &F2,
+        s2: StateId,
+        filter_state: &Self::FilterState,
+    ) -> Fallible<()>
+    where
+        F1: Fst<W = W>,
+        F2: Fst<W = W>;
+
+    /// Returns the filter state reached by matching `arc_1` with `arc_2` from the current state,
+    /// or `None` if the move is not allowed. Both arcs are never `None` at the same time.
+    fn filter_arc(
+        &self,
+        arc_1: Option<&Arc<W>>,
+        arc_2: Option<&Arc<W>>,
+    ) -> Option<Self::FilterState>;
+}
+
+/// Only allows the moves of both FSTs along matching arcs : epsilons are matched as regular
+/// labels, without any FST moving alone. This is the filter of [`compose`](../fn.compose.html),
+/// for which composing epsilon-free FSTs has no overhead.
+#[derive(Debug, Clone, Default)]
+pub struct TrivialComposeFilter {}
+
+impl<W: Semiring> ComposeFilter<W> for TrivialComposeFilter {
+    type FilterState = ();
+
+    fn start(&self) -> Self::FilterState {}
+
+    fn set_state<F1, F2>(
+        &mut self,
+        _fst_1: &F1,
+        _s1: StateId,
+        _fst_2: &F2,
+        _s2: StateId,
+        _filter_state: &Self::FilterState,
+    ) -> Fallible<()>
+    where
+        F1: Fst<W = W>,
+        F2: Fst<W = W>,
+    {
+        Ok(())
+    }
+
+    fn filter_arc(
+        &self,
+        arc_1: Option<&Arc<W>>,
+        arc_2: Option<&Arc<W>>,
+    ) -> Option<Self::FilterState> {
+        match (arc_1, arc_2) {
+            (Some(_), Some(_)) => Some(()),
+            _ => None,
+        }
+    }
+}
+
+/// Allows all the moves but the ones matching an output epsilon of the first FST with an input
+/// epsilon of the second FST. The epsilons are always consumed one FST at a time, in any order,
+/// which yields redundant paths when both FSTs have epsilons at the same position.
+#[derive(Debug, Clone, Default)]
+pub struct NoMatchFilter {}
+
+impl<W: Semiring> ComposeFilter<W> for NoMatchFilter {
+    type FilterState = ();
+
+    fn start(&self) -> Self::FilterState {}
+
+    fn set_state<F1, F2>(
+        &mut self,
+        _fst_1: &F1,
+        _s1: StateId,
+        _fst_2: &F2,
+        _s2: StateId,
+        _filter_state: &Self::FilterState,
+    ) -> Fallible<()>
+    where
+        F1: Fst<W = W>,
+        F2: Fst<W = W>,
+    {
+        Ok(())
+    }
+
+    fn filter_arc(
+        &self,
+        arc_1: Option<&Arc<W>>,
+        arc_2: Option<&Arc<W>>,
+    ) -> Option<Self::FilterState> {
+        match (arc_1, arc_2) {
+            (Some(arc_1), Some(_)) if arc_1.olabel == EPS_LABEL => None,
+            (None, None) => None,
+            _ => Some(()),
+        }
+    }
+}
+
+/// Filter of OpenFST's default composition : when both FSTs have epsilons at the same position,
+/// the output epsilons of the first FST are consumed before the input epsilons of the second
+/// one, which leaves a single path. The filter state is `true` once the second FST has moved
+/// alone, the first FST being then no longer allowed to move alone.
+#[derive(Debug, Clone, Default)]
+pub struct SequenceComposeFilter {
+    filter_state: bool,
+    /// The first FST is in a non-final state whose arcs all have an output epsilon.
+    all_eps_1: bool,
+    /// The first FST is in a state without any arc with an output epsilon.
+    no_eps_1: bool,
+}
+
+impl SequenceComposeFilter {
+    /// Creates a filter, its state being set by `set_state`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl<W: Semiring> ComposeFilter<W> for SequenceComposeFilter {
+    type FilterState = bool;
+
+    fn start(&self) -> Self::FilterState {
+        false
+    }
+
+    fn set_state<F1, F2>(
+        &mut self,
+        fst_1: &F1,
+        s1: StateId,
+        _fst_2: &F2,
+        _s2: StateId,
+        filter_state: &Self::FilterState,
+    ) -> Fallible<()>
+    where
+        F1: Fst<W = W>,
+        F2: Fst<W = W>,
+    {
+        let mut all_eps = true;
+        let mut no_eps = true;
+        for arc in fst_1.arcs_iter(s1)? {
+            if arc.olabel == EPS_LABEL {
+                no_eps = false;
+            } else {
+                all_eps = false;
+            }
+        }
+        self.filter_state = *filter_state;
+        self.all_eps_1 = all_eps && !fst_1.is_final(s1)?;
+        self.no_eps_1 = no_eps;
+        Ok(())
+    }
+
+    fn filter_arc(
+        &self,
+        arc_1: Option<&Arc<W>>,
+        arc_2: Option<&Arc<W>>,
+    ) -> Option<Self::FilterState> {
+        match (arc_1, arc_2) {
+            // The second FST moves alone
+            (None, Some(_)) => {
+                if self.all_eps_1 {
+                    None
+                } else {
+                    Some(!self.no_eps_1)
+                }
+            }
+            // The first FST moves alone
+            (Some(_), None) => {
+                if self.filter_state {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            (Some(arc_1), Some(_)) => {
+                if arc_1.olabel == EPS_LABEL {
+                    None
+                } else {
+                    Some(false)
+                }
+            }
+            (None, None) => None,
+        }
+    }
+}
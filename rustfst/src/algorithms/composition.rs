@@ -3,16 +3,31 @@ use std::collections::HashMap;
 use std::collections::VecDeque;
 
 use failure::Fallible;
-use itertools::iproduct;
 
+use crate::algorithms::compose_filters::{ComposeFilter, TrivialComposeFilter};
+use crate::algorithms::{connect, PrecomputedMatcher};
 use crate::arc::Arc;
 use crate::fst_traits::{ExpandedFst, MutableFst};
 use crate::semirings::Semiring;
+use crate::EPS_LABEL;
 
 /// This operation computes the composition of two transducers.
 /// If `A` transduces string `x` to `y` with weight `a` and `B` transduces `y` to `z`
 /// with weight `b`, then their composition transduces string `x` to `z` with weight `a ⊗ b`.
 ///
+/// The states of the result are the pairs of states of `fst_1` and `fst_2` reachable from the pair
+/// of start states, the result being trimmed with `connect` so that only the states lying on a
+/// successful path remain. The composition is empty if either FST is empty.
+///
+/// Epsilons are matched as regular labels : this is
+/// [`compose_with_filter`](fn.compose_with_filter.html) with a `TrivialComposeFilter`, see
+/// the other filters to compose FSTs with epsilon transitions.
+///
+/// The output is fully determined by the inputs : the states are numbered in the order in which
+/// they are discovered by a breadth-first traversal from the pair of start states, and the arcs
+/// leaving a state follow the order of the arcs of `fst_1` and then the order of the arcs of
+/// `fst_2` matching each of them.
+///
 /// # Example
 /// ```
 /// # #[macro_use] extern crate rustfst;
@@ -38,7 +53,126 @@ where
     W: Semiring,
     F1: ExpandedFst<W = W>,
     F2: ExpandedFst<W = W>,
-    F3: MutableFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    compose_with_filter(fst_1, fst_2, TrivialComposeFilter {})
+}
+
+/// Computes the composition of two transducers whose weights are interpolated instead of being
+/// multiplied : the weights `w1` and `w2` of two matching arcs, or of two final states, are
+/// combined into `alpha * w1 + (1 - alpha) * w2`. Over the log or tropical semirings, this is the
+/// log-linear interpolation of the two models, computed in a single pass. The weight of a path
+/// is then the ⊗-product of the interpolated weights of its arcs.
+///
+/// `alpha` must lie in `[0, 1]`. An interpolated weight is `zero()` as soon as one of the weights
+/// is `zero()`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::compose_interpolated;
+/// # fn main() -> Fallible<()> {
+/// let fst_1 : VectorFst<TropicalWeight> = transducer(&[1], &[2], TropicalWeight::new(4.0));
+/// let fst_2 : VectorFst<TropicalWeight> = transducer(&[2], &[3], TropicalWeight::new(2.0));
+///
+/// let fst_ref : VectorFst<TropicalWeight> = transducer(&[1], &[3], TropicalWeight::new(2.5));
+///
+/// let composed_fst : VectorFst<_> = compose_interpolated(&fst_1, &fst_2, 0.25)?;
+/// assert_eq!(composed_fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn compose_interpolated<W, F1, F2, F3>(fst_1: &F1, fst_2: &F2, alpha: f32) -> Fallible<F3>
+where
+    W: Semiring<Type = f32>,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    ensure!(
+        (0.0..=1.0).contains(&alpha),
+        "The interpolation weight must lie in [0, 1], got {}",
+        alpha
+    );
+    compose_with_filter_impl(fst_1, fst_2, None, TrivialComposeFilter {}, |w1, w2| {
+        if w1.is_zero() || w2.is_zero() {
+            Ok(W::zero())
+        } else {
+            Ok(W::new(alpha * w1.value() + (1.0 - alpha) * w2.value()))
+        }
+    })
+}
+
+/// Computes the composition of two transducers with epsilon transitions, the epsilons being
+/// matched as in OpenFST instead of being treated as regular labels like
+/// [`compose`](fn.compose.html) does.
+///
+/// An FST can stay in its state while the other one moves on an epsilon : an output epsilon of
+/// `fst_1` or an input epsilon of `fst_2` yields an arc of the composition on which the other
+/// FST doesn't move. As this creates redundant paths, the moves allowed are restricted by
+/// `filter`, whose state is part of the states of the composition. With a
+/// `SequenceComposeFilter`, the composition has the same paths as OpenFST's default one.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{MutableFst, PathsIterator};
+/// # use rustfst::algorithms::compose_with_filter;
+/// # use rustfst::algorithms::compose_filters::SequenceComposeFilter;
+/// # use rustfst::{Arc, EPS_LABEL};
+/// # fn main() -> Fallible<()> {
+/// let mut fst_1 = VectorFst::new();
+/// fst_1.add_states(2);
+/// fst_1.set_start(0)?;
+/// fst_1.set_final(1, TropicalWeight::one())?;
+/// fst_1.add_arc(0, Arc::new(1, EPS_LABEL, TropicalWeight::new(1.0), 1))?;
+///
+/// let mut fst_2 = VectorFst::new();
+/// fst_2.add_states(2);
+/// fst_2.set_start(0)?;
+/// fst_2.set_final(1, TropicalWeight::one())?;
+/// fst_2.add_arc(0, Arc::new(EPS_LABEL, 2, TropicalWeight::new(2.0), 1))?;
+///
+/// let composed_fst: VectorFst<_> =
+///     compose_with_filter(&fst_1, &fst_2, SequenceComposeFilter::new())?;
+/// assert_eq!(composed_fst.paths_iter().count(), 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn compose_with_filter<W, F1, F2, F3, CF>(fst_1: &F1, fst_2: &F2, filter: CF) -> Fallible<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+    CF: ComposeFilter<W>,
+{
+    compose_with_filter_impl(fst_1, fst_2, None, filter, |w1, w2| w1.times(w2))
+}
+
+/// Composition with a filter in which the weights of matching arcs and final states are
+/// combined by `combine`. The arcs of `fst_2` matching an arc of `fst_1` are looked up in
+/// `matcher` when there is one. All the compositions are computed by this function.
+fn compose_with_filter_impl<W, F1, F2, F3, CF, C>(
+    fst_1: &F1,
+    fst_2: &F2,
+    matcher: Option<&PrecomputedMatcher<F2>>,
+    mut filter: CF,
+    combine: C,
+) -> Fallible<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+    CF: ComposeFilter<W>,
+    C: Fn(&W, &W) -> Fallible<W>,
 {
     let mut composed_fst = F3::new();
     let mut queue = VecDeque::new();
@@ -46,49 +180,495 @@ where
     let mut mapping_states = HashMap::new();
 
     if let (Some(state_state_1), Some(start_state_2)) = (fst_1.start(), fst_2.start()) {
+        let filter_state = filter.start();
         let start_state = composed_fst.add_state();
-        mapping_states.insert((state_state_1, start_state_2), start_state);
+        mapping_states.insert(
+            (state_state_1, start_state_2, filter_state.clone()),
+            start_state,
+        );
         composed_fst.set_start(start_state)?;
-        queue.push_back((state_state_1, start_state_2, start_state));
+        queue.push_back((state_state_1, start_state_2, filter_state, start_state));
     }
 
-    while !queue.is_empty() {
-        let (q1, q2, q) = queue.pop_front().unwrap();
-
+    while let Some((q1, q2, filter_state, q)) = queue.pop_front() {
         if let (Some(rho_1), Some(rho_2)) = (fst_1.final_weight(q1)?, fst_2.final_weight(q2)?) {
-            composed_fst.set_final(q, rho_1.times(&rho_2)?)?;
+            composed_fst.set_final(q, combine(rho_1, rho_2)?)?;
         }
 
-        let arcs_it1 = fst_1.arcs_iter(q1)?;
-        let arcs_it2 = fst_2.arcs_iter(q2)?;
+        filter.set_state(fst_1, q1, fst_2, q2, &filter_state)?;
 
-        for (arc_1, arc_2) in iproduct!(arcs_it1, arcs_it2) {
-            if arc_1.olabel == arc_2.ilabel {
-                let n1 = arc_1.nextstate;
-                let n2 = arc_2.nextstate;
-
-                let q_prime = match mapping_states.entry((n1, n2)) {
-                    Entry::Vacant(v) => {
-                        let q_prime = composed_fst.add_state();
-                        v.insert(q_prime);
-                        queue.push_back((n1, n2, q_prime));
-                        q_prime
+        // Moves of fst_1, alone on an output epsilon or along with fst_2, then moves of fst_2
+        // alone on an input epsilon.
+        let mut moves = vec![];
+        for arc_1 in fst_1.arcs_iter(q1)? {
+            if arc_1.olabel == EPS_LABEL {
+                moves.push((Some(arc_1), None));
+            }
+            match matcher {
+                Some(matcher) => {
+                    for arc_2 in matcher.find(q2, arc_1.olabel) {
+                        moves.push((Some(arc_1), Some(*arc_2)));
                     }
-                    Entry::Occupied(o) => *o.get(),
-                };
-
-                composed_fst.add_arc(
-                    q,
-                    Arc::new(
-                        arc_1.ilabel,
-                        arc_2.olabel,
-                        arc_1.weight.times(&arc_2.weight)?,
-                        q_prime,
-                    ),
-                )?;
+                }
+                None => {
+                    for arc_2 in fst_2.arcs_iter(q2)? {
+                        if arc_1.olabel == arc_2.ilabel {
+                            moves.push((Some(arc_1), Some(arc_2)));
+                        }
+                    }
+                }
+            }
+        }
+        for arc_2 in fst_2.arcs_iter(q2)? {
+            if arc_2.ilabel == EPS_LABEL {
+                moves.push((None, Some(arc_2)));
             }
         }
+
+        for (arc_1, arc_2) in moves {
+            let next_filter_state = match filter.filter_arc(arc_1, arc_2) {
+                Some(next_filter_state) => next_filter_state,
+                None => continue,
+            };
+            let n1 = arc_1.map_or(q1, |arc| arc.nextstate);
+            let n2 = arc_2.map_or(q2, |arc| arc.nextstate);
+
+            let q_prime = match mapping_states.entry((n1, n2, next_filter_state.clone())) {
+                Entry::Vacant(v) => {
+                    let q_prime = composed_fst.add_state();
+                    v.insert(q_prime);
+                    queue.push_back((n1, n2, next_filter_state, q_prime));
+                    q_prime
+                }
+                Entry::Occupied(o) => *o.get(),
+            };
+
+            let weight = match (arc_1, arc_2) {
+                (Some(arc_1), Some(arc_2)) => combine(&arc_1.weight, &arc_2.weight)?,
+                (Some(arc), None) | (None, Some(arc)) => arc.weight.clone(),
+                (None, None) => unreachable!(),
+            };
+            composed_fst.add_arc(
+                q,
+                Arc::new(
+                    arc_1.map_or(EPS_LABEL, |arc| arc.ilabel),
+                    arc_2.map_or(EPS_LABEL, |arc| arc.olabel),
+                    weight,
+                    q_prime,
+                ),
+            )?;
+        }
     }
 
+    connect(&mut composed_fst)?;
     Ok(composed_fst)
 }
+
+/// Computes the composition of `fst_1` with the FST indexed by `matcher`. The result is the same
+/// as the one of [`compose`](fn.compose.html) but the arcs of the second FST matching an arc of
+/// the first one are found in O(1) by the matcher. As the matcher is built once, it can be shared
+/// by many compositions against the same FST.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::{compose_with_matcher, PrecomputedMatcher};
+/// # fn main() -> Fallible<()> {
+/// let fst_1 : VectorFst<IntegerWeight> = fst![1,2 => 2,3];
+///
+/// let fst_2 : VectorFst<IntegerWeight> = fst![2,3 => 3,4];
+/// let matcher = PrecomputedMatcher::new(&fst_2);
+///
+/// let fst_ref : VectorFst<IntegerWeight> = fst![1,2 => 3,4];
+///
+/// let composed_fst : VectorFst<_> = compose_with_matcher(&fst_1, &matcher)?;
+/// assert_eq!(composed_fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn compose_with_matcher<W, F1, F2, F3>(
+    fst_1: &F1,
+    matcher: &PrecomputedMatcher<F2>,
+) -> Fallible<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    compose_with_filter_impl(
+        fst_1,
+        matcher.fst(),
+        Some(matcher),
+        TrivialComposeFilter {},
+        |w1, w2| w1.times(w2),
+    )
+}
+
+/// Computes the composition `fsts[0] ∘ fsts[1] ∘ ... ∘ fsts[n - 1]` of a list of FSTs.
+///
+/// Composition is associative but the sizes of the intermediate FSTs depend on the order in
+/// which the FSTs are composed. Without `reorder`, they are composed from left to right.
+/// With it, the order is picked as in the matrix chain ordering problem, the number of states of
+/// the composition of two FSTs being estimated by the product of their numbers of states : the
+/// order minimizing the total estimated size of the intermediate FSTs is used. The result
+/// accepts the same paths in both cases but its states may be numbered differently.
+///
+/// An error is returned if the list is empty.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::compose_chain;
+/// # fn main() -> Fallible<()> {
+/// let fsts : Vec<VectorFst<IntegerWeight>> = vec![
+///     fst![1,2 => 2,3],
+///     fst![2,3 => 3,4],
+///     fst![3,4 => 5,6],
+/// ];
+///
+/// let fst_ref : VectorFst<IntegerWeight> = fst![1,2 => 5,6];
+///
+/// assert_eq!(compose_chain(&fsts, false)?, fst_ref);
+/// assert_eq!(compose_chain(&fsts, true)?, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn compose_chain<W, F>(fsts: &[F], reorder: bool) -> Fallible<F>
+where
+    W: Semiring,
+    F: ExpandedFst<W = W> + MutableFst<W = W>,
+{
+    ensure!(!fsts.is_empty(), "Composing an empty list of FSTs");
+
+    if !reorder {
+        let mut composed_fst = fsts[0].clone();
+        for fst in &fsts[1..] {
+            composed_fst = compose(&composed_fst, fst)?;
+        }
+        return Ok(composed_fst);
+    }
+
+    // split[i][j] is the position at which fsts[i..=j] is best split in two.
+    let n = fsts.len();
+    let sizes: Vec<f64> = fsts.iter().map(|f| f.num_states() as f64).collect();
+    let mut cost = vec![vec![0.0; n]; n];
+    let mut size = vec![vec![0.0; n]; n];
+    let mut split = vec![vec![0; n]; n];
+    for i in 0..n {
+        size[i][i] = sizes[i];
+    }
+    for len in 2..=n {
+        for i in 0..=(n - len) {
+            let j = i + len - 1;
+            size[i][j] = size[i][j - 1] * sizes[j];
+            cost[i][j] = std::f64::INFINITY;
+            for k in i..j {
+                let c = cost[i][k] + cost[k + 1][j] + size[i][j];
+                if c < cost[i][j] {
+                    cost[i][j] = c;
+                    split[i][j] = k;
+                }
+            }
+        }
+    }
+
+    compose_range(fsts, &split, 0, n - 1)
+}
+
+fn compose_range<W, F>(fsts: &[F], split: &[Vec<usize>], i: usize, j: usize) -> Fallible<F>
+where
+    W: Semiring,
+    F: ExpandedFst<W = W> + MutableFst<W = W>,
+{
+    if i == j {
+        return Ok(fsts[i].clone());
+    }
+    let k = split[i][j];
+    let left = compose_range(fsts, split, i, k)?;
+    let right = compose_range(fsts, split, k + 1, j)?;
+    compose(&left, &right)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::compose_filters::{NoMatchFilter, SequenceComposeFilter};
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst, Fst, PathsIterator, StateIterator};
+    use crate::semirings::TropicalWeight;
+    use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+    use crate::utils::acceptor;
+    use crate::FstPath;
+
+    #[test]
+    fn test_compose_with_shared_matcher() -> Fallible<()> {
+        // Lexicon mapping words to their pronunciations, word 1 having two of them
+        let mut lexicon = VectorFst::new();
+        let s0 = lexicon.add_state();
+        let s1 = lexicon.add_state();
+        lexicon.set_start(s0)?;
+        lexicon.set_final(s0, TropicalWeight::one())?;
+        lexicon.add_arc(s0, Arc::new(1, 10, TropicalWeight::new(1.0), s0))?;
+        lexicon.add_arc(s0, Arc::new(2, 12, TropicalWeight::new(2.0), s0))?;
+        lexicon.add_arc(s0, Arc::new(1, 13, TropicalWeight::new(3.0), s0))?;
+        lexicon.add_arc(s0, Arc::new(3, 10, TropicalWeight::new(0.5), s1))?;
+        lexicon.add_arc(s1, Arc::new(4, 11, TropicalWeight::new(0.5), s0))?;
+
+        let matcher = PrecomputedMatcher::new(&lexicon);
+
+        let sentences: Vec<VectorFst<TropicalWeight>> = vec![
+            acceptor(&[1, 2], TropicalWeight::one()),
+            acceptor(&[3, 4, 1], TropicalWeight::new(3.0)),
+            acceptor(&[2, 5], TropicalWeight::one()),
+        ];
+
+        for sentence in sentences.iter() {
+            let fst_ref: VectorFst<_> = compose(sentence, &lexicon)?;
+            let fst: VectorFst<_> = compose_with_matcher(sentence, &matcher)?;
+            assert_eq!(fst, fst_ref);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_chain() -> Fallible<()> {
+        // Large first FST followed by two small ones : composing the small ones first is cheaper
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(4);
+        fst_1.set_start(0)?;
+        fst_1.set_final(3, TropicalWeight::one())?;
+        for s in 0..3 {
+            fst_1.add_arc(s, Arc::new(1, 1, TropicalWeight::new(1.0), s + 1))?;
+            fst_1.add_arc(s, Arc::new(2, 2, TropicalWeight::new(2.0), s + 1))?;
+        }
+
+        let mut fst_2 = VectorFst::new();
+        let s0 = fst_2.add_state();
+        fst_2.set_start(s0)?;
+        fst_2.set_final(s0, TropicalWeight::new(0.5))?;
+        fst_2.add_arc(s0, Arc::new(1, 3, TropicalWeight::new(0.5), s0))?;
+        fst_2.add_arc(s0, Arc::new(2, 4, TropicalWeight::new(1.5), s0))?;
+
+        let mut fst_3 = VectorFst::new();
+        let s0 = fst_3.add_state();
+        fst_3.set_start(s0)?;
+        fst_3.set_final(s0, TropicalWeight::one())?;
+        fst_3.add_arc(s0, Arc::new(3, 5, TropicalWeight::new(0.25), s0))?;
+        fst_3.add_arc(s0, Arc::new(4, 6, TropicalWeight::one(), s0))?;
+
+        let fsts = vec![fst_1, fst_2, fst_3];
+        let fst_01: VectorFst<_> = compose(&fsts[0], &fsts[1])?;
+        let fst_ref: VectorFst<_> = compose(&fst_01, &fsts[2])?;
+
+        assert_eq!(compose_chain(&fsts, false)?, fst_ref);
+
+        let fst = compose_chain(&fsts, true)?;
+        let mut paths: Vec<_> = fst.paths_iter().collect();
+        let mut paths_ref: Vec<_> = fst_ref.paths_iter().collect();
+        paths.sort_by_key(|p| p.ilabels.clone());
+        paths_ref.sort_by_key(|p| p.ilabels.clone());
+        assert_eq!(paths, paths_ref);
+
+        assert!(compose_chain::<TropicalWeight, VectorFst<_>>(&[], true).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_output_order() -> Fallible<()> {
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(3);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, TropicalWeight::new(1.0))?;
+        fst_1.set_final(2, TropicalWeight::new(2.0))?;
+        fst_1.add_arc(0, Arc::new(1, 2, TropicalWeight::one(), 1))?;
+        fst_1.add_arc(0, Arc::new(1, 3, TropicalWeight::one(), 2))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(2);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, TropicalWeight::new(0.5))?;
+        fst_2.add_arc(0, Arc::new(3, 5, TropicalWeight::new(3.0), 1))?;
+        fst_2.add_arc(0, Arc::new(2, 4, TropicalWeight::new(4.0), 1))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(0)?;
+        fst_ref.add_arc(0, Arc::new(1, 4, TropicalWeight::new(4.0), 1))?;
+        fst_ref.add_arc(0, Arc::new(1, 5, TropicalWeight::new(3.0), 2))?;
+        fst_ref.set_final(1, TropicalWeight::new(1.5))?;
+        fst_ref.set_final(2, TropicalWeight::new(2.5))?;
+
+        let fst: VectorFst<_> = compose(&fst_1, &fst_2)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_interpolated() -> Fallible<()> {
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(2);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, TropicalWeight::new(1.0))?;
+        fst_1.add_arc(0, Arc::new(1, 2, TropicalWeight::new(2.0), 1))?;
+        fst_1.add_arc(0, Arc::new(1, 3, TropicalWeight::new(4.0), 1))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(2);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, TropicalWeight::new(3.0))?;
+        fst_2.add_arc(0, Arc::new(2, 4, TropicalWeight::new(6.0), 1))?;
+        fst_2.add_arc(0, Arc::new(3, 5, TropicalWeight::zero(), 1))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(2);
+        fst_ref.set_start(0)?;
+        fst_ref.set_final(1, TropicalWeight::new(0.4 * 1.0 + 0.6 * 3.0))?;
+        fst_ref.add_arc(
+            0,
+            Arc::new(1, 4, TropicalWeight::new(0.4 * 2.0 + 0.6 * 6.0), 1),
+        )?;
+        fst_ref.add_arc(0, Arc::new(1, 5, TropicalWeight::zero(), 1))?;
+
+        let fst: VectorFst<_> = compose_interpolated(&fst_1, &fst_2, 0.4)?;
+        assert_eq!(fst, fst_ref);
+
+        let fst: VectorFst<_> = compose_interpolated(&fst_1, &fst_2, 1.0)?;
+        assert_eq!(fst.final_weight(1)?, Some(&TropicalWeight::new(1.0)));
+
+        assert!(compose_interpolated::<_, _, _, VectorFst<_>>(&fst_1, &fst_2, 1.5).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_connect() -> Fallible<()> {
+        // The arc 1:3 of the first FST only leads to a dead end of the composition
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(3);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, TropicalWeight::one())?;
+        fst_1.add_arc(0, Arc::new(1, 3, TropicalWeight::new(1.0), 2))?;
+        fst_1.add_arc(0, Arc::new(1, 2, TropicalWeight::new(2.0), 1))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(3);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, TropicalWeight::one())?;
+        fst_2.add_arc(0, Arc::new(3, 5, TropicalWeight::one(), 2))?;
+        fst_2.add_arc(0, Arc::new(2, 4, TropicalWeight::new(0.5), 1))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(2);
+        fst_ref.set_start(0)?;
+        fst_ref.set_final(1, TropicalWeight::one())?;
+        fst_ref.add_arc(0, Arc::new(1, 4, TropicalWeight::new(2.5), 1))?;
+
+        let fst: VectorFst<_> = compose(&fst_1, &fst_2)?;
+        assert_eq!(fst, fst_ref);
+
+        let fst: VectorFst<_> = compose(&fst_1, &VectorFst::new())?;
+        assert_eq!(fst, VectorFst::new());
+        let fst: VectorFst<_> = compose(&VectorFst::new(), &fst_2)?;
+        assert_eq!(fst, VectorFst::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_with_filter_epsilons() -> Fallible<()> {
+        // Output epsilons in the first FST facing input epsilons in the second one
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(4);
+        fst_1.set_start(0)?;
+        fst_1.set_final(3, TropicalWeight::one())?;
+        fst_1.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst_1.add_arc(1, Arc::new(2, EPS_LABEL, TropicalWeight::new(2.0), 2))?;
+        fst_1.add_arc(2, Arc::new(3, 3, TropicalWeight::new(3.0), 3))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(4);
+        fst_2.set_start(0)?;
+        fst_2.set_final(3, TropicalWeight::one())?;
+        fst_2.add_arc(0, Arc::new(1, 4, TropicalWeight::new(0.5), 1))?;
+        fst_2.add_arc(1, Arc::new(EPS_LABEL, 5, TropicalWeight::new(0.25), 2))?;
+        fst_2.add_arc(2, Arc::new(3, 6, TropicalWeight::new(0.125), 3))?;
+
+        // As OpenFST, a single path consuming the epsilon of the first FST first
+        let fst: VectorFst<_> = compose_with_filter(&fst_1, &fst_2, SequenceComposeFilter::new())?;
+        let paths: Vec<_> = fst.paths_iter().collect();
+        assert_eq!(
+            paths,
+            vec![FstPath::new(
+                vec![1, 2, 3],
+                vec![4, 5, 6],
+                TropicalWeight::new(6.875)
+            )]
+        );
+        let labels: Vec<_> = fst
+            .arcs_iter(1)?
+            .map(|arc| (arc.ilabel, arc.olabel))
+            .collect();
+        assert_eq!(labels, vec![(2, EPS_LABEL)]);
+
+        // The epsilons can be consumed in both orders
+        let fst: VectorFst<_> = compose_with_filter(&fst_1, &fst_2, NoMatchFilter {})?;
+        let paths: Vec<_> = fst.paths_iter().collect();
+        assert_eq!(paths.len(), 2);
+        for path in paths {
+            assert_eq!(path.ilabels, vec![1, 2, 3]);
+            assert_eq!(path.olabels, vec![4, 5, 6]);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_compose_sequence_filter_without_epsilons() -> Fallible<()> {
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(2);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, TropicalWeight::new(0.5))?;
+        fst_1.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        fst_1.add_arc(0, Arc::new(2, 3, TropicalWeight::new(2.0), 0))?;
+        fst_1.add_arc(1, Arc::new(3, 2, TropicalWeight::new(3.0), 0))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(2);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, TropicalWeight::one())?;
+        fst_2.add_arc(0, Arc::new(2, 4, TropicalWeight::new(0.25), 1))?;
+        fst_2.add_arc(0, Arc::new(3, 5, TropicalWeight::new(0.5), 0))?;
+        fst_2.add_arc(1, Arc::new(2, 6, TropicalWeight::new(0.75), 0))?;
+        fst_2.add_arc(1, Arc::new(3, 7, TropicalWeight::one(), 1))?;
+
+        let fst: VectorFst<_> = compose(&fst_1, &fst_2)?;
+        assert_ne!(fst.num_states(), 0);
+        let fst_sequence: VectorFst<_> =
+            compose_with_filter(&fst_1, &fst_2, SequenceComposeFilter::new())?;
+        assert_eq!(fst_sequence, fst);
+
+        // The label sequences of the test FSTs, which have no epsilon, composed with themselves
+        for data in get_vector_fsts_for_tests() {
+            let fst_test = &data.fst;
+            let has_epsilons = fst_test.states_iter().any(|s| {
+                fst_test.num_input_epsilons(s).unwrap() > 0
+                    || fst_test.num_output_epsilons(s).unwrap() > 0
+            });
+            if has_epsilons {
+                continue;
+            }
+            let fst: VectorFst<_> = compose(fst_test, fst_test)?;
+            let fst_sequence: VectorFst<_> =
+                compose_with_filter(fst_test, fst_test, SequenceComposeFilter::new())?;
+            assert_eq!(fst_sequence, fst, "Test failing for {:?}", &data.name);
+        }
+        Ok(())
+    }
+}
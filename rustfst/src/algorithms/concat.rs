@@ -9,6 +9,10 @@ use crate::EPS_LABEL;
 /// and `B` transduces string `w` to `v` with weight `b`, then their concatenation
 /// transduces string `xw` to `yv` with weight `a ⊗ b`.
 ///
+/// The output is fully determined by the inputs : the states of `fst_1` keep their ids and are
+/// followed by the states of `fst_2` in their original order. Each state keeps its arcs in their
+/// original order, the epsilon arc leaving a final state of `fst_1` being added after them.
+///
 /// # Example
 /// ```
 /// # #[macro_use] extern crate rustfst;
@@ -88,11 +92,25 @@ mod tests {
     use failure::ResultExt;
     use itertools::Itertools;
 
+    use proptest::prelude::*;
+
     use crate::fst_impls::VectorFst;
     use crate::fst_traits::PathsIterator;
+    use crate::proptest_fst::proptest_acyclic_fst;
     use crate::semirings::IntegerWeight;
     use crate::test_data::vector_fst::get_vector_fsts_for_tests;
 
+    proptest! {
+        #[test]
+        fn test_concat_proptest(fst_1 in proptest_acyclic_fst(), fst_2 in proptest_acyclic_fst()) {
+            let fst: VectorFst<_> = concat(&fst_1, &fst_2).unwrap();
+            prop_assert_eq!(
+                fst.paths_iter().count(),
+                fst_1.paths_iter().count() * fst_2.paths_iter().count()
+            );
+        }
+    }
+
     #[test]
     fn test_concat_generic() -> Fallible<()> {
         for data in get_vector_fsts_for_tests().combinations(2) {
@@ -126,4 +144,33 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_concat_output_order() -> Fallible<()> {
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(2);
+        fst_1.set_start(0)?;
+        fst_1.set_final(1, IntegerWeight::new(3))?;
+        fst_1.add_arc(0, Arc::new(1, 2, IntegerWeight::new(2), 1))?;
+        fst_1.add_arc(1, Arc::new(5, 5, IntegerWeight::one(), 1))?;
+
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(2);
+        fst_2.set_start(0)?;
+        fst_2.set_final(1, IntegerWeight::new(7))?;
+        fst_2.add_arc(0, Arc::new(3, 4, IntegerWeight::new(5), 1))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(4);
+        fst_ref.set_start(0)?;
+        fst_ref.add_arc(0, Arc::new(1, 2, IntegerWeight::new(2), 1))?;
+        fst_ref.add_arc(1, Arc::new(5, 5, IntegerWeight::one(), 1))?;
+        fst_ref.add_arc(1, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(3), 2))?;
+        fst_ref.add_arc(2, Arc::new(3, 4, IntegerWeight::new(5), 3))?;
+        fst_ref.set_final(3, IntegerWeight::new(7))?;
+
+        let fst: VectorFst<_> = concat(&fst_1, &fst_2)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
 }
@@ -0,0 +1,120 @@
+use unsafe_unwrap::UnsafeUnwrap;
+
+use crate::algorithms::dfs_visit::dfs_visit;
+use crate::algorithms::visitors::SccVisitor;
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::{BooleanWeight, Semiring};
+use crate::EPS_LABEL;
+
+/// Computes the strongly connected components of an FST and returns the component of each state
+/// along with the condensation of the FST : a DAG with one state per component and an epsilon
+/// arc from a component to another one if at least one arc of the FST goes from the first to
+/// the second. The state of the DAG matching a component has the id of that component, the
+/// start state is the component of the start state of the FST and the final states are the
+/// components containing a final state.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{BooleanWeight, Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+/// # use rustfst::algorithms::condensation;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0).unwrap();
+/// fst.set_final(s1, TropicalWeight::one()).unwrap();
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1)).unwrap();
+/// fst.add_arc(s1, Arc::new(2, 2, TropicalWeight::one(), s0)).unwrap();
+///
+/// let (scc, dag) = condensation(&fst);
+/// assert_eq!(scc, vec![0, 0]);
+/// assert_eq!(dag.num_states(), 1);
+/// assert_eq!(dag.final_weight(0).unwrap(), Some(&BooleanWeight::one()));
+/// ```
+pub fn condensation<F: ExpandedFst>(fst: &F) -> (Vec<i32>, VectorFst<BooleanWeight>) {
+    let mut scc_visitor = SccVisitor::new(fst, true, false);
+    dfs_visit(fst, &mut scc_visitor, false);
+    let n_sccs = scc_visitor.nscc as usize;
+    let scc = unsafe { scc_visitor.scc.unsafe_unwrap() };
+
+    let mut dag = VectorFst::new();
+    dag.add_states(n_sccs);
+    if let Some(start) = fst.start() {
+        unsafe { dag.set_start_unchecked(scc[start] as usize) };
+    }
+
+    let mut edges = vec![];
+    for s in 0..fst.num_states() {
+        let c = scc[s] as usize;
+        if unsafe { fst.is_final_unchecked(s) } {
+            unsafe { dag.set_final_unchecked(c, BooleanWeight::one()) };
+        }
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            let next_c = scc[arc.nextstate] as usize;
+            if next_c != c {
+                edges.push((c, next_c));
+            }
+        }
+    }
+    edges.sort();
+    edges.dedup();
+    for (c, next_c) in edges {
+        unsafe {
+            dag.add_arc_unchecked(
+                c,
+                Arc::new(EPS_LABEL, EPS_LABEL, BooleanWeight::one(), next_c),
+            )
+        };
+    }
+
+    (scc, dag)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{ArcIterator, CoreFst};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_condensation() -> Fallible<()> {
+        // Components {0, 1} and {2, 3} linked by the arc 1 -> 2
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, TropicalWeight::new(1.0), 0))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(1.0), 2))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::new(1.0), 3))?;
+        fst.add_arc(3, Arc::new(5, 5, TropicalWeight::new(1.0), 2))?;
+
+        let (scc, dag) = condensation(&fst);
+
+        assert_eq!(scc[0], scc[1]);
+        assert_eq!(scc[2], scc[3]);
+        assert_ne!(scc[0], scc[2]);
+
+        let c0 = scc[0] as usize;
+        let c1 = scc[2] as usize;
+        assert_eq!(dag.num_states(), 2);
+        assert_eq!(dag.start(), Some(c0));
+        assert!(dag.is_final(c1)?);
+        assert!(!dag.is_final(c0)?);
+        assert_eq!(
+            dag.arcs_iter(c0)?.cloned().collect::<Vec<_>>(),
+            vec![Arc::new(EPS_LABEL, EPS_LABEL, BooleanWeight::one(), c1)]
+        );
+        assert_eq!(dag.num_arcs(c1)?, 0);
+        assert!(dag.properties()?.contains(FstProperties::ACYCLIC));
+        Ok(())
+    }
+}
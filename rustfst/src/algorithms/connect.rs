@@ -6,8 +6,26 @@ use crate::fst_traits::Fst;
 use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst};
 use crate::Arc;
 use crate::StateId;
+use crate::EPS_LABEL;
 use crate::NO_STATE_ID;
 
+/// Options of `connect_with_config`.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct ConnectConfig {
+    /// Whether the states with an epsilon self-loop, i.e. an arc to themselves with epsilon as
+    /// both input and output label, are considered coaccessible even when no final state can be
+    /// reached from them. Such states are kept when they are accessible, along with the states
+    /// on the paths leading to them.
+    pub keep_epsilon_self_loops: bool,
+}
+
+impl ConnectConfig {
+    /// Creates a configuration trimming the FST like `connect`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
 /// This operation trims an FST, removing states and arcs that are not on successful paths.
 ///
 /// # Example
@@ -30,7 +48,46 @@ use crate::NO_STATE_ID;
 /// assert_eq!(connected_fst, fst);
 /// ```
 pub fn connect<F: ExpandedFst + MutableFst>(fst: &mut F) -> Fallible<()> {
+    connect_with_config(fst, ConnectConfig::new())
+}
+
+/// Same as `connect` with the options of `config`.
+///
+/// Setting `keep_epsilon_self_loops` preserves the accessible states carrying an epsilon
+/// self-loop, e.g. in formalisms where such a loop is meaningful, even when they have been
+/// disconnected from the final states by previous edits.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::{connect_with_config, ConnectConfig};
+/// # use rustfst::fst_traits::{MutableFst, ExpandedFst};
+/// # use rustfst::{Arc, EPS_LABEL};
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::<IntegerWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_arc(s0, Arc::new(1, 1, IntegerWeight::one(), s1))?;
+/// fst.add_arc(s1, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::one(), s1))?;
+///
+/// let config = ConnectConfig {
+///     keep_epsilon_self_loops: true,
+/// };
+/// connect_with_config(&mut fst, config)?;
+///
+/// assert_eq!(fst.num_states(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn connect_with_config<F: ExpandedFst + MutableFst>(
+    fst: &mut F,
+    config: ConnectConfig,
+) -> Fallible<()> {
     let mut visitor = ConnectVisitor::new(fst);
+    visitor.keep_epsilon_self_loops = config.keep_epsilon_self_loops;
     dfs_visit(fst, &mut visitor, false);
     let mut dstates = Vec::with_capacity(visitor.access.len());
     for s in 0..visitor.access.len() {
@@ -42,6 +99,111 @@ pub fn connect<F: ExpandedFst + MutableFst>(fst: &mut F) -> Fallible<()> {
     Ok(())
 }
 
+/// This operation trims an FST like `connect` but without renumbering the states : the states
+/// that are not on a successful path are kept in place, their arcs and final weights being
+/// removed, as well as the arcs reaching them. The `StateId`s of the remaining states are thus
+/// left unchanged.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::connect_keep_ids;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst, ExpandedFst};
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::<IntegerWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+/// fst.set_start(s0).unwrap();
+/// fst.set_final(s2, IntegerWeight::one()).unwrap();
+/// fst.add_arc(s0, Arc::new(1, 1, IntegerWeight::one(), s1)).unwrap();
+/// fst.add_arc(s0, Arc::new(2, 2, IntegerWeight::one(), s2)).unwrap();
+///
+/// connect_keep_ids(&mut fst).unwrap();
+///
+/// assert_eq!(fst.num_states(), 3);
+/// assert_eq!(fst.num_arcs(s0).unwrap(), 1);
+/// assert!(fst.is_final(s2).unwrap());
+/// ```
+pub fn connect_keep_ids<F: ExpandedFst + MutableFst>(fst: &mut F) -> Fallible<()> {
+    let mut visitor = ConnectVisitor::new(fst);
+    dfs_visit(fst, &mut visitor, false);
+    let dead: Vec<bool> = visitor
+        .access
+        .iter()
+        .zip(visitor.coaccess.iter())
+        .map(|(access, coaccess)| !access || !coaccess)
+        .collect();
+    for (s, is_dead) in dead.iter().enumerate() {
+        if *is_dead {
+            fst.delete_arcs(s)?;
+            fst.delete_final_weight(s)?;
+        } else {
+            let arcs = fst.pop_arcs(s)?;
+            for arc in arcs.into_iter().filter(|arc| !dead[arc.nextstate]) {
+                fst.add_arc(s, arc)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// This operation removes the states from which no final state can be reached (the
+/// non-coaccessible states) and the arcs leaving or reaching them. Unlike `connect`, the states
+/// that are not reachable from the start state are kept. The coaccessible states are found by a
+/// backward search from the final states, so that the start state isn't needed.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::trim_non_coaccessible;
+/// # use rustfst::fst_traits::{MutableFst, ExpandedFst};
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::<IntegerWeight>::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+/// fst.set_start(s0).unwrap();
+/// fst.set_final(s1, IntegerWeight::one()).unwrap();
+/// fst.add_arc(s0, Arc::new(1, 1, IntegerWeight::one(), s1)).unwrap();
+/// fst.add_arc(s0, Arc::new(2, 2, IntegerWeight::one(), s2)).unwrap();
+///
+/// trim_non_coaccessible(&mut fst).unwrap();
+///
+/// assert_eq!(fst.num_states(), 2);
+/// ```
+pub fn trim_non_coaccessible<F: ExpandedFst + MutableFst>(fst: &mut F) -> Fallible<()> {
+    let mut predecessors = vec![vec![]; fst.num_states()];
+    for s in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            predecessors[arc.nextstate].push(s);
+        }
+    }
+
+    let mut coaccess = vec![false; fst.num_states()];
+    let mut stack = vec![];
+    for s in 0..fst.num_states() {
+        if unsafe { fst.is_final_unchecked(s) } {
+            coaccess[s] = true;
+            stack.push(s);
+        }
+    }
+    while let Some(s) = stack.pop() {
+        for p in &predecessors[s] {
+            if !coaccess[*p] {
+                coaccess[*p] = true;
+                stack.push(*p);
+            }
+        }
+    }
+
+    let dstates: Vec<_> = (0..fst.num_states()).filter(|s| !coaccess[*s]).collect();
+    fst.del_states(dstates)?;
+    Ok(())
+}
+
 struct ConnectVisitor<'a, F: Fst> {
     access: Vec<bool>,
     coaccess: Vec<bool>,
@@ -52,6 +214,7 @@ struct ConnectVisitor<'a, F: Fst> {
     lowlink: Vec<i32>,
     onstack: Vec<bool>,
     scc_stack: Vec<StateId>,
+    keep_epsilon_self_loops: bool,
 }
 
 impl<'a, F: 'a + Fst + ExpandedFst> ConnectVisitor<'a, F> {
@@ -67,6 +230,7 @@ impl<'a, F: 'a + Fst + ExpandedFst> ConnectVisitor<'a, F> {
             lowlink: vec![-1; n],
             onstack: vec![false; n],
             scc_stack: vec![],
+            keep_epsilon_self_loops: false,
         }
     }
 }
@@ -123,6 +287,12 @@ impl<'a, F: 'a + ExpandedFst> Visitor<'a, F> for ConnectVisitor<'a, F> {
         if unsafe { self.fst.is_final_unchecked(s) } {
             self.coaccess[s] = true;
         }
+        if self.keep_epsilon_self_loops
+            && unsafe { self.fst.arcs_iter_unchecked(s) }
+                .any(|arc| arc.nextstate == s && arc.ilabel == EPS_LABEL && arc.olabel == EPS_LABEL)
+        {
+            self.coaccess[s] = true;
+        }
         if self.dfnumber[s] == self.lowlink[s] {
             let mut scc_coaccess = false;
             let mut i = self.scc_stack.len();
@@ -169,7 +339,9 @@ mod tests {
 
     use crate::proptest_fst::proptest_fst;
 
+    use crate::fst_impls::VectorFst;
     use crate::fst_properties::FstProperties;
+    use crate::semirings::{Semiring, TropicalWeight};
 
     use proptest::prelude::*;
 
@@ -185,6 +357,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_trim_non_coaccessible() -> Fallible<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, TropicalWeight::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(1.0), s1))?;
+        // Dead state : only a self-loop, no path to a final state.
+        fst.add_arc(s0, Arc::new(2, 2, TropicalWeight::new(2.0), s2))?;
+        fst.add_arc(s2, Arc::new(3, 3, TropicalWeight::new(3.0), s2))?;
+        // Unreachable from the start state but coaccessible.
+        fst.add_arc(s3, Arc::new(4, 4, TropicalWeight::new(4.0), s1))?;
+
+        trim_non_coaccessible(&mut fst)?;
+
+        let mut ref_fst = VectorFst::<TropicalWeight>::new();
+        let s0 = ref_fst.add_state();
+        let s1 = ref_fst.add_state();
+        let s2 = ref_fst.add_state();
+        ref_fst.set_start(s0)?;
+        ref_fst.set_final(s1, TropicalWeight::one())?;
+        ref_fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(1.0), s1))?;
+        ref_fst.add_arc(s2, Arc::new(4, 4, TropicalWeight::new(4.0), s1))?;
+
+        assert_eq!(fst, ref_fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_trim_non_coaccessible_no_start() -> Fallible<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(3);
+        fst.set_final(1, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(2, Arc::new(2, 2, TropicalWeight::new(2.0), 2))?;
+
+        trim_non_coaccessible(&mut fst)?;
+
+        let mut ref_fst = VectorFst::<TropicalWeight>::new();
+        ref_fst.add_states(2);
+        ref_fst.set_final(1, TropicalWeight::one())?;
+        ref_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+
+        assert_eq!(fst, ref_fst);
+        Ok(())
+    }
+
+    #[test]
+    fn test_connect_keep_ids() -> Fallible<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(0.5))?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 2))?;
+        // Not coaccessible.
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 1))?;
+        // Not accessible.
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(3, Arc::new(4, 4, TropicalWeight::new(4.0), 2))?;
+        // On a successful path.
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(5.0), 4))?;
+        fst.add_arc(4, Arc::new(6, 6, TropicalWeight::new(6.0), 2))?;
+
+        connect_keep_ids(&mut fst)?;
+
+        let mut ref_fst = VectorFst::<TropicalWeight>::new();
+        ref_fst.add_states(5);
+        ref_fst.set_start(0)?;
+        ref_fst.set_final(2, TropicalWeight::new(0.5))?;
+        ref_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 2))?;
+        ref_fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(5.0), 4))?;
+        ref_fst.add_arc(4, Arc::new(6, 6, TropicalWeight::new(6.0), 2))?;
+
+        assert_eq!(fst, ref_fst);
+        Ok(())
+    }
+
     #[test]
     fn test_connect_generic() -> Fallible<()> {
         for data in get_vector_fsts_for_tests() {
@@ -201,4 +454,52 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_connect_keep_epsilon_self_loops() -> Fallible<()> {
+        let mut fst = VectorFst::<TropicalWeight>::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.set_final(1, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        // No path to a final state but an epsilon self-loop, reached through state 3.
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 3))?;
+        fst.add_arc(3, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+        fst.add_arc(
+            2,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(4.0), 2),
+        )?;
+        // A self-loop which is not an epsilon self-loop.
+        fst.add_arc(0, Arc::new(5, 5, TropicalWeight::new(5.0), 4))?;
+        fst.add_arc(4, Arc::new(EPS_LABEL, 6, TropicalWeight::new(6.0), 4))?;
+
+        let mut connected_fst = fst.clone();
+        connect(&mut connected_fst)?;
+
+        let mut ref_fst = VectorFst::<TropicalWeight>::new();
+        ref_fst.add_states(2);
+        ref_fst.set_start(0)?;
+        ref_fst.set_final(1, TropicalWeight::one())?;
+        ref_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        assert_eq!(connected_fst, ref_fst);
+
+        let config = ConnectConfig {
+            keep_epsilon_self_loops: true,
+        };
+        connect_with_config(&mut fst, config)?;
+
+        let mut ref_fst = VectorFst::<TropicalWeight>::new();
+        ref_fst.add_states(4);
+        ref_fst.set_start(0)?;
+        ref_fst.set_final(1, TropicalWeight::one())?;
+        ref_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        ref_fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 3))?;
+        ref_fst.add_arc(3, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+        ref_fst.add_arc(
+            2,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(4.0), 2),
+        )?;
+        assert_eq!(fst, ref_fst);
+        Ok(())
+    }
 }
@@ -0,0 +1,64 @@
+use failure::Fallible;
+
+use crate::algorithms::fst_convert;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+
+/// Copies an FST into another FST implementation over the same semiring, e.g. to move from a
+/// `ConstFst` to a `VectorFst`. The state ids are preserved.
+///
+/// This is an alias of [`fst_convert`](fn.fst_convert.html) returning a `Fallible`, like the
+/// other conversions between FST implementations.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::{ConstFst, VectorFst};
+/// # use rustfst::algorithms::convert_fst;
+/// # fn main() -> Fallible<()> {
+/// let fst : VectorFst<IntegerWeight> = fst![1, 2 => 3, 4];
+/// let const_fst : ConstFst<IntegerWeight> = fst.clone().into();
+///
+/// let converted_fst : VectorFst<IntegerWeight> = convert_fst(&const_fst)?;
+/// assert_eq!(converted_fst, fst);
+/// # Ok(())
+/// # }
+/// ```
+pub fn convert_fst<FI, FO>(fst: &FI) -> Fallible<FO>
+where
+    FI: ExpandedFst,
+    FO: MutableFst<W = FI::W> + ExpandedFst,
+{
+    Ok(fst_convert(fst))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_convert_fst() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(1)?;
+        fst.set_final(2, TropicalWeight::new(0.5))?;
+        fst.add_arc(1, Arc::new(1, 2, TropicalWeight::new(1.0), 0))?;
+        fst.add_arc(0, Arc::new(3, 4, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(0, Arc::new(5, 6, TropicalWeight::one(), 0))?;
+
+        let vector_fst: VectorFst<TropicalWeight> = convert_fst(&fst)?;
+        assert_eq!(vector_fst, fst);
+
+        let const_fst: ConstFst<TropicalWeight> = fst.clone().into();
+        let vector_fst: VectorFst<TropicalWeight> = convert_fst(&const_fst)?;
+        assert_eq!(vector_fst, fst);
+        Ok(())
+    }
+}
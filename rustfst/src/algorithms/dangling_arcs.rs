@@ -0,0 +1,117 @@
+use failure::Fallible;
+
+use crate::fst_traits::{ExpandedFst, MutableFst};
+
+/// What `repair_dangling_arcs` does with the arcs leading to a state that doesn't exist.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DanglingPolicy {
+    /// The dangling arcs are deleted.
+    Remove,
+    /// An error is returned as soon as a dangling arc is found.
+    Error,
+}
+
+/// Looks for the arcs whose `nextstate` isn't a state of the FST, which the unchecked methods
+/// of `MutableFst` and `add_arc` don't prevent, and either deletes them or returns an error
+/// depending on `policy`. Running it after a manual construction avoids out of bound panics in
+/// the algorithms traversing the FST.
+///
+/// Returns the number of dangling arcs found, which is 0 if `policy` is `Error` and no error is
+/// returned.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::{repair_dangling_arcs, DanglingPolicy};
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), 3))?;
+///
+/// assert!(repair_dangling_arcs(&mut fst, DanglingPolicy::Error).is_err());
+/// assert_eq!(repair_dangling_arcs(&mut fst, DanglingPolicy::Remove)?, 1);
+/// assert_eq!(fst.num_arcs(s0)?, 0);
+/// # Ok(())
+/// # }
+/// ```
+pub fn repair_dangling_arcs<F>(fst: &mut F, policy: DanglingPolicy) -> Fallible<usize>
+where
+    F: MutableFst + ExpandedFst,
+{
+    let num_states = fst.num_states();
+    let mut n_dangling = 0;
+    for s in 0..num_states {
+        let to_del: Vec<_> = unsafe { fst.arcs_iter_unchecked(s) }
+            .enumerate()
+            .filter(|(_, arc)| arc.nextstate >= num_states)
+            .map(|(idx, _)| idx)
+            .collect();
+        if to_del.is_empty() {
+            continue;
+        }
+        match policy {
+            DanglingPolicy::Error => {
+                let arc = unsafe { fst.arcs_iter_unchecked(s) }
+                    .nth(to_del[0])
+                    .unwrap();
+                bail!(
+                    "Arc {:?} of state {} leads to a state that doesn't exist, the FST having {} states",
+                    arc,
+                    s,
+                    num_states
+                );
+            }
+            DanglingPolicy::Remove => {
+                n_dangling += to_del.len();
+                unsafe { fst.del_arcs_id_sorted_unchecked(s, &to_del) };
+            }
+        }
+    }
+    Ok(n_dangling)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_repair_dangling_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 5))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+        fst.add_arc(1, Arc::new(4, 4, TropicalWeight::new(4.0), 3))?;
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(5.0), 0))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(0)?;
+        fst_ref.set_final(2, TropicalWeight::one())?;
+        fst_ref.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst_ref.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+        fst_ref.add_arc(2, Arc::new(5, 5, TropicalWeight::new(5.0), 0))?;
+
+        let fst_before = fst.clone();
+        assert!(repair_dangling_arcs(&mut fst, DanglingPolicy::Error).is_err());
+        assert_eq!(fst, fst_before);
+
+        assert_eq!(repair_dangling_arcs(&mut fst, DanglingPolicy::Remove)?, 2);
+        assert_eq!(fst, fst_ref);
+
+        assert_eq!(repair_dangling_arcs(&mut fst, DanglingPolicy::Error)?, 0);
+        assert_eq!(repair_dangling_arcs(&mut fst, DanglingPolicy::Remove)?, 0);
+        Ok(())
+    }
+}
@@ -1,5 +1,6 @@
+use std::any::{Any, TypeId};
 use std::collections::hash_map::Entry;
-use std::collections::{HashMap, HashSet, VecDeque};
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::marker::PhantomData;
 use std::slice::Iter as IterSlice;
 
@@ -8,6 +9,7 @@ use failure::Fallible;
 
 use crate::algorithms::cache::CacheImpl;
 use crate::algorithms::factor_iterators::{GallicFactor, GallicFactorMin, GallicFactorRestrict};
+use crate::algorithms::functional::find_non_functional_state;
 use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
 use crate::algorithms::{factor_weight, weight_convert, FactorWeightOptions, FactorWeightType};
 use crate::arc::Arc;
@@ -15,8 +17,8 @@ use crate::fst_impls::VectorFst;
 use crate::fst_traits::{ExpandedFst, Fst, MutableFst};
 use crate::semirings::{
     DivideType, GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict, Semiring,
-    SemiringProperties, StringWeightLeft, StringWeightRestrict, WeaklyDivisibleSemiring,
-    WeightQuantize,
+    SemiringProperties, StringWeightLeft, StringWeightRestrict, TropicalWeight,
+    WeaklyDivisibleSemiring, WeightQuantize,
 };
 use crate::{Label, StateId, EPS_LABEL, KDELTA};
 
@@ -32,6 +34,53 @@ pub enum DeterminizeType {
     DeterminizeDisambiguate,
 }
 
+/// Options of `determinize_with_config`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeterminizeConfig {
+    /// Determinization type, only used when the input is not an acceptor.
+    pub det_type: DeterminizeType,
+    /// Delta used to quantize the weights of the residuals.
+    pub delta: f32,
+    /// Number of states of the result after which the weights of the residuals stop being
+    /// refined : a new weighted subset is then merged with the first state created for the same
+    /// set of states, whatever their weights, a state being only created for a new set of states.
+    /// This bounds the runtime on FSTs whose weighted subsets never converge (e.g. over the log
+    /// semiring when the twins property doesn't hold) but the weights of the result are then
+    /// approximate. The accepted strings are unchanged.
+    pub max_states: Option<usize>,
+    /// Threshold under which the weights of the residuals stop being refined : a new weighted
+    /// subset is merged with a state created for the same set of states whose residual weights
+    /// all differ from its own ones by less than `weight_threshold`. This bounds the runtime on
+    /// FSTs whose weighted subsets converge without ever being equal (e.g. over the log
+    /// semiring) but the weights of the result are then approximate. The accepted strings are
+    /// unchanged. Unlike `max_states`, it doesn't help when the residuals drift apart.
+    pub weight_threshold: Option<f32>,
+    /// Debugging option checking that the result assigns the same weights (up to `delta`) as
+    /// the input to the label sequences of their cycle-free paths, an error being returned
+    /// otherwise. The check enumerates the paths and can be exponentially slow : it is meant to
+    /// be enabled in debug builds, e.g. with `verify: cfg!(debug_assertions)`. Only the
+    /// cycle-free paths are compared, hence a result wrong only on the paths going through a
+    /// cycle isn't detected. Only acceptors can be verified : an error is returned when the
+    /// option is set for a transducer. When `max_states` is reached or `weight_threshold` is set,
+    /// the weights of the result being approximate, only the accepted label sequences are
+    /// checked.
+    pub verify: bool,
+}
+
+impl DeterminizeConfig {
+    /// Creates the default configuration for a determinization type : the weights are quantized
+    /// with `KDELTA` and always refined, and the result isn't verified.
+    pub fn new(det_type: DeterminizeType) -> Self {
+        Self {
+            det_type,
+            delta: KDELTA,
+            max_states: None,
+            weight_threshold: None,
+            verify: false,
+        }
+    }
+}
+
 pub trait CommonDivisor<W: Semiring> {
     fn common_divisor(w1: &W, w2: &W) -> Fallible<W>;
 }
@@ -173,13 +222,24 @@ where
     ghost: PhantomData<CD>,
     in_dist: Option<&'b [F::W]>,
     out_dist: Vec<F::W>,
+    delta: f32,
+    max_states: Option<usize>,
+    weight_threshold: Option<f32>,
+    // States created for each set of states (and filter state), whatever the weights
+    state_sets: HashMap<(Vec<StateId>, StateId), Vec<StateId>>,
 }
 
 impl<'a, 'b, F: Fst, CD: CommonDivisor<F::W>> DeterminizeFsaImpl<'a, 'b, F, CD>
 where
     F::W: WeaklyDivisibleSemiring + WeightQuantize,
 {
-    pub fn new(fst: &'a F, in_dist: Option<&'b [F::W]>) -> Fallible<Self> {
+    pub fn new(
+        fst: &'a F,
+        in_dist: Option<&'b [F::W]>,
+        delta: f32,
+        max_states: Option<usize>,
+        weight_threshold: Option<f32>,
+    ) -> Fallible<Self> {
         if !fst.is_acceptor() {
             bail!("DeterminizeFsaImpl : expected acceptor as argument");
         }
@@ -190,6 +250,10 @@ where
             ghost: PhantomData,
             in_dist,
             out_dist: vec![],
+            delta,
+            max_states,
+            weight_threshold,
+            state_sets: HashMap::new(),
         })
     }
 
@@ -249,8 +313,9 @@ where
     }
 
     fn expand(&mut self, state: StateId) -> Fallible<()> {
-        // GetLabelMap
-        let mut label_map: HashMap<Label, DeterminizeArc<F::W>> = HashMap::new();
+        // GetLabelMap. The arcs, and thus the ids of the states they lead to, are created in the
+        // order of the labels so that the output doesn't depend on the order of a HashMap.
+        let mut label_map: BTreeMap<Label, DeterminizeArc<F::W>> = BTreeMap::new();
         let src_tuple = self.state_table.get_by_left(&state).unwrap();
         for src_elt in src_tuple.subset.iter() {
             for arc in self.fst.arcs_iter(src_elt.state)? {
@@ -259,16 +324,9 @@ where
                 let dest_elt = DeterminizeElement::new(arc.nextstate, r);
 
                 // Filter Arc
-                match label_map.entry(arc.ilabel) {
-                    Entry::Occupied(_) => {}
-                    Entry::Vacant(e) => {
-                        e.insert(DeterminizeArc::from_arc(arc, 0));
-                    }
-                };
-
                 label_map
-                    .get_mut(&arc.ilabel)
-                    .unwrap()
+                    .entry(arc.ilabel)
+                    .or_insert_with(|| DeterminizeArc::from_arc(arc, 0))
                     .dest_tuple
                     .subset
                     .pairs
@@ -298,12 +356,6 @@ where
     }
 
     fn norm_arc(&mut self, det_arc: &mut DeterminizeArc<F::W>) -> Fallible<()> {
-        det_arc
-            .dest_tuple
-            .subset
-            .pairs
-            .sort_by(|a, b| a.state.cmp(&b.state));
-
         for dest_elt in det_arc.dest_tuple.subset.pairs.iter() {
             det_arc.weight = CD::common_divisor(&det_arc.weight, &dest_elt.weight)?;
         }
@@ -321,12 +373,17 @@ where
         }
 
         det_arc.dest_tuple.subset.pairs = new_pairs.values().cloned().collect();
+        det_arc
+            .dest_tuple
+            .subset
+            .pairs
+            .sort_by(|a, b| a.state.cmp(&b.state));
 
         for dest_elt in det_arc.dest_tuple.subset.pairs.iter_mut() {
             dest_elt.weight = dest_elt
                 .weight
                 .divide(&det_arc.weight, DivideType::DivideLeft)?;
-            dest_elt.weight.quantize_assign(KDELTA)?;
+            dest_elt.weight.quantize_assign(self.delta)?;
         }
 
         Ok(())
@@ -335,7 +392,28 @@ where
     fn find_state(&mut self, tuple: &DeterminizeStateTuple<F::W>) -> Fallible<StateId> {
         if !self.state_table.contains_right(tuple) {
             let n = self.state_table.len();
+            let state_set = (
+                tuple.subset.iter().map(|elt| elt.state).collect(),
+                tuple.filter_state,
+            );
+            if let Some(states) = self.state_sets.get(&state_set) {
+                if self.max_states.map_or(false, |max| n >= max) {
+                    return Ok(states[0]);
+                }
+                if let Some(threshold) = self.weight_threshold {
+                    for s in states {
+                        let subset = &self.state_table.get_by_left(s).unwrap().subset;
+                        if close_subsets(subset, &tuple.subset, threshold) {
+                            return Ok(*s);
+                        }
+                    }
+                }
+            }
             self.state_table.insert(n, tuple.clone());
+            self.state_sets
+                .entry(state_set)
+                .or_insert_with(Vec::new)
+                .push(n);
         }
         let s = *self.state_table.get_by_right(tuple).unwrap();
         if let Some(_in_dist) = self.in_dist.as_ref() {
@@ -410,6 +488,22 @@ where
     }
 }
 
+/// Whether two weighted subsets over the same states have residual weights differing by less
+/// than `threshold`, i.e whether the quotients of their weights are `one()` once quantized with
+/// `2 * threshold`.
+fn close_subsets<W: WeaklyDivisibleSemiring>(
+    subset_1: &WeightedSubset<W>,
+    subset_2: &WeightedSubset<W>,
+    threshold: f32,
+) -> bool {
+    subset_1.iter().zip(subset_2.iter()).all(|(elt_1, elt_2)| {
+        elt_1
+            .weight
+            .divide(&elt_2.weight, DivideType::DivideLeft)
+            .map_or(false, |w| w.approx_equal(&W::one(), 2.0 * threshold))
+    })
+}
+
 pub fn determinize_with_distance<W, F1, F2>(ifst: &F1, in_dist: &[W]) -> Fallible<(F2, Vec<W>)>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
@@ -420,11 +514,16 @@ where
         bail!("determinize_fsa : weight must be left distributive")
     }
     let mut det_fsa_impl: DeterminizeFsaImpl<_, DefaultCommonDivisor> =
-        DeterminizeFsaImpl::new(ifst, Some(in_dist))?;
+        DeterminizeFsaImpl::new(ifst, Some(in_dist), KDELTA, None, None)?;
     det_fsa_impl.compute_with_distance()
 }
 
-pub fn determinize_fsa<W, F1, F2, CD>(fst_in: &F1) -> Fallible<F2>
+pub fn determinize_fsa<W, F1, F2, CD>(
+    fst_in: &F1,
+    delta: f32,
+    max_states: Option<usize>,
+    weight_threshold: Option<f32>,
+) -> Fallible<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
     F1: Fst<W = W>,
@@ -434,11 +533,130 @@ where
     if !W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
         bail!("determinize_fsa : weight must be left distributive")
     }
-    let mut det_fsa_impl: DeterminizeFsaImpl<_, CD> = DeterminizeFsaImpl::new(fst_in, None)?;
+    let mut det_fsa_impl: DeterminizeFsaImpl<_, CD> =
+        DeterminizeFsaImpl::new(fst_in, None, delta, max_states, weight_threshold)?;
     det_fsa_impl.compute()
 }
 
-pub fn determinize_fst<W, F1, F2>(fst_in: &F1, det_type: DeterminizeType) -> Fallible<F2>
+/// Quantizes a tropical value the same way `WeightQuantize` does with `delta` and returns
+/// its bit pattern, so that two values that compare equal share the same key.
+#[inline]
+fn tropical_key(value: f32, delta: f32) -> u32 {
+    if value.is_infinite() {
+        return value.to_bits();
+    }
+    (((value / delta) + 0.5).floor() * delta).to_bits()
+}
+
+/// Reads the value of a weight known to be a `TropicalWeight`.
+#[inline]
+pub(crate) fn tropical_value<W: Semiring + 'static>(weight: &W) -> f32 {
+    let weight = (weight as &dyn Any).downcast_ref::<TropicalWeight>();
+    *weight.unwrap().value()
+}
+
+/// Builds a weight known to be a `TropicalWeight` from its value.
+#[inline]
+pub(crate) fn tropical_weight<W: Semiring + 'static>(value: f32) -> W {
+    let mut weight: Option<W> = None;
+    *(&mut weight as &mut dyn Any)
+        .downcast_mut::<Option<TropicalWeight>>()
+        .unwrap() = Some(TropicalWeight::new(value));
+    weight.unwrap()
+}
+
+/// Determinization of an acceptor over the tropical semiring.
+///
+/// Equivalent to `determinize_fsa` with the `DefaultCommonDivisor` but the subsets are
+/// stored as `(state, quantized f32 bits)` pairs which are hashed and compared directly,
+/// without going through the generic `Semiring` operations.
+/// Must only be called with `W` being `TropicalWeight`.
+fn determinize_fsa_tropical<W, F1, F2>(fst_in: &F1, delta: f32) -> Fallible<F2>
+where
+    W: Semiring + 'static,
+    F1: Fst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    let mut fst_out = F2::new();
+    let start_state = match fst_in.start() {
+        Some(s) => s,
+        None => return Ok(fst_out),
+    };
+
+    let start_subset = vec![(start_state, 0.0f32.to_bits())];
+    let mut state_table = HashMap::new();
+    state_table.insert(start_subset.clone(), 0);
+    let mut subsets = vec![start_subset];
+    fst_out.add_state();
+    fst_out.set_start(0)?;
+
+    let mut s = 0;
+    while s < subsets.len() {
+        let mut final_weight = f32::INFINITY;
+        let mut label_map: BTreeMap<Label, Vec<(StateId, f32)>> = BTreeMap::new();
+        for &(q, residual) in subsets[s].iter() {
+            let residual = f32::from_bits(residual);
+            if let Some(w) = fst_in.final_weight(q)? {
+                final_weight = final_weight.min(residual + tropical_value(w));
+            }
+            for arc in fst_in.arcs_iter(q)? {
+                label_map
+                    .entry(arc.ilabel)
+                    .or_default()
+                    .push((arc.nextstate, residual + tropical_value(&arc.weight)));
+            }
+        }
+        if final_weight != f32::INFINITY {
+            fst_out.set_final(s, tropical_weight(final_weight))?;
+        }
+
+        for (label, mut dest_elts) in label_map {
+            let arc_weight = dest_elts
+                .iter()
+                .fold(f32::INFINITY, |acc, &(_, w)| acc.min(w));
+            if arc_weight == f32::INFINITY {
+                continue;
+            }
+
+            dest_elts.sort_by_key(|e| e.0);
+            let mut dest_subset: Vec<(StateId, u32)> = Vec::with_capacity(dest_elts.len());
+            let mut i = 0;
+            while i < dest_elts.len() {
+                let (q, mut w) = dest_elts[i];
+                i += 1;
+                while i < dest_elts.len() && dest_elts[i].0 == q {
+                    w = w.min(dest_elts[i].1);
+                    i += 1;
+                }
+                dest_subset.push((q, tropical_key(w - arc_weight, delta)));
+            }
+
+            let nextstate = match state_table.entry(dest_subset) {
+                Entry::Occupied(e) => *e.get(),
+                Entry::Vacant(e) => {
+                    let n = fst_out.add_state();
+                    subsets.push(e.key().clone());
+                    e.insert(n);
+                    n
+                }
+            };
+            fst_out.add_arc(
+                s,
+                Arc::new(label, label, tropical_weight(arc_weight), nextstate),
+            )?;
+        }
+        s += 1;
+    }
+    Ok(fst_out)
+}
+
+pub fn determinize_fst<W, F1, F2>(
+    fst_in: &F1,
+    det_type: DeterminizeType,
+    delta: f32,
+    max_states: Option<usize>,
+    weight_threshold: Option<f32>,
+) -> Fallible<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
     F1: ExpandedFst<W = W>,
@@ -450,7 +668,7 @@ where
     };
 
     let factor_opts = FactorWeightOptions {
-        delta: KDELTA,
+        delta,
         mode: FactorWeightType::FACTOR_FINAL_WEIGHTS,
         final_ilabel: 0,
         final_olabel: 0,
@@ -465,15 +683,32 @@ where
             }
             let fsa: VectorFst<GallicWeightMin<W>> = weight_convert(fst_in, &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeightMin<W>> =
-                determinize_fsa::<_, _, _, GallicCommonDivisor>(&fsa)?;
+                determinize_fsa::<_, _, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    max_states,
+                    weight_threshold,
+                )?;
             let factored_determinized_fsa: VectorFst<GallicWeightMin<W>> =
                 factor_weight::<_, _, GallicFactorMin<W>>(&determinized_fsa, factor_opts)?;
             weight_convert(&factored_determinized_fsa, &mut from_gallic)
         }
         DeterminizeType::DeterminizeFunctional => {
+            if let Some((s1, s2)) = find_non_functional_state(fst_in, true)? {
+                bail!(
+                    "determinize : input FST is not functional, the same input string leads to states {} and {} with different outputs",
+                    s1,
+                    s2
+                )
+            }
             let fsa: VectorFst<GallicWeightRestrict<W>> = weight_convert(fst_in, &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
-                determinize_fsa::<_, _, _, GallicCommonDivisor>(&fsa)?;
+                determinize_fsa::<_, _, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    max_states,
+                    weight_threshold,
+                )?;
             let factored_determinized_fsa: VectorFst<GallicWeightRestrict<W>> =
                 factor_weight::<_, _, GallicFactorRestrict<W>>(&determinized_fsa, factor_opts)?;
             weight_convert(&factored_determinized_fsa, &mut from_gallic)
@@ -481,7 +716,12 @@ where
         DeterminizeType::DeterminizeNonFunctional => {
             let fsa: VectorFst<GallicWeight<W>> = weight_convert(fst_in, &mut to_gallic)?;
             let determinized_fsa: VectorFst<GallicWeight<W>> =
-                determinize_fsa::<_, _, _, GallicCommonDivisor>(&fsa)?;
+                determinize_fsa::<_, _, _, GallicCommonDivisor>(
+                    &fsa,
+                    delta,
+                    max_states,
+                    weight_threshold,
+                )?;
             let factored_determinized_fsa: VectorFst<GallicWeight<W>> =
                 factor_weight::<_, _, GallicFactor<W>>(&determinized_fsa, factor_opts)?;
             weight_convert(&factored_determinized_fsa, &mut from_gallic)
@@ -492,24 +732,191 @@ where
 /// This operations creates an equivalent FST that has the property that no
 /// state has two transitions with the same input label. For this algorithm,
 /// epsilon transitions are treated as regular symbols.
+///
+/// The weights are quantized with `KDELTA`, see `determinize_with_delta` to use another delta.
 pub fn determinize<W, F1, F2>(fst_in: &F1, det_type: DeterminizeType) -> Fallible<F2>
 where
     W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
     F1: ExpandedFst<W = W>,
     F2: MutableFst<W = W> + ExpandedFst<W = W>,
 {
+    determinize_with_delta(fst_in, det_type, KDELTA)
+}
+
+/// Same as `determinize` but the weights of the residuals are quantized with `delta` to decide
+/// whether two subsets are the same state of the determinized FST.
+pub fn determinize_with_delta<W, F1, F2>(
+    fst_in: &F1,
+    det_type: DeterminizeType,
+    delta: f32,
+) -> Fallible<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    determinize_with_config(
+        fst_in,
+        DeterminizeConfig {
+            delta,
+            ..DeterminizeConfig::new(det_type)
+        },
+    )
+}
+
+/// Same as `determinize` with the options of `config`. When `max_states` or `weight_threshold` is
+/// set, the weights of the result are only approximate, see `DeterminizeConfig`.
+pub fn determinize_with_config<W, F1, F2>(fst_in: &F1, config: DeterminizeConfig) -> Fallible<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    let DeterminizeConfig {
+        det_type,
+        delta,
+        max_states,
+        weight_threshold,
+        verify,
+    } = config;
     if fst_in.is_acceptor() {
-        determinize_fsa::<_, _, _, DefaultCommonDivisor>(fst_in)
+        // The specialized code path doesn't bound the number of states
+        let fst_out: F2 = if TypeId::of::<W>() == TypeId::of::<TropicalWeight>()
+            && max_states.is_none()
+            && weight_threshold.is_none()
+        {
+            determinize_fsa_tropical(fst_in, delta)?
+        } else {
+            determinize_fsa::<_, _, _, DefaultCommonDivisor>(
+                fst_in,
+                delta,
+                max_states,
+                weight_threshold,
+            )?
+        };
+        if verify {
+            let exact_weights = weight_threshold.is_none()
+                && max_states.map_or(true, |max| fst_out.num_states() < max);
+            verify_determinization(fst_in, &fst_out, delta, exact_weights)?;
+        }
+        Ok(fst_out)
     } else {
-        determinize_fst(fst_in, det_type)
+        ensure!(
+            !verify,
+            "The verification of the determinization is only supported for acceptors"
+        );
+        determinize_fst(fst_in, det_type, delta, max_states, weight_threshold)
+    }
+}
+
+/// Checks that two acceptors assign the same weights, up to `delta`, to the label sequences of
+/// the cycle-free paths of both FSTs. Epsilon is handled as a regular label, as determinize does.
+/// Without `exact_weights`, only checks that both FSTs accept the same label sequences.
+fn verify_determinization<F1, F2>(
+    fst_in: &F1,
+    fst_out: &F2,
+    delta: f32,
+    exact_weights: bool,
+) -> Fallible<()>
+where
+    F1: ExpandedFst,
+    F2: ExpandedFst<W = F1::W>,
+{
+    let mut sequences = label_sequences(fst_in)?;
+    sequences.extend(label_sequences(fst_out)?);
+    for labels in sequences {
+        let weight_in = label_sequence_weight(fst_in, &labels)?;
+        let weight_out = label_sequence_weight(fst_out, &labels)?;
+        if !exact_weights {
+            ensure!(
+                weight_in.is_zero() == weight_out.is_zero(),
+                "Determinization changed whether the labels {:?} are accepted",
+                labels
+            );
+            continue;
+        }
+        ensure!(
+            weight_in.approx_equal(&weight_out, delta),
+            "Determinization changed the weight of the labels {:?} from {:?} to {:?}",
+            labels,
+            weight_in,
+            weight_out
+        );
+    }
+    Ok(())
+}
+
+/// Label sequences of the cycle-free successful paths of an acceptor.
+fn label_sequences<F: ExpandedFst>(fst: &F) -> Fallible<Vec<Vec<Label>>> {
+    let mut sequences = vec![];
+    if let Some(start) = fst.start() {
+        let mut on_path = vec![false; fst.num_states()];
+        let mut labels = vec![];
+        collect_label_sequences(fst, start, &mut on_path, &mut labels, &mut sequences)?;
+    }
+    Ok(sequences)
+}
+
+fn collect_label_sequences<F: ExpandedFst>(
+    fst: &F,
+    state: StateId,
+    on_path: &mut [bool],
+    labels: &mut Vec<Label>,
+    sequences: &mut Vec<Vec<Label>>,
+) -> Fallible<()> {
+    if fst.is_final(state)? {
+        sequences.push(labels.clone());
+    }
+    on_path[state] = true;
+    for arc in fst.arcs_iter(state)? {
+        if !on_path[arc.nextstate] {
+            labels.push(arc.ilabel);
+            collect_label_sequences(fst, arc.nextstate, on_path, labels, sequences)?;
+            labels.pop();
+        }
     }
+    on_path[state] = false;
+    Ok(())
+}
+
+/// Sum of the weights of the paths of an acceptor matching exactly a label sequence.
+fn label_sequence_weight<F: ExpandedFst>(fst: &F, labels: &[Label]) -> Fallible<F::W> {
+    let mut weights = BTreeMap::new();
+    if let Some(start) = fst.start() {
+        weights.insert(start, F::W::one());
+    }
+    for label in labels {
+        let mut next_weights: BTreeMap<StateId, F::W> = BTreeMap::new();
+        for (state, weight) in &weights {
+            for arc in fst.arcs_iter(*state)?.filter(|arc| arc.ilabel == *label) {
+                next_weights
+                    .entry(arc.nextstate)
+                    .or_insert_with(F::W::zero)
+                    .plus_assign(weight.times(&arc.weight)?)?;
+            }
+        }
+        weights = next_weights;
+    }
+
+    let mut weight = F::W::zero();
+    for (state, w) in weights {
+        if let Some(final_weight) = fst.final_weight(state)? {
+            weight.plus_assign(w.times(final_weight)?)?;
+        }
+    }
+    Ok(weight)
 }
 
 #[cfg(test)]
 mod tests {
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    use crate::algorithms::{arc_sum, isomorphic};
     use crate::arc::Arc;
     use crate::fst_impls::VectorFst;
-    use crate::semirings::TropicalWeight;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{ArcIterator, CoreFst, MutableArcIterator};
+    use crate::semirings::{LogWeight, TropicalWeight};
 
     use super::*;
 
@@ -576,4 +983,286 @@ mod tests {
         assert_eq!(determinized_fst, ref_fst);
         Ok(())
     }
+
+    #[test]
+    fn test_determinize_functional_non_functional_input() -> Fallible<()> {
+        let mut input_fst = VectorFst::new();
+        let s0 = input_fst.add_state();
+        let s1 = input_fst.add_state();
+        let s2 = input_fst.add_state();
+
+        input_fst.set_start(s0)?;
+        input_fst.set_final(s1, TropicalWeight::one())?;
+        input_fst.set_final(s2, TropicalWeight::one())?;
+
+        input_fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::new(1.0), s1))?;
+        input_fst.add_arc(s0, Arc::new(1, 3, TropicalWeight::new(2.0), s2))?;
+        input_fst.add_arc(s1, Arc::new(1, 2, TropicalWeight::new(1.0), s1))?;
+        input_fst.add_arc(s2, Arc::new(1, 3, TropicalWeight::new(2.0), s2))?;
+
+        let res: Fallible<VectorFst<TropicalWeight>> =
+            determinize(&input_fst, DeterminizeType::DeterminizeFunctional);
+        let err = res.unwrap_err().to_string();
+
+        assert!(err.contains("not functional"));
+        assert!(err.contains("states 1 and 2"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_tropical_same_as_generic() -> Fallible<()> {
+        // Acyclic lattice with many ambiguous paths.
+        let mut rg = StdRng::from_seed([17; 32]);
+        let n_layers = 30;
+        let layer_size = 5;
+        let mut input_fst = VectorFst::<TropicalWeight>::new();
+        input_fst.add_states(n_layers * layer_size + 1);
+        input_fst.set_start(0)?;
+        for layer in 0..n_layers {
+            let sources: Vec<_> = if layer == 0 {
+                vec![0]
+            } else {
+                (1 + (layer - 1) * layer_size..1 + layer * layer_size).collect()
+            };
+            for s in sources {
+                for _ in 0..3 {
+                    let label = rg.gen_range(1, 4);
+                    let weight = TropicalWeight::new(rg.gen_range(0, 10) as f32 * 0.5);
+                    let nextstate = 1 + layer * layer_size + rg.gen_range(0, layer_size);
+                    input_fst.add_arc(s, Arc::new(label, label, weight, nextstate))?;
+                }
+            }
+        }
+        for s in 1 + (n_layers - 1) * layer_size..1 + n_layers * layer_size {
+            input_fst.set_final(s, TropicalWeight::new(rg.gen_range(0, 4) as f32))?;
+        }
+
+        let tropical_fst: VectorFst<TropicalWeight> =
+            determinize(&input_fst, DeterminizeType::DeterminizeFunctional)?;
+        let generic_fst: VectorFst<TropicalWeight> =
+            determinize_fsa::<_, _, _, DefaultCommonDivisor>(&input_fst, KDELTA, None, None)?;
+
+        assert_eq!(tropical_fst.num_states(), generic_fst.num_states());
+        assert!(isomorphic(&tropical_fst, &generic_fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_max_states() -> Fallible<()> {
+        // The twins property doesn't hold : the residual weights of the states 1 and 2 drift
+        // apart on each label 2 and the weighted subsets never converge.
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(3);
+        input_fst.set_start(0)?;
+        input_fst.set_final(1, LogWeight::one())?;
+        input_fst.set_final(2, LogWeight::one())?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::new(1.0), 1))?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::new(2.0), 2))?;
+        input_fst.add_arc(1, Arc::new(2, 2, LogWeight::new(1.0), 1))?;
+        input_fst.add_arc(2, Arc::new(2, 2, LogWeight::new(2.0), 2))?;
+
+        // The weights are approximate but the accepted strings are verified
+        let config = DeterminizeConfig {
+            max_states: Some(10),
+            verify: true,
+            ..DeterminizeConfig::new(DeterminizeType::DeterminizeFunctional)
+        };
+        let determinized_fst: VectorFst<LogWeight> = determinize_with_config(&input_fst, config)?;
+
+        assert!(determinized_fst.num_states() <= 10);
+        assert!(determinized_fst
+            .properties()?
+            .contains(FstProperties::I_DETERMINISTIC));
+
+        // The strings 1 2^k are still accepted
+        let mut state = determinized_fst.start().unwrap();
+        for label in [1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2].iter() {
+            assert!(determinized_fst.is_final(state)? || *label == 1);
+            state = determinized_fst
+                .arcs_iter(state)?
+                .find(|arc| arc.ilabel == *label)
+                .unwrap()
+                .nextstate;
+        }
+        assert!(determinized_fst.is_final(state)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_weight_threshold() -> Fallible<()> {
+        // The ratio of the residual weights of the states 1 and 2 converges on each label 2
+        // without ever being reached : the weighted subsets only become equal once the
+        // precision of f32 is exhausted, after more than a hundred states.
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(3);
+        input_fst.set_start(0)?;
+        input_fst.set_final(1, LogWeight::one())?;
+        input_fst.set_final(2, LogWeight::one())?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::one(), 1))?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::one(), 2))?;
+        input_fst.add_arc(1, Arc::new(2, 2, LogWeight::new(0.5f32.ln().abs()), 1))?;
+        input_fst.add_arc(1, Arc::new(2, 2, LogWeight::new(0.25f32.ln().abs()), 2))?;
+        input_fst.add_arc(2, Arc::new(2, 2, LogWeight::new(0.49f32.ln().abs()), 2))?;
+
+        // Without threshold, a new state is created for each label 2 until max_states
+        let config = DeterminizeConfig {
+            delta: 1e-6,
+            max_states: Some(100),
+            ..DeterminizeConfig::new(DeterminizeType::DeterminizeFunctional)
+        };
+        let determinized_fst: VectorFst<LogWeight> = determinize_with_config(&input_fst, config)?;
+        assert_eq!(determinized_fst.num_states(), 100);
+
+        let config = DeterminizeConfig {
+            delta: 1e-6,
+            weight_threshold: Some(1e-2),
+            verify: true,
+            ..DeterminizeConfig::new(DeterminizeType::DeterminizeFunctional)
+        };
+        let determinized_fst: VectorFst<LogWeight> = determinize_with_config(&input_fst, config)?;
+        assert!(determinized_fst.num_states() < 100);
+        assert!(determinized_fst
+            .properties()?
+            .contains(FstProperties::I_DETERMINISTIC));
+
+        // The last state loops on the label 2
+        let mut state = determinized_fst.start().unwrap();
+        for label in [1, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2, 2].iter() {
+            assert!(determinized_fst.is_final(state)? || *label == 1);
+            state = determinized_fst
+                .arcs_iter(state)?
+                .find(|arc| arc.ilabel == *label)
+                .unwrap()
+                .nextstate;
+        }
+        assert!(determinized_fst.is_final(state)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_verify() -> Fallible<()> {
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(4);
+        input_fst.set_start(0)?;
+        input_fst.set_final(3, LogWeight::new(0.5))?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::new(1.0), 1))?;
+        input_fst.add_arc(0, Arc::new(1, 1, LogWeight::new(2.0), 2))?;
+        input_fst.add_arc(1, Arc::new(2, 2, LogWeight::new(1.0), 3))?;
+        input_fst.add_arc(2, Arc::new(2, 2, LogWeight::new(3.0), 3))?;
+        input_fst.add_arc(2, Arc::new(3, 3, LogWeight::new(1.0), 3))?;
+        input_fst.add_arc(3, Arc::new(4, 4, LogWeight::new(1.0), 3))?;
+
+        let config = DeterminizeConfig {
+            verify: true,
+            ..DeterminizeConfig::new(DeterminizeType::DeterminizeFunctional)
+        };
+        let determinized_fst: VectorFst<LogWeight> = determinize_with_config(&input_fst, config)?;
+
+        // A broken determinization changing the weight of a path is reported
+        let mut broken_fst = determinized_fst.clone();
+        let s = broken_fst.start().unwrap();
+        for arc in broken_fst.arcs_iter_mut(s)? {
+            arc.weight = LogWeight::new(5.0);
+        }
+        verify_determinization(&input_fst, &determinized_fst, KDELTA, true)?;
+        assert!(verify_determinization(&input_fst, &broken_fst, KDELTA, true).is_err());
+
+        // So is a broken determinization accepting a new string
+        let mut broken_fst = determinized_fst;
+        let s = broken_fst.start().unwrap();
+        broken_fst.add_arc(s, Arc::new(5, 5, LogWeight::one(), s))?;
+        broken_fst.set_final(s, LogWeight::one())?;
+        assert!(verify_determinization(&input_fst, &broken_fst, KDELTA, true).is_err());
+        assert!(verify_determinization(&input_fst, &broken_fst, KDELTA, false).is_err());
+
+        // Transducers can't be verified
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(2);
+        input_fst.set_start(0)?;
+        input_fst.set_final(1, LogWeight::one())?;
+        input_fst.add_arc(0, Arc::new(1, 2, LogWeight::new(1.0), 1))?;
+        input_fst.add_arc(0, Arc::new(1, 2, LogWeight::new(2.0), 1))?;
+        let config = DeterminizeConfig {
+            verify: true,
+            ..DeterminizeConfig::new(DeterminizeType::DeterminizeFunctional)
+        };
+        let determinized_fst: Fallible<VectorFst<LogWeight>> =
+            determinize_with_config(&input_fst, config);
+        assert!(determinized_fst.is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_is_deterministic() -> Fallible<()> {
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(5);
+        input_fst.set_start(0)?;
+        input_fst.set_final(4, LogWeight::one())?;
+        for (label, weight) in &[(7, 1.0), (3, 0.5), (9, 2.0), (1, 1.5), (5, 0.25)] {
+            input_fst.add_arc(0, Arc::new(*label, *label, LogWeight::new(*weight), 1))?;
+            input_fst.add_arc(
+                0,
+                Arc::new(*label, *label, LogWeight::new(*weight + 1.0), 2),
+            )?;
+        }
+        input_fst.add_arc(1, Arc::new(2, 2, LogWeight::new(1.0), 3))?;
+        input_fst.add_arc(1, Arc::new(4, 4, LogWeight::new(2.0), 4))?;
+        input_fst.add_arc(2, Arc::new(2, 2, LogWeight::new(0.5), 4))?;
+        input_fst.add_arc(3, Arc::new(6, 6, LogWeight::new(0.5), 4))?;
+
+        let fst_ref: VectorFst<LogWeight> =
+            determinize(&input_fst, DeterminizeType::DeterminizeFunctional)?;
+        for _ in 0..10 {
+            let fst: VectorFst<LogWeight> =
+                determinize(&input_fst, DeterminizeType::DeterminizeFunctional)?;
+            assert_eq!(fst, fst_ref);
+        }
+
+        let labels: Vec<_> = fst_ref
+            .arcs_iter(fst_ref.start().unwrap())?
+            .map(|arc| arc.ilabel)
+            .collect();
+        assert_eq!(labels, vec![1, 3, 5, 7, 9]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_determinize_no_duplicated_arcs() -> Fallible<()> {
+        let mut input_fst = VectorFst::new();
+        input_fst.add_states(3);
+        input_fst.set_start(0)?;
+        input_fst.set_final(2, TropicalWeight::one())?;
+        input_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        input_fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(2.0), 2))?;
+        input_fst.add_arc(1, Arc::new(2, 2, TropicalWeight::new(1.0), 2))?;
+        input_fst.add_arc(2, Arc::new(2, 2, TropicalWeight::new(3.0), 2))?;
+        input_fst.add_arc(2, Arc::new(2, 2, TropicalWeight::new(3.0), 2))?;
+
+        let fst: VectorFst<_> = determinize(&input_fst, DeterminizeType::DeterminizeFunctional)?;
+
+        // No two arcs leaving a state share their input label
+        for s in 0..fst.num_states() {
+            let mut ilabels: Vec<_> = fst.arcs_iter(s)?.map(|arc| arc.ilabel).collect();
+            let num_arcs = ilabels.len();
+            ilabels.sort();
+            ilabels.dedup();
+            assert_eq!(ilabels.len(), num_arcs);
+        }
+        assert!(fst.properties()?.contains(FstProperties::I_DETERMINISTIC));
+
+        // Hence arc_sum leaves the output of determinize unchanged
+        let mut fst_summed = fst.clone();
+        arc_sum(&mut fst_summed);
+        assert_eq!(fst_summed, fst);
+
+        // Duplicated arcs of the input are merged
+        let mut fst = input_fst.clone();
+        arc_sum(&mut fst);
+        assert_eq!(fst.num_arcs(2)?, 1);
+        assert_eq!(
+            fst.arcs_iter(2)?.next().unwrap().weight,
+            TropicalWeight::new(3.0)
+        );
+        Ok(())
+    }
 }
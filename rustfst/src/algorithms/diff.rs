@@ -0,0 +1,222 @@
+use std::fmt;
+
+use crate::arc::Arc;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::StateId;
+
+/// First structural difference between two FSTs, as returned by `diff_fsts`. Its `Display`
+/// implementation gives a human-readable description.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FstDiff<W: Semiring> {
+    /// The FSTs don't have the same number of states.
+    NumStates { left: usize, right: usize },
+    /// The FSTs don't have the same start state.
+    Start {
+        left: Option<StateId>,
+        right: Option<StateId>,
+    },
+    /// A state doesn't have the same final weight in both FSTs.
+    FinalWeight {
+        state: StateId,
+        left: Option<W>,
+        right: Option<W>,
+    },
+    /// A state doesn't have the same number of arcs in both FSTs.
+    NumArcs {
+        state: StateId,
+        left: usize,
+        right: usize,
+    },
+    /// The arcs at the same position of a state differ.
+    Arc {
+        state: StateId,
+        position: usize,
+        left: Arc<W>,
+        right: Arc<W>,
+    },
+}
+
+impl<W: Semiring> fmt::Display for FstDiff<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FstDiff::NumStates { left, right } => write!(
+                f,
+                "Different number of states : {} on the left, {} on the right",
+                left, right
+            ),
+            FstDiff::Start { left, right } => write!(
+                f,
+                "Different start states : {:?} on the left, {:?} on the right",
+                left, right
+            ),
+            FstDiff::FinalWeight { state, left, right } => write!(
+                f,
+                "Different final weights for state {} : {:?} on the left, {:?} on the right",
+                state, left, right
+            ),
+            FstDiff::NumArcs { state, left, right } => write!(
+                f,
+                "Different number of arcs for state {} : {} on the left, {} on the right",
+                state, left, right
+            ),
+            FstDiff::Arc {
+                state,
+                position,
+                left,
+                right,
+            } => {
+                if left.ilabel == right.ilabel
+                    && left.olabel == right.olabel
+                    && left.nextstate == right.nextstate
+                {
+                    write!(
+                        f,
+                        "Different weights for arc {} of state {} ({}:{} -> {}) : {} on the left, {} on the right",
+                        position, state, left.ilabel, left.olabel, left.nextstate, left.weight, right.weight
+                    )
+                } else {
+                    write!(
+                        f,
+                        "Different arcs at position {} of state {} : {:?} on the left, {:?} on the right",
+                        position, state, left, right
+                    )
+                }
+            }
+        }
+    }
+}
+
+/// Compares two FSTs state by state and arc by arc, in the order in which they are stored, and
+/// returns the first difference found, `None` meaning that the FSTs are equal. This is the same
+/// comparison as `==` on `VectorFst`s but it tells where the FSTs differ, which helps to debug
+/// failing tests.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::{diff_fsts, FstDiff};
+/// # fn main() -> Fallible<()> {
+/// let mut fst1 = VectorFst::new();
+/// let s0 = fst1.add_state();
+/// fst1.set_start(s0)?;
+///
+/// let mut fst2 = fst1.clone();
+/// assert_eq!(diff_fsts(&fst1, &fst2), None);
+///
+/// fst2.set_final(s0, TropicalWeight::one())?;
+/// let diff = diff_fsts(&fst1, &fst2).unwrap();
+/// assert_eq!(
+///     diff,
+///     FstDiff::FinalWeight { state: s0, left: None, right: Some(TropicalWeight::one()) }
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn diff_fsts<F1, F2>(fst1: &F1, fst2: &F2) -> Option<FstDiff<F1::W>>
+where
+    F1: ExpandedFst,
+    F2: ExpandedFst<W = F1::W>,
+{
+    if fst1.num_states() != fst2.num_states() {
+        return Some(FstDiff::NumStates {
+            left: fst1.num_states(),
+            right: fst2.num_states(),
+        });
+    }
+    if fst1.start() != fst2.start() {
+        return Some(FstDiff::Start {
+            left: fst1.start(),
+            right: fst2.start(),
+        });
+    }
+
+    for state in 0..fst1.num_states() {
+        let final_weight1 = unsafe { fst1.final_weight_unchecked(state) };
+        let final_weight2 = unsafe { fst2.final_weight_unchecked(state) };
+        if final_weight1 != final_weight2 {
+            return Some(FstDiff::FinalWeight {
+                state,
+                left: final_weight1.cloned(),
+                right: final_weight2.cloned(),
+            });
+        }
+
+        let num_arcs1 = unsafe { fst1.num_arcs_unchecked(state) };
+        let num_arcs2 = unsafe { fst2.num_arcs_unchecked(state) };
+        if num_arcs1 != num_arcs2 {
+            return Some(FstDiff::NumArcs {
+                state,
+                left: num_arcs1,
+                right: num_arcs2,
+            });
+        }
+
+        let arcs1 = unsafe { fst1.arcs_iter_unchecked(state) };
+        let arcs2 = unsafe { fst2.arcs_iter_unchecked(state) };
+        for (position, (arc1, arc2)) in arcs1.zip(arcs2).enumerate() {
+            if arc1 != arc2 {
+                return Some(FstDiff::Arc {
+                    state,
+                    position,
+                    left: arc1.clone(),
+                    right: arc2.clone(),
+                });
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::fst_impls::{ConstFst, VectorFst};
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_diff_fsts_arc_weight() -> Fallible<()> {
+        let mut fst1 = VectorFst::new();
+        fst1.add_states(3);
+        fst1.set_start(0)?;
+        fst1.set_final(2, TropicalWeight::one())?;
+        fst1.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst1.add_arc(1, Arc::new(2, 3, TropicalWeight::new(2.0), 2))?;
+        fst1.add_arc(1, Arc::new(4, 5, TropicalWeight::new(3.0), 2))?;
+
+        let const_fst: ConstFst<TropicalWeight> = fst1.clone().into();
+        assert_eq!(diff_fsts(&fst1, &const_fst), None);
+
+        let mut fst2 = VectorFst::new();
+        fst2.add_states(3);
+        fst2.set_start(0)?;
+        fst2.set_final(2, TropicalWeight::one())?;
+        fst2.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst2.add_arc(1, Arc::new(2, 3, TropicalWeight::new(2.0), 2))?;
+        fst2.add_arc(1, Arc::new(4, 5, TropicalWeight::new(3.5), 2))?;
+
+        let diff = diff_fsts(&fst1, &fst2).unwrap();
+        assert_eq!(
+            diff,
+            FstDiff::Arc {
+                state: 1,
+                position: 1,
+                left: Arc::new(4, 5, TropicalWeight::new(3.0), 2),
+                right: Arc::new(4, 5, TropicalWeight::new(3.5), 2),
+            }
+        );
+        assert_eq!(
+            diff.to_string(),
+            "Different weights for arc 1 of state 1 (4:5 -> 2) : 3 on the left, 3.5 on the right"
+        );
+        Ok(())
+    }
+}
@@ -193,6 +193,40 @@ where
     Ok(encode_mapper.encode_table)
 }
 
+/// Encodes the `(ilabel, olabel)` pair of each arc as a single label, keeping the weights : the
+/// transducer becomes a weighted acceptor, e.g. to be intersected with another acceptor by
+/// composition. Same as `encode` with `encode_labels` set and `encode_weights` unset, `decode`
+/// restoring the transducer with the returned `EncodeTable`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_properties::FstProperties;
+/// # use rustfst::fst_traits::ExpandedFst;
+/// # use rustfst::algorithms::{decode, encode_as_acceptor};
+/// # fn main() -> Fallible<()> {
+/// let fst_ref : VectorFst<IntegerWeight> = fst![1, 2 => 3, 4; 5];
+/// let mut fst = fst_ref.clone();
+///
+/// let encode_table = encode_as_acceptor(&mut fst)?;
+/// assert!(fst.properties()?.contains(FstProperties::ACCEPTOR));
+///
+/// decode(&mut fst, encode_table)?;
+/// assert_eq!(fst, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn encode_as_acceptor<F>(fst: &mut F) -> Fallible<EncodeTable<F::W>>
+where
+    F: MutableFst + ExpandedFst,
+{
+    encode(fst, true, false)
+}
+
 /// The `decode` operation takes as input an encoded FST and the corresponding `EncodeTable` object
 /// and reverts the encoding.
 pub fn decode<F>(fst: &mut F, encode_table: EncodeTable<F::W>) -> Fallible<()>
@@ -205,3 +239,46 @@ where
     rm_final_epsilon(fst)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::compose;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{ArcIterator, PathsIterator};
+    use crate::semirings::IntegerWeight;
+    use crate::utils::acceptor;
+    use crate::FstPath;
+
+    #[test]
+    fn test_encode_as_acceptor_intersect() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, IntegerWeight::new(2))?;
+        fst.add_arc(0, Arc::new(1, 2, IntegerWeight::new(3), 1))?;
+        fst.add_arc(0, Arc::new(1, 3, IntegerWeight::new(5), 1))?;
+        fst.add_arc(1, Arc::new(4, 5, IntegerWeight::new(7), 2))?;
+
+        let encode_table = encode_as_acceptor(&mut fst)?;
+        assert!(fst.properties()?.contains(FstProperties::ACCEPTOR));
+
+        // Keeps the path going through the second arc of the state 0
+        let labels = [
+            fst.arcs_iter(0)?.nth(1).unwrap().ilabel,
+            fst.arcs_iter(1)?.next().unwrap().ilabel,
+        ];
+        let filter: VectorFst<IntegerWeight> = acceptor(&labels, IntegerWeight::one());
+        let mut intersection: VectorFst<IntegerWeight> = compose(&fst, &filter)?;
+
+        decode(&mut intersection, encode_table)?;
+        let paths: Vec<_> = intersection.paths_iter().collect();
+        assert_eq!(
+            paths,
+            vec![FstPath::new(vec![1, 4], vec![3, 5], IntegerWeight::new(70))]
+        );
+        Ok(())
+    }
+}
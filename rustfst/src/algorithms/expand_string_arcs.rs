@@ -0,0 +1,108 @@
+use failure::Fallible;
+
+use crate::algorithms::factor_iterators::{
+    StringFactorLeft, StringFactorRestrict, StringFactorRight,
+};
+use crate::algorithms::{factor_weight, FactorWeightOptions, FactorWeightType};
+use crate::fst_traits::{ExpandedFst, Fst, MutableFst};
+use crate::semirings::{StringWeightLeft, StringWeightRestrict, StringWeightRight, WeightQuantize};
+
+/// Trait implemented by the string semirings, whose weights can be factored label by label.
+pub trait StringFactorWeight: WeightQuantize + 'static {
+    /// Factors the arc weights and the final weights of an FST into single-label weights.
+    fn factor_string_weights<F1, F2>(fst: &F1) -> Fallible<F2>
+    where
+        F1: Fst<W = Self>,
+        F2: MutableFst<W = Self> + ExpandedFst<W = Self>;
+}
+
+macro_rules! impl_string_factor_weight {
+    ($semiring: ident, $factor: ident) => {
+        impl StringFactorWeight for $semiring {
+            fn factor_string_weights<F1, F2>(fst: &F1) -> Fallible<F2>
+            where
+                F1: Fst<W = Self>,
+                F2: MutableFst<W = Self> + ExpandedFst<W = Self>,
+            {
+                let opts = FactorWeightOptions::new(
+                    FactorWeightType::FACTOR_ARC_WEIGHTS | FactorWeightType::FACTOR_FINAL_WEIGHTS,
+                );
+                factor_weight::<_, _, $factor>(fst, opts)
+            }
+        }
+    };
+}
+
+impl_string_factor_weight!(StringWeightLeft, StringFactorLeft);
+impl_string_factor_weight!(StringWeightRight, StringFactorRight);
+impl_string_factor_weight!(StringWeightRestrict, StringFactorRestrict);
+
+/// This operation splits every arc whose string weight contains more than one label into
+/// a chain of arcs carrying a single label each, going through newly created states.
+/// The labels of the final weights are moved to epsilon arcs leading to a new final state.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_traits::{MutableFst, CoreFst, ExpandedFst};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, StringWeightLeft};
+/// # use rustfst::algorithms::expand_string_arcs;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, StringWeightLeft::one())?;
+/// fst.add_arc(s0, Arc::new(1, 1, StringWeightLeft::from(vec![3, 4, 5]), s1))?;
+///
+/// expand_string_arcs(&mut fst)?;
+///
+/// assert_eq!(fst.num_states(), 4);
+/// assert_eq!(fst.num_arcs(0)?, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn expand_string_arcs<F>(fst: &mut F) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+    F::W: StringFactorWeight,
+{
+    *fst = F::W::factor_string_weights(fst)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::Semiring;
+    use crate::Arc;
+
+    #[test]
+    fn test_expand_string_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, StringWeightLeft::one())?;
+        fst.add_arc(s0, Arc::new(1, 2, StringWeightLeft::from(vec![3, 4]), s1))?;
+
+        expand_string_arcs(&mut fst)?;
+
+        let mut fst_ref = VectorFst::new();
+        let s0 = fst_ref.add_state();
+        let s1 = fst_ref.add_state();
+        let s2 = fst_ref.add_state();
+        fst_ref.set_start(s0)?;
+        fst_ref.set_final(s2, StringWeightLeft::one())?;
+        fst_ref.add_arc(s0, Arc::new(1, 2, StringWeightLeft::from(3), s1))?;
+        fst_ref.add_arc(s1, Arc::new(0, 0, StringWeightLeft::from(4), s2))?;
+
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+}
@@ -0,0 +1,101 @@
+use failure::Fallible;
+
+use crate::algorithms::shortest_distance_to_final;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::{ExpectationWeight, LogWeight, Semiring};
+use crate::Arc;
+
+/// Computes the expected number of arcs of the accepting paths of an FST over the log semiring,
+/// the probability of a path being given by its weight. Epsilon arcs are counted as any other
+/// arc. For a stochastic FST, this is the expected length of a path drawn from the FST, e.g. of
+/// a sentence generated by a language model.
+///
+/// The expectation is computed with the shortest distance to the final states over the
+/// `ExpectationWeight` semiring, each arc contributing a length of 1. It is normalized by the
+/// total probability of the FST, which only matters when the FST is not stochastic. An error is
+/// returned if the FST has no accepting path.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, LogWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::expected_length;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, LogWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 1, LogWeight::new(2f32.ln()), s1))?;
+/// fst.add_arc(s0, Arc::new(2, 2, LogWeight::new(2f32.ln()), s0))?;
+///
+/// assert!((expected_length(&fst)? - 2.0).abs() < 1e-2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn expected_length<F: ExpandedFst<W = LogWeight>>(fst: &F) -> Fallible<f32> {
+    let mut efst: VectorFst<ExpectationWeight<LogWeight>> = VectorFst::new();
+    efst.add_states(fst.num_states());
+    if let Some(start) = fst.start() {
+        efst.set_start(start)?;
+    }
+    for s in fst.states_iter() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            // In the log semiring, the probability times a length of 1 is the arc weight itself
+            let weight = ExpectationWeight::new((arc.weight, arc.weight));
+            efst.add_arc(s, Arc::new(arc.ilabel, arc.olabel, weight, arc.nextstate))?;
+        }
+        if let Some(final_weight) = unsafe { fst.final_weight_unchecked(s) } {
+            efst.set_final(
+                s,
+                ExpectationWeight::new((*final_weight, LogWeight::zero())),
+            )?;
+        }
+    }
+
+    let total = shortest_distance_to_final(&efst)?;
+    ensure!(
+        !total.value1().is_zero(),
+        "expected_length : the FST has no accepting path"
+    );
+    Ok((total.value1().value() - total.value2().value()).exp())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn log_weight(probability: f32) -> LogWeight {
+        LogWeight::new(-probability.ln())
+    }
+
+    #[test]
+    fn test_expected_length() -> Fallible<()> {
+        // Paths of lengths 1, 2 and 3 with probabilities 0.2, 0.5 and 0.3
+        let mut fst = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.set_final(4, LogWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, log_weight(0.2), 4))?;
+        fst.add_arc(0, Arc::new(2, 2, log_weight(0.5), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, LogWeight::one(), 4))?;
+        fst.add_arc(0, Arc::new(4, 4, log_weight(0.3), 2))?;
+        fst.add_arc(2, Arc::new(5, 5, LogWeight::one(), 3))?;
+        fst.add_arc(3, Arc::new(6, 6, LogWeight::one(), 4))?;
+
+        let expected = 0.2 * 1.0 + 0.5 * 2.0 + 0.3 * 3.0;
+        assert!((expected_length(&fst)? - expected).abs() < 1e-3);
+
+        // Not stochastic, the expectation is normalized
+        fst.set_final(4, log_weight(0.5))?;
+        assert!((expected_length(&fst)? - expected).abs() < 1e-3);
+
+        fst.delete_final_weight(4)?;
+        assert!(expected_length(&fst).is_err());
+        Ok(())
+    }
+}
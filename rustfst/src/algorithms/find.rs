@@ -0,0 +1,63 @@
+use crate::fst_traits::ExpandedFst;
+use crate::{Label, StateId};
+
+/// Returns the location of every arc of an FST whose input label is `label`, as the state the
+/// arc leaves and the position of the arc among the arcs of that state. The arcs are listed in
+/// the order of the states and, within a state, in the order of its arcs.
+///
+/// This makes it possible to rewrite a given label across the whole FST, e.g. a disambiguation
+/// symbol, with `MutableFst::arcs_iter_mut`.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::find_arcs_with_ilabel;
+/// let fst : VectorFst<IntegerWeight> = fst![3, 1, 3 => 2, 1, 1];
+///
+/// assert_eq!(find_arcs_with_ilabel(&fst, 3), vec![(0, 0), (2, 0)]);
+/// ```
+pub fn find_arcs_with_ilabel<F: ExpandedFst>(fst: &F, label: Label) -> Vec<(StateId, usize)> {
+    let mut locations = vec![];
+    for s in fst.states_iter() {
+        for (idx, arc) in unsafe { fst.arcs_iter_unchecked(s) }.enumerate() {
+            if arc.ilabel == label {
+                locations.push((s, idx));
+            }
+        }
+    }
+    locations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::Arc;
+
+    #[test]
+    fn test_find_arcs_with_ilabel() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(5, 1, TropicalWeight::one(), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(1, 5, TropicalWeight::one(), 2))?;
+        fst.add_arc(1, Arc::new(5, 5, TropicalWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::one(), 0))?;
+        fst.add_arc(2, Arc::new(5, 3, TropicalWeight::one(), 2))?;
+
+        assert_eq!(find_arcs_with_ilabel(&fst, 5), vec![(0, 0), (1, 1), (2, 1)]);
+        assert_eq!(find_arcs_with_ilabel(&fst, 4), vec![]);
+        Ok(())
+    }
+}
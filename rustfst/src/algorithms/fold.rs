@@ -0,0 +1,72 @@
+use crate::arc::Arc;
+use crate::fst_traits::ExpandedFst;
+use crate::StateId;
+
+/// Accumulates a value over all the arcs of an FST. `f` is called for each arc with the current
+/// value, the state the arc is leaving and the arc itself, and returns the new value. The states
+/// are visited in increasing order and the arcs in the order in which they are stored.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::fold_arcs;
+/// let fst : VectorFst<IntegerWeight> = fst![1, 2, 3 => 4, 5, 6];
+///
+/// // Number of arcs whose output label is even
+/// let n = fold_arcs(&fst, 0, |acc, _, arc| if arc.olabel % 2 == 0 { acc + 1 } else { acc });
+/// assert_eq!(n, 2);
+/// ```
+pub fn fold_arcs<F, B, G>(fst: &F, init: B, mut f: G) -> B
+where
+    F: ExpandedFst,
+    G: FnMut(B, StateId, &Arc<F::W>) -> B,
+{
+    let mut acc = init;
+    for s in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            acc = f(acc, s, arc);
+        }
+    }
+    acc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst, MutableFst, StateIterator};
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_fold_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.5), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(0.25), 2))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::new(3.0), 0))?;
+
+        let total = fold_arcs(&fst, 0.0f32, |acc, _, arc| acc + *arc.weight.value());
+
+        let mut total_ref = 0.0f32;
+        for s in fst.states_iter() {
+            for arc in fst.arcs_iter(s)? {
+                total_ref += *arc.weight.value();
+            }
+        }
+        assert_eq!(total, total_ref);
+        assert_eq!(total, 6.75);
+
+        let max_out_degree = fold_arcs(&fst, 0, |acc, s, _| acc.max(fst.num_arcs(s).unwrap()));
+        assert_eq!(max_out_degree, 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use failure::Fallible;
+
+use crate::fst_traits::{ExpandedFst, Fst};
+use crate::semirings::{DivideType, Semiring, StringWeightRestrict, WeaklyDivisibleSemiring};
+use crate::{Label, StateId, EPS_LABEL};
+
+/// Output labels emitted on one side of a pair of paths and not yet matched by the other side.
+/// At most one of the two strings is not `One`.
+type Delay = (StringWeightRestrict, StringWeightRestrict);
+
+fn output_weight(olabel: Label) -> StringWeightRestrict {
+    if olabel == EPS_LABEL {
+        StringWeightRestrict::one()
+    } else {
+        olabel.into()
+    }
+}
+
+/// Appends the output labels of two arcs read with the same input label to a delay and divides
+/// both sides by their common prefix. Returns `None` if the two outputs diverge.
+fn next_delay(delay: &Delay, olabel1: Label, olabel2: Label) -> Fallible<Option<Delay>> {
+    let out1 = delay.0.times(&output_weight(olabel1))?;
+    let out2 = delay.1.times(&output_weight(olabel2))?;
+    let prefix: Vec<Label> = out1
+        .value()
+        .unwrap_labels()
+        .iter()
+        .zip(out2.value().unwrap_labels().iter())
+        .take_while(|(l1, l2)| l1 == l2)
+        .map(|(l1, _)| *l1)
+        .collect();
+    let prefix: StringWeightRestrict = prefix.into();
+    let out1 = out1.divide(&prefix, DivideType::DivideLeft)?;
+    let out2 = out2.divide(&prefix, DivideType::DivideLeft)?;
+    if !out1.is_one() && !out2.is_one() {
+        Ok(None)
+    } else {
+        Ok(Some((out1, out2)))
+    }
+}
+
+/// Looks for a pair of states that can be reached with the same input string and leads to two
+/// different output strings for a same accepted input. Returns `None` if the FST is functional,
+/// i.e if each input string is mapped to at most one output string.
+///
+/// With `epsilon_as_label`, epsilon input labels are treated as regular symbols, as it is done in
+/// `determinize`. Otherwise, an arc with an epsilon input label is followed on one side while the
+/// other side stays in the same state.
+pub(crate) fn find_non_functional_state<F: Fst>(
+    fst: &F,
+    epsilon_as_label: bool,
+) -> Fallible<Option<(StateId, StateId)>> {
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+
+    // Pairs of states reachable by reading the same input string on both sides.
+    let mut pairs = vec![(start_state, start_state)];
+    let mut pairs_ids = HashMap::new();
+    pairs_ids.insert((start_state, start_state), 0);
+    let mut transitions: Vec<Vec<(usize, Label, Label)>> = vec![];
+    let mut i = 0;
+    while i < pairs.len() {
+        let (p, q) = pairs[i];
+        let mut next_pairs = vec![];
+        for arc1 in fst.arcs_iter(p)? {
+            if !epsilon_as_label && arc1.ilabel == EPS_LABEL {
+                next_pairs.push(((arc1.nextstate, q), arc1.olabel, EPS_LABEL));
+                continue;
+            }
+            for arc2 in fst.arcs_iter(q)? {
+                if arc1.ilabel == arc2.ilabel {
+                    next_pairs.push(((arc1.nextstate, arc2.nextstate), arc1.olabel, arc2.olabel));
+                }
+            }
+        }
+        if !epsilon_as_label {
+            for arc2 in fst.arcs_iter(q)?.filter(|arc| arc.ilabel == EPS_LABEL) {
+                next_pairs.push(((p, arc2.nextstate), EPS_LABEL, arc2.olabel));
+            }
+        }
+        let mut pair_transitions = vec![];
+        for (next_pair, olabel1, olabel2) in next_pairs {
+            let next_id = match pairs_ids.entry(next_pair) {
+                Entry::Occupied(e) => *e.get(),
+                Entry::Vacant(e) => {
+                    pairs.push(next_pair);
+                    *e.insert(pairs.len() - 1)
+                }
+            };
+            pair_transitions.push((next_id, olabel1, olabel2));
+        }
+        transitions.push(pair_transitions);
+        i += 1;
+    }
+
+    // Only the pairs from which a common input string leads to final states on both sides matter.
+    let mut reverse_transitions = vec![vec![]; pairs.len()];
+    for (id, pair_transitions) in transitions.iter().enumerate() {
+        for &(next_id, _, _) in pair_transitions {
+            reverse_transitions[next_id].push(id);
+        }
+    }
+    let mut coaccess = vec![false; pairs.len()];
+    let mut queue = VecDeque::new();
+    for (id, &(p, q)) in pairs.iter().enumerate() {
+        if fst.is_final(p)? && fst.is_final(q)? {
+            coaccess[id] = true;
+            queue.push_back(id);
+        }
+    }
+    while let Some(id) = queue.pop_front() {
+        for &prev_id in &reverse_transitions[id] {
+            if !coaccess[prev_id] {
+                coaccess[prev_id] = true;
+                queue.push_back(prev_id);
+            }
+        }
+    }
+
+    // In a functional FST, each of those pairs has a unique delay, which is empty if both
+    // states are final.
+    let mut delays: Vec<Option<Delay>> = vec![None; pairs.len()];
+    if coaccess[0] {
+        delays[0] = Some((StringWeightRestrict::one(), StringWeightRestrict::one()));
+        queue.push_back(0);
+    }
+    while let Some(id) = queue.pop_front() {
+        let delay = delays[id].clone().unwrap();
+        let (p, q) = pairs[id];
+        if (!delay.0.is_one() || !delay.1.is_one()) && fst.is_final(p)? && fst.is_final(q)? {
+            return Ok(Some((p, q)));
+        }
+        for &(next_id, olabel1, olabel2) in &transitions[id] {
+            if !coaccess[next_id] {
+                continue;
+            }
+            let next_delay = match next_delay(&delay, olabel1, olabel2)? {
+                Some(d) => d,
+                None => return Ok(Some(pairs[next_id])),
+            };
+            match &delays[next_id] {
+                Some(d) if *d != next_delay => return Ok(Some(pairs[next_id])),
+                Some(_) => {}
+                None => {
+                    delays[next_id] = Some(next_delay);
+                    queue.push_back(next_id);
+                }
+            }
+        }
+    }
+    Ok(None)
+}
+
+/// Returns whether a transducer is functional, i.e whether each input string is mapped to at most
+/// one output string. This is the precondition of `determinize` with
+/// `DeterminizeType::DeterminizeFunctional`.
+///
+/// The pairs of paths reading the same input string are explored as in the composition of the
+/// inverse of the transducer with itself, the outputs left to match on each side being tracked as
+/// `StringWeightRestrict` weights. The transducer is not functional if the outputs diverge.
+///
+/// Epsilon input labels match the empty string : the paths `ε:x a:y` and `a:z` read the same
+/// input `a`, hence a transducer having both isn't functional. Note that `determinize` handles
+/// epsilon as a regular label and accepts such a transducer with `DeterminizeFunctional`.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::is_functional;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, TropicalWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::one(), s1))?;
+/// assert!(is_functional(&fst)?);
+///
+/// fst.add_arc(s0, Arc::new(1, 3, TropicalWeight::one(), s1))?;
+/// assert!(!is_functional(&fst)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn is_functional<F: ExpandedFst>(fst: &F) -> Fallible<bool> {
+    Ok(find_non_functional_state(fst, false)?.is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_find_non_functional_state_functional() -> Fallible<()> {
+        // Two paths mapping the input 1 2 3 to the output 4 5 with different delays.
+        let mut fst = VectorFst::new();
+        fst.add_states(6);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 4, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(2, 5, TropicalWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 0, TropicalWeight::one(), 3))?;
+        fst.add_arc(0, Arc::new(1, 0, TropicalWeight::new(2.0), 4))?;
+        fst.add_arc(4, Arc::new(2, 4, TropicalWeight::one(), 5))?;
+        fst.add_arc(5, Arc::new(3, 5, TropicalWeight::one(), 3))?;
+        assert_eq!(find_non_functional_state(&fst, true)?, None);
+        assert_eq!(find_non_functional_state(&fst, false)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_functional() -> Fallible<()> {
+        // Maps 1 2 to 3 on one path and 1 2 2 to 3 4 on the other one.
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 3, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 0, TropicalWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(2, 4, TropicalWeight::one(), 3))?;
+        assert!(is_functional(&fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_functional_non_functional() -> Fallible<()> {
+        // The input 1 1 is mapped to both 2 2 and 2 3.
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(1, 2, TropicalWeight::one(), 2))?;
+        fst.add_arc(1, Arc::new(1, 3, TropicalWeight::one(), 3))?;
+        assert!(!is_functional(&fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_is_functional_input_epsilon() -> Fallible<()> {
+        // The input 1 is mapped to 3 4 by the path eps:3 1:4 and to 5 by the path 1:5.
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(0, 3, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(1, 4, TropicalWeight::one(), 2))?;
+        fst.add_arc(0, Arc::new(1, 5, TropicalWeight::one(), 2))?;
+        assert!(!is_functional(&fst)?);
+        // Epsilon being a regular label for determinize, the inputs eps 1 and 1 differ.
+        assert_eq!(find_non_functional_state(&fst, true)?, None);
+
+        // Functional once both paths output 3 4, the epsilon arc being read on either side.
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(0, 3, TropicalWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(1, 4, TropicalWeight::one(), 2))?;
+        fst.add_arc(0, Arc::new(1, 3, TropicalWeight::one(), 3))?;
+        fst.add_arc(3, Arc::new(0, 4, TropicalWeight::one(), 2))?;
+        assert!(is_functional(&fst)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,151 @@
+use failure::Fallible;
+
+use crate::algorithms::compose_filters::SequenceComposeFilter;
+use crate::algorithms::compose_with_filter;
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+
+/// This operation computes the intersection of two acceptors.
+/// If `A` accepts string `x` with weight `a` and `B` accepts `x` with weight `b`,
+/// then their intersection accepts `x` with weight `a ⊗ b`.
+///
+/// The intersection is the composition of the two acceptors with a `SequenceComposeFilter`, see
+/// [`compose_with_filter`](fn.compose_with_filter.html), so that the epsilons don't need to be at
+/// the same positions in both acceptors. An error is returned if either FST is not an acceptor.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::intersect;
+/// # fn main() -> Fallible<()> {
+/// let fst_1 : VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(1.0));
+///
+/// let fst_2 : VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(3.0));
+///
+/// let fst_ref : VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::new(4.0));
+///
+/// let intersection : VectorFst<_> = intersect(&fst_1, &fst_2)?;
+/// assert_eq!(intersection, fst_ref);
+/// # Ok(())
+/// # }
+/// ```
+pub fn intersect<W, F1, F2, F3>(fst_1: &F1, fst_2: &F2) -> Fallible<F3>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    F3: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    ensure!(
+        fst_1.properties()?.contains(FstProperties::ACCEPTOR),
+        "intersect : the first FST is not an acceptor"
+    );
+    ensure!(
+        fst_2.properties()?.contains(FstProperties::ACCEPTOR),
+        "intersect : the second FST is not an acceptor"
+    );
+    compose_with_filter(fst_1, fst_2, SequenceComposeFilter::new())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::TropicalWeight;
+    use crate::utils::{acceptor, transducer};
+    use crate::{FstPath, EPS_LABEL};
+
+    #[test]
+    fn test_intersect() -> Fallible<()> {
+        // Accepts 1 2* 3
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(3);
+        fst_1.set_start(0)?;
+        fst_1.set_final(2, TropicalWeight::new(0.5))?;
+        fst_1.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst_1.add_arc(1, Arc::new(2, 2, TropicalWeight::new(2.0), 1))?;
+        fst_1.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+
+        // Accepts 1 2 3 and 1 3 3
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(4);
+        fst_2.set_start(0)?;
+        fst_2.set_final(3, TropicalWeight::one())?;
+        fst_2.add_arc(0, Arc::new(1, 1, TropicalWeight::new(0.25), 1))?;
+        fst_2.add_arc(1, Arc::new(2, 2, TropicalWeight::new(0.25), 2))?;
+        fst_2.add_arc(1, Arc::new(3, 3, TropicalWeight::new(0.25), 2))?;
+        fst_2.add_arc(2, Arc::new(3, 3, TropicalWeight::new(0.25), 3))?;
+
+        let intersection: VectorFst<_> = intersect(&fst_1, &fst_2)?;
+        let paths: Vec<_> = intersection.paths_iter().collect();
+        assert_eq!(
+            paths,
+            vec![FstPath::new(
+                vec![1, 2, 3],
+                vec![1, 2, 3],
+                TropicalWeight::new(7.25)
+            )]
+        );
+
+        let fst_3: VectorFst<_> = acceptor(&[4], TropicalWeight::one());
+        let intersection: VectorFst<_> = intersect(&fst_1, &fst_3)?;
+        assert_eq!(intersection.paths_iter().count(), 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_epsilons_at_different_positions() -> Fallible<()> {
+        // Accepts 1 ε 2
+        let mut fst_1 = VectorFst::new();
+        fst_1.add_states(4);
+        fst_1.set_start(0)?;
+        fst_1.set_final(3, TropicalWeight::one())?;
+        fst_1.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst_1.add_arc(
+            1,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(2.0), 2),
+        )?;
+        fst_1.add_arc(2, Arc::new(2, 2, TropicalWeight::new(3.0), 3))?;
+
+        // Accepts ε 1 2
+        let mut fst_2 = VectorFst::new();
+        fst_2.add_states(4);
+        fst_2.set_start(0)?;
+        fst_2.set_final(3, TropicalWeight::one())?;
+        fst_2.add_arc(
+            0,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(0.5), 1),
+        )?;
+        fst_2.add_arc(1, Arc::new(1, 1, TropicalWeight::new(0.25), 2))?;
+        fst_2.add_arc(2, Arc::new(2, 2, TropicalWeight::new(0.25), 3))?;
+
+        let intersection: VectorFst<_> = intersect(&fst_1, &fst_2)?;
+        let paths: Vec<_> = intersection.paths_iter().collect();
+        assert_eq!(
+            paths,
+            vec![FstPath::new(
+                vec![1, 2],
+                vec![1, 2],
+                TropicalWeight::new(7.0)
+            )]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_intersect_not_acceptor() -> Fallible<()> {
+        let fst_1: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+        let fst_2: VectorFst<TropicalWeight> = transducer(&[1, 2], &[1, 3], TropicalWeight::one());
+
+        assert!(intersect::<_, _, _, VectorFst<_>>(&fst_1, &fst_2).is_err());
+        assert!(intersect::<_, _, _, VectorFst<_>>(&fst_2, &fst_1).is_err());
+        Ok(())
+    }
+}
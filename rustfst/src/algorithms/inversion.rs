@@ -1,9 +1,11 @@
-use std::mem::swap;
-
-use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::algorithms::arc_mappers::InvertArcMapper;
+use crate::algorithms::ArcMapper;
+use crate::fst_traits::MutableFst;
 
 /// This operation inverts the transduction corresponding to an FST
-/// by exchanging the FST's input and output labels.
+/// by exchanging the FST's input and output labels, with an `InvertArcMapper`.
+///
+/// Contrary to `arc_map`, the arcs are inverted even if the FST has no start state.
 ///
 /// # Example
 /// ```
@@ -17,21 +19,29 @@ use crate::fst_traits::{ExpandedFst, MutableFst};
 ///
 /// assert_eq!(fst, fst![3 => 2]);
 /// ```
-pub fn invert<F: ExpandedFst + MutableFst>(fst: &mut F) {
-    for state in 0..fst.num_states() {
+pub fn invert<F: MutableFst>(fst: &mut F) {
+    let mut mapper = InvertArcMapper {};
+    let states: Vec<_> = fst.states_iter().collect();
+    for state in states {
         for arc in unsafe { fst.arcs_iter_unchecked_mut(state) } {
-            swap(&mut arc.ilabel, &mut arc.olabel);
+            // The mapper never fails
+            mapper.arc_map(arc).unwrap();
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use std::mem::swap;
+
     use counter::Counter;
     use failure::Fallible;
 
-    use crate::fst_traits::PathsIterator;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, PathsIterator};
+    use crate::semirings::{IntegerWeight, Semiring};
     use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+    use crate::Arc;
 
     use super::*;
 
@@ -61,4 +71,34 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_invert_twice() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            let mut fst = data.fst.clone();
+
+            invert(&mut fst);
+            invert(&mut fst);
+
+            assert_eq!(
+                fst, data.fst,
+                "Test failing for invert twice on wFST {:?}",
+                &data.name
+            )
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_invert_without_start_state() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.add_arc(0, Arc::new(1, 2, IntegerWeight::one(), 1))?;
+
+        invert(&mut fst);
+
+        let arcs: Vec<_> = fst.arcs_iter(0)?.cloned().collect();
+        assert_eq!(arcs, vec![Arc::new(2, 1, IntegerWeight::one(), 1)]);
+        Ok(())
+    }
 }
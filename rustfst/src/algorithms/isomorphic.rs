@@ -7,11 +7,18 @@ use crate::fst_traits::ExpandedFst;
 use crate::semirings::Semiring;
 use crate::{Arc, StateId};
 
-struct Isomorphism<'a, W: Semiring, F1: ExpandedFst<W = W>, F2: ExpandedFst<W = W>> {
+struct Isomorphism<'a, W, F1, F2, WE>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    WE: Fn(&W, &W) -> Fallible<bool>,
+{
     fst_1: &'a F1,
     fst_2: &'a F2,
     state_pairs: Vec<Option<StateId>>,
     queue: VecDeque<(StateId, StateId)>,
+    weight_equal: WE,
 }
 
 /// Compare arcs in the order input label, output label, weight and nextstate.
@@ -43,13 +50,20 @@ pub fn arc_compare<W: Semiring>(arc_1: &Arc<W>, arc_2: &Arc<W>) -> Ordering {
     Ordering::Equal
 }
 
-impl<'a, W: Semiring, F1: ExpandedFst<W = W>, F2: ExpandedFst<W = W>> Isomorphism<'a, W, F1, F2> {
-    fn new(fst_1: &'a F1, fst_2: &'a F2) -> Self {
+impl<'a, W, F1, F2, WE> Isomorphism<'a, W, F1, F2, WE>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+    WE: Fn(&W, &W) -> Fallible<bool>,
+{
+    fn new(fst_1: &'a F1, fst_2: &'a F2, weight_equal: WE) -> Self {
         Self {
             fst_1,
             fst_2,
             state_pairs: vec![None; fst_1.num_states()],
             queue: VecDeque::new(),
+            weight_equal,
         }
     }
 
@@ -66,9 +80,15 @@ impl<'a, W: Semiring, F1: ExpandedFst<W = W>, F2: ExpandedFst<W = W>> Isomorphis
     }
 
     fn ismorphic_state(&mut self, s1: StateId, s2: StateId) -> Fallible<bool> {
-        if !(self.fst_1.final_weight(s1)? == self.fst_2.final_weight(s2)?) {
-            return Ok(false);
-        }
+        match (self.fst_1.final_weight(s1)?, self.fst_2.final_weight(s2)?) {
+            (Some(w1), Some(w2)) => {
+                if !(self.weight_equal)(w1, w2)? {
+                    return Ok(false);
+                }
+            }
+            (None, None) => {}
+            _ => return Ok(false),
+        };
 
         let narcs1 = self.fst_1.num_arcs(s1).unwrap();
         let narcs2 = self.fst_2.num_arcs(s2).unwrap();
@@ -92,7 +112,7 @@ impl<'a, W: Semiring, F1: ExpandedFst<W = W>, F2: ExpandedFst<W = W>> Isomorphis
             if arc1.olabel != arc2.olabel {
                 return Ok(false);
             }
-            if !(arc1.weight == arc2.weight) {
+            if !(self.weight_equal)(&arc1.weight, &arc2.weight)? {
                 return Ok(false);
             }
             if !(self.pair_state(arc1.nextstate, arc2.nextstate)) {
@@ -144,7 +164,22 @@ where
     F1: ExpandedFst<W = W>,
     F2: ExpandedFst<W = W>,
 {
-    let mut iso = Isomorphism::new(fst_1, fst_2);
+    let mut iso = Isomorphism::new(fst_1, fst_2, |w1: &W, w2: &W| Ok(w1 == w2));
+    iso.isomorphic()
+}
+
+/// Same as `isomorphic` but the weights are compared with `approx_equal` and `delta`.
+pub fn isomorphic_with_delta<W, F1, F2>(fst_1: &F1, fst_2: &F2, delta: f32) -> Fallible<bool>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+{
+    let mut iso = Isomorphism::new(
+        fst_1,
+        fst_2,
+        |w1: &W, w2: &W| Ok(w1.approx_equal(w2, delta)),
+    );
     iso.isomorphic()
 }
 
@@ -156,7 +191,7 @@ mod test {
     use crate::fst_impls::VectorFst;
     use crate::fst_traits::{MutableFst, TextParser};
     use crate::semirings::{LogWeight, Semiring};
-    use crate::Arc;
+    use crate::{Arc, KDELTA};
 
     #[test]
     fn test_isomorphic_1() -> Fallible<()> {
@@ -190,4 +225,23 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn test_isomorphic_with_delta() -> Fallible<()> {
+        let fst_1: VectorFst<LogWeight> = TextParser::from_text_string(
+            "0\t1\t12\t25\t1.0\n\
+             1\n",
+        )?;
+
+        let fst_2: VectorFst<LogWeight> = TextParser::from_text_string(
+            "0\t1\t12\t25\t1.01\n\
+             1\n",
+        )?;
+
+        assert!(!isomorphic(&fst_1, &fst_2)?);
+        assert!(!isomorphic_with_delta(&fst_1, &fst_2, KDELTA)?);
+        assert!(isomorphic_with_delta(&fst_1, &fst_2, 0.1)?);
+
+        Ok(())
+    }
 }
@@ -0,0 +1,89 @@
+use std::collections::HashMap;
+
+use failure::Fallible;
+
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::Label;
+
+/// Multiplies the weight of each arc on the right by the penalty associated with its input
+/// label. The arcs whose input label has no penalty are left unchanged.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use std::collections::HashMap;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::ArcIterator;
+/// # use rustfst::algorithms::apply_label_penalties;
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// let mut fst : VectorFst<IntegerWeight> = fst![2, 3 => 4, 5];
+/// let mut penalties = HashMap::new();
+/// penalties.insert(2, IntegerWeight::new(5));
+///
+/// apply_label_penalties(&mut fst, &penalties)?;
+///
+/// assert_eq!(fst.arcs_iter(0)?.next().unwrap().weight, IntegerWeight::new(5));
+/// assert_eq!(fst.arcs_iter(1)?.next().unwrap().weight, IntegerWeight::one());
+/// # Ok(())
+/// # }
+/// ```
+pub fn apply_label_penalties<F>(fst: &mut F, penalties: &HashMap<Label, F::W>) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+{
+    if penalties.is_empty() {
+        return Ok(());
+    }
+    for state_id in 0..fst.num_states() {
+        for arc in fst.arcs_iter_mut(state_id)? {
+            if let Some(penalty) = penalties.get(&arc.ilabel) {
+                arc.weight.times_assign(penalty)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::CoreFst;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_apply_label_penalties() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(1.0))?;
+        fst.add_arc(0, Arc::new(1, 4, TropicalWeight::new(0.5), 1))?;
+        fst.add_arc(0, Arc::new(2, 1, TropicalWeight::new(1.5), 2))?;
+        fst.add_arc(1, Arc::new(1, 1, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+
+        let mut penalties = HashMap::new();
+        penalties.insert(1, TropicalWeight::new(10.0));
+        penalties.insert(4, TropicalWeight::new(100.0));
+        apply_label_penalties(&mut fst, &penalties)?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(0)?;
+        fst_ref.set_final(2, TropicalWeight::new(1.0))?;
+        fst_ref.add_arc(0, Arc::new(1, 4, TropicalWeight::new(10.5), 1))?;
+        fst_ref.add_arc(0, Arc::new(2, 1, TropicalWeight::new(1.5), 2))?;
+        fst_ref.add_arc(1, Arc::new(1, 1, TropicalWeight::new(12.0), 2))?;
+        fst_ref.add_arc(1, Arc::new(3, 3, TropicalWeight::new(3.0), 2))?;
+
+        assert_eq!(fst, fst_ref);
+        assert_eq!(fst.final_weight(2)?, Some(&TropicalWeight::new(1.0)));
+        Ok(())
+    }
+}
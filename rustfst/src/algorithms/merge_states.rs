@@ -0,0 +1,143 @@
+use std::collections::HashSet;
+
+use failure::Fallible;
+
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::StateId;
+
+/// Merges a set of states of an FST into the first one, `states[0]`, which is returned.
+///
+/// The merged state gets the out-arcs of all the states, the arcs leading to any of them now
+/// lead to it and it is the start state if one of them was. Its final weight is the ⊕-sum of
+/// the final weights of the states, so that it is final as soon as one of them was, and not
+/// only the final weight of `states[0]`. The other states are left in the FST, without arcs and
+/// non-final, and can be removed with `connect`.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::merge_states;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s2, TropicalWeight::new(2.0))?;
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1))?;
+/// fst.add_arc(s0, Arc::new(2, 2, TropicalWeight::one(), s2))?;
+///
+/// assert_eq!(merge_states(&mut fst, &[s1, s2])?, s1);
+/// assert_eq!(fst.final_weight(s1)?, Some(&TropicalWeight::new(2.0)));
+/// assert_eq!(fst.num_arcs(s0)?, 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn merge_states<F>(fst: &mut F, states: &[StateId]) -> Fallible<StateId>
+where
+    F: MutableFst + ExpandedFst,
+{
+    ensure!(!states.is_empty(), "No state to merge");
+    for s in states {
+        ensure!(*s < fst.num_states(), "The state {:?} doesn't exist", s);
+    }
+    let merged = states[0];
+    let others: HashSet<_> = states.iter().cloned().filter(|s| *s != merged).collect();
+
+    let mut is_final = false;
+    let mut final_weight = F::W::zero();
+    for s in states.iter().take(1).chain(others.iter()) {
+        if let Some(w) = fst.final_weight(*s)? {
+            is_final = true;
+            final_weight.plus_assign(w)?;
+        }
+    }
+    if is_final {
+        fst.set_final(merged, final_weight)?;
+    }
+
+    for s in others.iter() {
+        fst.delete_final_weight(*s)?;
+        for arc in fst.pop_arcs(*s)? {
+            fst.add_arc(merged, arc)?;
+        }
+    }
+
+    for s in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked_mut(s) } {
+            if others.contains(&arc.nextstate) {
+                arc.nextstate = merged;
+            }
+        }
+    }
+
+    if let Some(start) = fst.start() {
+        if others.contains(&start) {
+            fst.set_start(merged)?;
+        }
+    }
+
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst};
+    use crate::semirings::ProbabilityWeight;
+
+    #[test]
+    fn test_merge_states_final_and_non_final() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, ProbabilityWeight::new(2.0))?;
+        fst.add_arc(0, Arc::new(1, 1, ProbabilityWeight::new(0.5), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, ProbabilityWeight::new(0.25), 2))?;
+
+        assert_eq!(merge_states(&mut fst, &[1, 2])?, 1);
+
+        assert_eq!(fst.final_weight(1)?, Some(&ProbabilityWeight::new(2.0)));
+        assert_eq!(fst.final_weight(2)?, None);
+        assert_eq!(fst.num_arcs(2)?, 0);
+        assert_eq!(
+            fst.arcs_iter(1)?.cloned().collect::<Vec<_>>(),
+            vec![Arc::new(2, 2, ProbabilityWeight::new(0.25), 1)]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_merge_states_two_finals() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(1)?;
+        fst.set_final(0, ProbabilityWeight::new(2.0))?;
+        fst.set_final(1, ProbabilityWeight::new(3.0))?;
+        fst.add_arc(1, Arc::new(1, 1, ProbabilityWeight::new(0.5), 0))?;
+        fst.add_arc(2, Arc::new(2, 2, ProbabilityWeight::new(0.5), 1))?;
+
+        assert_eq!(merge_states(&mut fst, &[0, 1])?, 0);
+
+        assert_eq!(fst.final_weight(0)?, Some(&ProbabilityWeight::new(5.0)));
+        assert_eq!(fst.final_weight(1)?, None);
+        assert_eq!(fst.start(), Some(0));
+        assert_eq!(
+            fst.arcs_iter(0)?.cloned().collect::<Vec<_>>(),
+            vec![Arc::new(1, 1, ProbabilityWeight::new(0.5), 0)]
+        );
+        assert_eq!(
+            fst.arcs_iter(2)?.cloned().collect::<Vec<_>>(),
+            vec![Arc::new(2, 2, ProbabilityWeight::new(0.5), 0)]
+        );
+        Ok(())
+    }
+}
@@ -8,7 +8,6 @@ use failure::Fallible;
 use stable_bst::TreeMap;
 
 use crate::algorithms::arc_compares::ilabel_compare;
-use crate::algorithms::arc_mappers::QuantizeMapper;
 use crate::algorithms::arc_unique;
 use crate::algorithms::factor_iterators::GallicFactorLeft;
 use crate::algorithms::partition::Partition;
@@ -17,15 +16,17 @@ use crate::algorithms::reverse;
 use crate::algorithms::weight_converters::{FromGallicConverter, ToGallicConverter};
 use crate::algorithms::Queue;
 use crate::algorithms::{
-    arc_map, arc_sort, connect, decode, encode, factor_weight, push_weights, weight_convert,
-    FactorWeightOptions, FactorWeightType, ReweightType,
+    arc_sort, connect, decode, determinize, encode, factor_weight, push_weights, quantize_fst,
+    reverse_with_superinitial, rm_epsilon, weight_convert, DeterminizeType, FactorWeightOptions,
+    FactorWeightType, ReweightType,
 };
 use crate::fst_impls::VectorFst;
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::ArcIterator;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst};
 use crate::semirings::{
-    GallicWeightLeft, Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize,
+    GallicWeightLeft, Semiring, SemiringProperties, StarSemiring, WeaklyDivisibleSemiring,
+    WeightQuantize,
 };
 use crate::Arc;
 use crate::StateId;
@@ -34,6 +35,79 @@ use crate::KDELTA;
 use crate::NO_STATE_ID;
 
 pub fn minimize<F>(ifst: &mut F, allow_nondet: bool) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+    F::W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
+    <<F as CoreFst>::W as Semiring>::ReverseWeight: 'static,
+{
+    minimize_approx(ifst, allow_nondet, KDELTA)
+}
+
+/// Minimizes an acceptor with Brzozowski's algorithm : the FST is reversed and determinized,
+/// twice. The first determinization merges the states having the same past once reversed, i.e.
+/// the same future in the input, and the second one gives back a deterministic FST. Epsilons are
+/// removed after each reversal as determinization treats them as regular labels.
+///
+/// Contrary to `minimize`, which requires a deterministic input over a non-idempotent semiring,
+/// the input doesn't need to be deterministic but its determinizations must terminate. The
+/// semiring must be its own reverse, which is the case of the tropical and log semirings.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{ExpandedFst, MutableFst};
+/// # use rustfst::algorithms::minimize_via_reverse;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// fst.add_states(3);
+/// fst.set_start(0)?;
+/// fst.set_final(1, TropicalWeight::one())?;
+/// fst.set_final(2, TropicalWeight::one())?;
+/// fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+/// fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(1.0), 2))?;
+///
+/// let min_fst: VectorFst<_> = minimize_via_reverse(&fst)?;
+/// assert_eq!(min_fst.num_states(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn minimize_via_reverse<W, F1, F2>(ifst: &F1) -> Fallible<F2>
+where
+    W: WeaklyDivisibleSemiring + WeightQuantize + StarSemiring + Semiring<ReverseWeight = W>,
+    W: 'static,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    if !ifst.properties()?.contains(FstProperties::ACCEPTOR) {
+        bail!("minimize_via_reverse only supports acceptors");
+    }
+
+    let rfst: VectorFst<W> = reverse_with_superinitial(ifst, false)?;
+    let rfst: VectorFst<W> = rm_epsilon(&rfst)?;
+    let dfst: VectorFst<W> = determinize(&rfst, DeterminizeType::DeterminizeFunctional)?;
+
+    let rfst: VectorFst<W> = reverse_with_superinitial(&dfst, false)?;
+    let rfst: VectorFst<W> = rm_epsilon(&rfst)?;
+    let mut ofst: F2 = determinize(&rfst, DeterminizeType::DeterminizeFunctional)?;
+    connect(&mut ofst)?;
+    Ok(ofst)
+}
+
+/// Same as `minimize` but the weights are quantized with `delta` instead of `KDELTA` before the
+/// states are compared : states whose weights, once pushed, are equal up to `delta` are merged.
+///
+/// With floating point weights, states that are equivalent up to rounding errors aren't merged
+/// for a small `delta`. A larger `delta` merges them but the minimization is then lossy : the
+/// weights of the result are only equal to the ones of the input up to `delta`, and states that
+/// aren't equivalent may be merged if their weights are close.
+///
+/// As the `PartialEq` and `Hash` implementations of the floating point weights compare them up to
+/// `KDELTA`, a `delta` smaller than `KDELTA` has the same effect as `KDELTA`.
+pub fn minimize_approx<F>(ifst: &mut F, allow_nondet: bool, delta: f32) -> Fallible<()>
 where
     F: MutableFst + ExpandedFst,
     F::W: WeaklyDivisibleSemiring + WeightQuantize + 'static,
@@ -58,13 +132,12 @@ where
         let mut to_gallic = ToGallicConverter {};
         let mut gfst: VectorFst<GallicWeightLeft<F::W>> = weight_convert(ifst, &mut to_gallic)?;
         push_weights(&mut gfst, ReweightType::ReweightToInitial, false)?;
-        let mut quantize_mapper = QuantizeMapper {};
-        arc_map(&mut gfst, &mut quantize_mapper)?;
+        quantize_fst(&mut gfst, delta)?;
         let encode_table = encode(&mut gfst, true, true)?;
         acceptor_minimize(&mut gfst, allow_acyclic_minimization)?;
         decode(&mut gfst, encode_table)?;
         let factor_opts: FactorWeightOptions = FactorWeightOptions {
-            delta: KDELTA,
+            delta,
             mode: FactorWeightType::FACTOR_FINAL_WEIGHTS | FactorWeightType::FACTOR_ARC_WEIGHTS,
             final_ilabel: 0,
             final_olabel: 0,
@@ -81,8 +154,7 @@ where
     } else if props.contains(FstProperties::WEIGHTED) {
         // Weighted acceptor
         push_weights(ifst, ReweightType::ReweightToInitial, false)?;
-        let mut quantize_mapper = QuantizeMapper {};
-        arc_map(ifst, &mut quantize_mapper)?;
+        quantize_fst(ifst, delta)?;
         let encode_table = encode(ifst, true, true)?;
         acceptor_minimize(ifst, allow_acyclic_minimization)?;
         decode(ifst, encode_table)
@@ -507,3 +579,77 @@ impl ArcIterCompare {
         xarc.ilabel > yarc.ilabel
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::TropicalWeight;
+
+    /// States 1 and 2 only differ by their final weight, `1.0` and `final_weight_2`, which can't
+    /// be pushed away as they have another path to the final state 3.
+    fn near_equivalent_fst(final_weight_2: f32) -> Fallible<VectorFst<TropicalWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(1, TropicalWeight::new(1.0))?;
+        fst.set_final(2, TropicalWeight::new(final_weight_2))?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(0.5), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(1.5), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(2.0), 3))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::new(2.0), 3))?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_minimize_approx() -> Fallible<()> {
+        let mut fst = near_equivalent_fst(1.01)?;
+        minimize_approx(&mut fst, false, 0.1)?;
+        assert_eq!(fst.num_states(), 3);
+
+        let mut fst = near_equivalent_fst(1.01)?;
+        minimize_approx(&mut fst, false, KDELTA)?;
+        assert_eq!(fst.num_states(), 4);
+
+        // The weights are compared up to KDELTA whatever the delta
+        let mut fst = near_equivalent_fst(1.000_001)?;
+        minimize_approx(&mut fst, false, 1e-9)?;
+        assert_eq!(fst.num_states(), 3);
+        Ok(())
+    }
+
+    #[test]
+    fn test_minimize_via_reverse() -> Fallible<()> {
+        // States 1 and 2 have the same future once the weights are pushed, so do states 3 and 4
+        let mut fst = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.set_final(4, TropicalWeight::new(2.0))?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(1.0), 3))?;
+        fst.add_arc(1, Arc::new(4, 4, TropicalWeight::new(2.0), 3))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::new(1.0), 4))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::new(2.0), 4))?;
+
+        let min_fst: VectorFst<TropicalWeight> = minimize_via_reverse(&fst)?;
+
+        let mut min_fst_ref = fst.clone();
+        minimize(&mut min_fst_ref, false)?;
+        assert_eq!(min_fst.num_states(), 3);
+        assert_eq!(min_fst.num_states(), min_fst_ref.num_states());
+        assert!(min_fst
+            .properties()?
+            .contains(FstProperties::I_DETERMINISTIC));
+
+        let mut paths: Vec<_> = min_fst.paths_iter().collect();
+        let mut paths_ref: Vec<_> = fst.paths_iter().collect();
+        paths.sort_by_key(|p| p.ilabels.clone());
+        paths_ref.sort_by_key(|p| p.ilabels.clone());
+        assert_eq!(paths, paths_ref);
+        Ok(())
+    }
+}
@@ -1,32 +1,58 @@
 mod all_pairs_shortest_distance;
+mod alphabet;
 mod arc_map;
 mod arc_sort;
 mod arc_sum;
 pub(crate) mod arc_unique;
+mod arcs_by_weight;
 mod closure;
+mod components;
 mod composition;
 mod concat;
+mod condensation;
 mod connect;
+mod convert;
+mod dangling_arcs;
 mod determinize;
 pub(crate) mod dfs_visit;
+mod diff;
 mod encode;
+mod expand_string_arcs;
+mod expectation;
 mod factor_weight;
+mod find;
+mod fold;
 mod fst_convert;
+mod functional;
+mod intersect;
 mod inversion;
 mod isomorphic;
+mod label_penalties;
+mod merge_states;
 mod minimize;
+mod ngram;
+mod oracle;
 mod partition;
+mod precomputed_matcher;
+mod print;
 mod projection;
 mod push;
+mod push_finals_to_arcs;
+mod quantize;
 mod queue;
 mod relabel_pairs;
 mod reverse;
+mod reverse_adjacency;
 mod reweight;
 mod rm_epsilon;
 mod rm_final_epsilon;
+#[cfg(feature = "rand")]
+mod sample_path;
 mod shortest_distance;
 mod shortest_path;
 mod state_sort;
+mod state_split;
+mod stats;
 mod top_sort;
 mod union;
 mod weight_convert;
@@ -39,6 +65,9 @@ pub mod arc_filters;
 /// Module that provide structures implementing the `ArcMapper` trait.
 pub mod arc_mappers;
 
+/// Filters restricting the pairs of arcs matched by `compose_with_filter`.
+pub mod compose_filters;
+
 pub(crate) mod visitors;
 
 #[allow(unused)]
@@ -58,36 +87,79 @@ pub mod arc_compares {
 
 pub use self::{
     all_pairs_shortest_distance::all_pairs_shortest_distance,
+    alphabet::{composable, input_labels, output_labels},
     arc_map::{arc_map, ArcMapper, FinalArc, MapFinalAction},
     arc_sort::arc_sort,
     arc_sum::arc_sum,
     arc_unique::arc_unique,
-    closure::{closure_plus, closure_star},
-    composition::compose,
+    arcs_by_weight::arcs_by_weight,
+    closure::{closure, closure_plus, closure_star, ClosureType},
+    components::num_connected_components,
+    composition::{
+        compose, compose_chain, compose_interpolated, compose_with_filter, compose_with_matcher,
+    },
     concat::concat,
-    connect::connect,
-    determinize::{determinize, determinize_with_distance, DeterminizeType},
-    encode::{decode, encode},
+    condensation::condensation,
+    connect::{
+        connect, connect_keep_ids, connect_with_config, trim_non_coaccessible, ConnectConfig,
+    },
+    convert::convert_fst,
+    dangling_arcs::{repair_dangling_arcs, DanglingPolicy},
+    determinize::{
+        determinize, determinize_with_config, determinize_with_delta, determinize_with_distance,
+        DeterminizeConfig, DeterminizeType,
+    },
+    diff::{diff_fsts, FstDiff},
+    encode::{decode, encode, encode_as_acceptor},
+    expand_string_arcs::{expand_string_arcs, StringFactorWeight},
+    expectation::expected_length,
+    find::find_arcs_with_ilabel,
+    fold::fold_arcs,
     fst_convert::fst_convert,
+    functional::is_functional,
+    intersect::intersect,
     inversion::invert,
-    isomorphic::isomorphic,
-    minimize::minimize,
-    projection::{project, ProjectType},
-    push::{push, push_weights, PushType},
+    isomorphic::{isomorphic, isomorphic_with_delta},
+    label_penalties::apply_label_penalties,
+    merge_states::merge_states,
+    minimize::{minimize, minimize_approx, minimize_via_reverse},
+    ngram::extract_ngrams,
+    oracle::oracle_error,
+    precomputed_matcher::{arcs_grouped_by_ilabel, PrecomputedMatcher},
+    print::fstprint,
+    projection::{project, project_assign, to_acceptor, ProjectType},
+    push::{
+        is_stochastic, push, push_to_stochastic, push_weights, push_weights_stochastic, PushType,
+    },
+    push_finals_to_arcs::push_finals_to_arcs,
+    quantize::quantize_fst,
     queue::{Queue, QueueType},
-    relabel_pairs::relabel_pairs,
-    reverse::reverse,
+    relabel_pairs::{compact_labels, relabel_pairs},
+    reverse::{reverse, reverse_with_adjacency, reverse_with_superinitial},
+    reverse_adjacency::ReverseAdjacency,
     reweight::{reweight, ReweightType},
-    rm_epsilon::rm_epsilon,
+    rm_epsilon::{epsilon_closure, has_epsilon_cycle, rm_epsilon, rm_epsilon_with_config},
     rm_final_epsilon::rm_final_epsilon,
-    shortest_distance::{shortest_distance, single_source_shortest_distance},
-    shortest_path::shortest_path,
+    shortest_distance::{
+        backward_shortest_distance, dial_shortest_distance, shortest_distance,
+        shortest_distance_dense, shortest_distance_scc, shortest_distance_to_final,
+        shortest_distance_with_delta, single_source_shortest_distance, tropical_shortest_distance,
+    },
+    shortest_path::{shortest_path, shortest_path_with_config, ShortestPathConfig},
     state_sort::state_sort,
+    state_split::{state_split, SplitArc, SplitNextState, SplitState, StateSplitter},
+    stats::{final_states, final_weights_dense},
     top_sort::top_sort,
-    union::union,
+    union::{union, union_aligned},
     weight_convert::{weight_convert, WeightConverter},
 };
 
+#[cfg(feature = "rayon")]
+pub use self::shortest_distance::par_shortest_distance_scc;
+
+#[cfg(feature = "rand")]
+pub use self::sample_path::{sample_path, SamplingWeight};
+
 #[allow(unused)]
 pub(crate) use self::factor_weight::{
     factor_weight, FactorIterator, FactorWeightOptions, FactorWeightType,
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+
+use failure::Fallible;
+
+use crate::algorithms::{
+    backward_shortest_distance, single_source_shortest_distance, ReverseAdjacency,
+};
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Label, EPS_LABEL};
+
+/// Computes the weighted counts of the sequences of `n` consecutive input labels of an acyclic
+/// FST, epsilons being skipped. The count of a sequence is the ⊕-sum over its occurrences of the
+/// weight of the paths going through the occurrence, i.e. the shortest distance to the state
+/// where it starts ⊗ the weight of its arcs ⊗ the shortest distance from the state where it ends
+/// to the final states. With the probability semiring on a normalized lattice, these are the
+/// expected counts of the n-grams.
+///
+/// An error is returned if the FST is cyclic or if `n` is zero.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::extract_ngrams;
+/// # use rustfst::utils::acceptor;
+/// # fn main() -> Fallible<()> {
+/// let fst: VectorFst<IntegerWeight> = acceptor(&[1, 2, 1, 2], IntegerWeight::one());
+///
+/// let bigrams = extract_ngrams(&fst, 2)?;
+/// assert_eq!(bigrams.len(), 2);
+/// assert_eq!(bigrams[&vec![1, 2]], IntegerWeight::new(2));
+/// assert_eq!(bigrams[&vec![2, 1]], IntegerWeight::new(1));
+/// # Ok(())
+/// # }
+/// ```
+pub fn extract_ngrams<F: ExpandedFst>(fst: &F, n: usize) -> Fallible<HashMap<Vec<Label>, F::W>> {
+    ensure!(n > 0, "The length of the n-grams must be positive");
+    ensure!(
+        fst.properties()?.contains(FstProperties::ACYCLIC),
+        "Extracting n-grams requires an acyclic FST"
+    );
+
+    let mut counts = HashMap::new();
+    let start = match fst.start() {
+        Some(s) => s,
+        None => return Ok(counts),
+    };
+
+    let mut alpha = single_source_shortest_distance(fst, start)?;
+    alpha.resize(fst.num_states(), F::W::zero());
+    let beta = backward_shortest_distance(fst, &ReverseAdjacency::new(fst))?;
+
+    // Walks every window of n labels starting with a non-epsilon arc, from every reachable
+    // state. The FST being acyclic, the walk terminates.
+    let mut stack = vec![];
+    for (p, alpha_p) in alpha.iter().enumerate() {
+        if alpha_p.is_zero() {
+            continue;
+        }
+        for arc in unsafe { fst.arcs_iter_unchecked(p) } {
+            if arc.ilabel != EPS_LABEL {
+                stack.push((arc.nextstate, vec![arc.ilabel], alpha_p.times(&arc.weight)?));
+            }
+        }
+        while let Some((q, labels, weight)) = stack.pop() {
+            if labels.len() == n {
+                let count = weight.times(&beta[q])?;
+                if !count.is_zero() {
+                    counts
+                        .entry(labels)
+                        .or_insert_with(F::W::zero)
+                        .plus_assign(&count)?;
+                }
+                continue;
+            }
+            for arc in unsafe { fst.arcs_iter_unchecked(q) } {
+                let mut next_labels = labels.clone();
+                if arc.ilabel != EPS_LABEL {
+                    next_labels.push(arc.ilabel);
+                }
+                stack.push((arc.nextstate, next_labels, weight.times(&arc.weight)?));
+            }
+        }
+    }
+
+    Ok(counts)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::IntegerWeight;
+
+    #[test]
+    fn test_extract_ngrams() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, IntegerWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, IntegerWeight::new(2), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, IntegerWeight::new(3), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, IntegerWeight::one(), 2))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, 3, IntegerWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(1, 1, IntegerWeight::one(), 3))?;
+        fst.add_arc(2, Arc::new(4, 4, IntegerWeight::new(5), 3))?;
+
+        let bigrams = extract_ngrams(&fst, 2)?;
+
+        let mut bigrams_ref = HashMap::new();
+        bigrams_ref.insert(vec![1, 3], IntegerWeight::new(2 + 2 * 5));
+        bigrams_ref.insert(vec![2, 3], IntegerWeight::new(3 + 3 * 5));
+        bigrams_ref.insert(vec![3, 1], IntegerWeight::new(2 + 3));
+        bigrams_ref.insert(vec![3, 4], IntegerWeight::new(2 * 5 + 3 * 5));
+        bigrams_ref.insert(vec![1, 1], IntegerWeight::new(2));
+        bigrams_ref.insert(vec![1, 4], IntegerWeight::new(2 * 5));
+        bigrams_ref.insert(vec![2, 1], IntegerWeight::new(3));
+        bigrams_ref.insert(vec![2, 4], IntegerWeight::new(3 * 5));
+        assert_eq!(bigrams, bigrams_ref);
+
+        assert!(extract_ngrams(&fst, 4)?.is_empty());
+        assert!(extract_ngrams(&fst, 0).is_err());
+
+        fst.add_arc(2, Arc::new(5, 5, IntegerWeight::one(), 0))?;
+        assert!(extract_ngrams(&fst, 2).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use failure::Fallible;
+
+use crate::algorithms::compose_filters::SequenceComposeFilter;
+use crate::algorithms::{compose_with_filter, output_labels, shortest_distance};
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst};
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::utils::{acceptor, edit_transducer};
+use crate::{Label, EPS_LABEL};
+
+/// Computes the oracle error of a lattice, i.e the minimum edit distance between the `reference`
+/// label sequence and the output labels of any successful path of the lattice. The weights of
+/// the lattice are ignored.
+///
+/// The lattice is composed with an edit-distance transducer and the linear acceptor of the
+/// reference, the epsilons being matched by a `SequenceComposeFilter`. The oracle error is the
+/// shortest distance of the result in the tropical semiring.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::algorithms::oracle_error;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut lattice = VectorFst::new();
+/// let s0 = lattice.add_state();
+/// let s1 = lattice.add_state();
+/// let s2 = lattice.add_state();
+/// lattice.set_start(s0)?;
+/// lattice.set_final(s2, TropicalWeight::one())?;
+/// lattice.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1))?;
+/// lattice.add_arc(s1, Arc::new(2, 2, TropicalWeight::one(), s2))?;
+///
+/// assert_eq!(oracle_error(&lattice, &[1, 2])?, 0);
+/// assert_eq!(oracle_error(&lattice, &[1, 3, 2])?, 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn oracle_error<F: ExpandedFst>(lattice: &F, reference: &[Label]) -> Fallible<usize> {
+    let mut unweighted_lattice = VectorFst::new();
+    unweighted_lattice.add_states(lattice.num_states());
+    if let Some(start_state) = lattice.start() {
+        unweighted_lattice.set_start(start_state)?;
+    }
+    for s in lattice.states_iter() {
+        for arc in lattice.arcs_iter(s)? {
+            unweighted_lattice.add_arc(
+                s,
+                Arc::new(arc.ilabel, arc.olabel, TropicalWeight::one(), arc.nextstate),
+            )?;
+        }
+        if lattice.is_final(s)? {
+            unweighted_lattice.set_final(s, TropicalWeight::one())?;
+        }
+    }
+
+    let mut alphabet = output_labels(lattice);
+    alphabet.extend(reference.iter().filter(|l| **l != EPS_LABEL));
+    let alphabet: Vec<_> = alphabet.into_iter().collect();
+    let edit_fst: VectorFst<TropicalWeight> = edit_transducer(&alphabet, 1.0, 1.0, 1.0);
+
+    let reference_fst: VectorFst<TropicalWeight> = acceptor(reference, TropicalWeight::one());
+
+    let aligned_fst: VectorFst<_> =
+        compose_with_filter(&unweighted_lattice, &edit_fst, SequenceComposeFilter::new())?;
+    let aligned_fst: VectorFst<_> =
+        compose_with_filter(&aligned_fst, &reference_fst, SequenceComposeFilter::new())?;
+
+    let dists = shortest_distance(&aligned_fst, false)?;
+    let mut oracle = TropicalWeight::zero();
+    for (s, d) in dists.iter().enumerate() {
+        if let Some(final_weight) = aligned_fst.final_weight(s)? {
+            oracle.plus_assign(d.times(final_weight)?)?;
+        }
+    }
+    if oracle.is_zero() {
+        bail!("oracle_error : the lattice has no successful path")
+    }
+    Ok(oracle.value().round() as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_oracle_error() -> Fallible<()> {
+        // Lattice whose outputs are 1 2 3, 1 4 3 and any number of 5.
+        let mut lattice = VectorFst::new();
+        lattice.add_states(5);
+        lattice.set_start(0)?;
+        lattice.set_final(3, TropicalWeight::one())?;
+        lattice.set_final(4, TropicalWeight::one())?;
+        lattice.add_arc(0, Arc::new(1, 1, TropicalWeight::new(0.5), 1))?;
+        lattice.add_arc(1, Arc::new(2, 2, TropicalWeight::new(1.5), 2))?;
+        lattice.add_arc(1, Arc::new(4, 4, TropicalWeight::new(0.5), 2))?;
+        lattice.add_arc(2, Arc::new(3, 3, TropicalWeight::new(1.0), 3))?;
+        lattice.add_arc(0, Arc::new(5, EPS_LABEL, TropicalWeight::new(3.0), 4))?;
+        lattice.add_arc(4, Arc::new(6, 5, TropicalWeight::new(3.0), 4))?;
+
+        assert_eq!(oracle_error(&lattice, &[1, 2, 3])?, 0);
+        assert_eq!(oracle_error(&lattice, &[1, 4, 4, 3])?, 1);
+        assert_eq!(oracle_error(&lattice, &[2, 2])?, 2);
+        assert_eq!(oracle_error(&lattice, &[5, 5, 5, 5])?, 0);
+        assert_eq!(oracle_error(&lattice, &[])?, 0);
+        Ok(())
+    }
+}
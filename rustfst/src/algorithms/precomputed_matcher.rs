@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap};
+
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_traits::ExpandedFst;
+use crate::{Label, StateId};
+
+/// Index of the arcs of an FST by state and input label, built once and queried in O(1).
+///
+/// It is meant to be reused when many FSTs are composed against the same, usually large, FST
+/// (e.g. a lexicon) with [`compose_with_matcher`](fn.compose_with_matcher.html), instead of
+/// looking for the matching arcs again at each composition.
+#[derive(Debug)]
+pub struct PrecomputedMatcher<'a, F: ExpandedFst> {
+    fst: &'a F,
+    arcs_by_ilabel: Vec<HashMap<Label, Vec<&'a Arc<F::W>>>>,
+}
+
+impl<'a, F: ExpandedFst> PrecomputedMatcher<'a, F> {
+    /// Indexes the arcs of every state of `fst` by input label.
+    pub fn new(fst: &'a F) -> Self {
+        let mut arcs_by_ilabel = Vec::with_capacity(fst.num_states());
+        for s in 0..fst.num_states() {
+            let mut arcs: HashMap<Label, Vec<&'a Arc<F::W>>> = HashMap::new();
+            for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+                arcs.entry(arc.ilabel).or_default().push(arc);
+            }
+            arcs_by_ilabel.push(arcs);
+        }
+        Self {
+            fst,
+            arcs_by_ilabel,
+        }
+    }
+
+    /// Returns the indexed FST.
+    pub fn fst(&self) -> &'a F {
+        self.fst
+    }
+
+    /// Returns the arcs leaving `state` with `label` as input label, in the order in which they
+    /// appear in the FST.
+    pub fn find(&self, state: StateId, label: Label) -> &[&'a Arc<F::W>] {
+        self.arcs_by_ilabel
+            .get(state)
+            .and_then(|arcs| arcs.get(&label))
+            .map_or(&[][..], |arcs| arcs.as_slice())
+    }
+}
+
+/// Returns the arcs leaving `state` grouped by input label, the arcs of a group being in the
+/// order in which they appear in the FST. Contrary to `PrecomputedMatcher`, which indexes every
+/// state at once, only the arcs of `state` are visited.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::arcs_grouped_by_ilabel;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.add_arc(s0, Arc::new(2, 1, TropicalWeight::one(), s0))?;
+/// fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::one(), s0))?;
+/// fst.add_arc(s0, Arc::new(2, 3, TropicalWeight::one(), s0))?;
+///
+/// let groups = arcs_grouped_by_ilabel(&fst, s0)?;
+/// assert_eq!(groups.keys().cloned().collect::<Vec<_>>(), vec![1, 2]);
+/// assert_eq!(groups[&2].len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn arcs_grouped_by_ilabel<F: ExpandedFst>(
+    fst: &F,
+    state: StateId,
+) -> Fallible<BTreeMap<Label, Vec<&Arc<F::W>>>> {
+    let mut groups: BTreeMap<Label, Vec<&Arc<F::W>>> = BTreeMap::new();
+    for arc in fst.arcs_iter(state)? {
+        groups.entry(arc.ilabel).or_default().push(arc);
+    }
+    Ok(groups)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_arcs_grouped_by_ilabel() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        let arcs = vec![
+            Arc::new(3, 1, TropicalWeight::new(1.0), 1),
+            Arc::new(1, 2, TropicalWeight::new(2.0), 2),
+            Arc::new(3, 3, TropicalWeight::new(3.0), 2),
+            Arc::new(0, 4, TropicalWeight::new(4.0), 1),
+            Arc::new(1, 5, TropicalWeight::new(5.0), 1),
+            Arc::new(3, 6, TropicalWeight::new(6.0), 0),
+        ];
+        for arc in arcs.iter() {
+            fst.add_arc(0, arc.clone())?;
+        }
+
+        let groups = arcs_grouped_by_ilabel(&fst, 0)?;
+
+        let mut groups_ref = BTreeMap::new();
+        groups_ref.insert(0, vec![&arcs[3]]);
+        groups_ref.insert(1, vec![&arcs[1], &arcs[4]]);
+        groups_ref.insert(3, vec![&arcs[0], &arcs[2], &arcs[5]]);
+        assert_eq!(groups, groups_ref);
+
+        assert!(arcs_grouped_by_ilabel(&fst, 1)?.is_empty());
+        assert!(arcs_grouped_by_ilabel(&fst, 3).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::fmt::Write;
+
+use failure::Fallible;
+
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::{Label, StateId, SymbolTable};
+
+/// Prints an FST in the text format of OpenFST's `fstprint`, byte for byte. The labels are
+/// replaced by their symbol when a symbol table is given, an error being returned if a label is
+/// missing from it.
+///
+/// Contrary to `text`, the weights equal to `one()` are omitted, the weights are rendered like
+/// OpenFST does (six significant digits, `Infinity`) and each state is followed by its final
+/// weight. States without any arc are printed as final states, with an `Infinity` weight if
+/// they are not final.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::fstprint;
+/// # use rustfst::SymbolTable;
+/// # fn main() -> Fallible<()> {
+/// let symt = symt!["a", "b"];
+/// let fst: VectorFst<TropicalWeight> = transducer(&[1], &[2], TropicalWeight::new(0.5));
+///
+/// assert_eq!(fstprint(&fst, Some(&symt), Some(&symt))?, "0\t1\ta\tb\n1\t0.5\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn fstprint<F>(
+    fst: &F,
+    isyms: Option<&SymbolTable>,
+    osyms: Option<&SymbolTable>,
+) -> Fallible<String>
+where
+    F: ExpandedFst,
+    F::W: Semiring<Type = f32>,
+{
+    let mut out = String::new();
+    if let Some(start) = fst.start() {
+        print_state(fst, start, isyms, osyms, &mut out)?;
+        for s in 0..fst.num_states() {
+            if s != start {
+                print_state(fst, s, isyms, osyms, &mut out)?;
+            }
+        }
+    }
+    Ok(out)
+}
+
+fn print_state<F>(
+    fst: &F,
+    state: StateId,
+    isyms: Option<&SymbolTable>,
+    osyms: Option<&SymbolTable>,
+    out: &mut String,
+) -> Fallible<()>
+where
+    F: ExpandedFst,
+    F::W: Semiring<Type = f32>,
+{
+    let mut has_arcs = false;
+    for arc in fst.arcs_iter(state)? {
+        write!(
+            out,
+            "{}\t{}\t{}\t{}",
+            state,
+            arc.nextstate,
+            print_label(arc.ilabel, isyms)?,
+            print_label(arc.olabel, osyms)?
+        )?;
+        if !arc.weight.is_one() {
+            write!(out, "\t{}", print_weight(*arc.weight.value()))?;
+        }
+        out.push('\n');
+        has_arcs = true;
+    }
+
+    let final_weight = fst.final_weight(state)?;
+    if final_weight.is_some() || !has_arcs {
+        write!(out, "{}", state)?;
+        match final_weight {
+            Some(w) if w.is_one() => {}
+            Some(w) => write!(out, "\t{}", print_weight(*w.value()))?,
+            None => write!(out, "\t{}", print_weight(*F::W::zero().value()))?,
+        };
+        out.push('\n');
+    }
+    Ok(())
+}
+
+fn print_label(label: Label, symt: Option<&SymbolTable>) -> Fallible<String> {
+    match symt {
+        Some(symt) => symt
+            .get_symbol(label)
+            .map(|s| s.to_string())
+            .ok_or_else(|| format_err!("Label {} is not mapped to any symbol", label)),
+        None => Ok(label.to_string()),
+    }
+}
+
+/// Renders a float the way a C++ stream does with its default precision (`%g` with six
+/// significant digits), as OpenFST does.
+fn print_weight(value: f32) -> String {
+    if value.is_nan() {
+        return "BadNumber".to_string();
+    }
+    if value.is_infinite() {
+        return if value > 0.0 { "Infinity" } else { "-Infinity" }.to_string();
+    }
+    if value == 0.0 {
+        return if value.is_sign_negative() { "-0" } else { "0" }.to_string();
+    }
+
+    // OpenFST prints the float once converted to a double
+    let value = f64::from(value);
+    let scientific = format!("{:.5e}", value);
+    let exponent_pos = scientific.find('e').unwrap();
+    let exponent: i32 = scientific[exponent_pos + 1..].parse().unwrap();
+    if (-4..6).contains(&exponent) {
+        let fixed = format!("{:.*}", (5 - exponent) as usize, value);
+        strip_trailing_zeros(&fixed).to_string()
+    } else {
+        format!(
+            "{}e{}{:02}",
+            strip_trailing_zeros(&scientific[..exponent_pos]),
+            if exponent < 0 { '-' } else { '+' },
+            exponent.abs()
+        )
+    }
+}
+
+fn strip_trailing_zeros(s: &str) -> &str {
+    if s.contains('.') {
+        s.trim_end_matches('0').trim_end_matches('.')
+    } else {
+        s
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::TropicalWeight;
+    use crate::symt;
+
+    #[test]
+    fn test_fstprint_symbols() -> Fallible<()> {
+        let isyms = symt!["a", "b", "c"];
+        let osyms = symt!["x", "y"];
+
+        let mut fst = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(1)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.set_final(2, TropicalWeight::new(1.0 / 3.0))?;
+        fst.add_arc(1, Arc::new(1, 1, TropicalWeight::one(), 2))?;
+        fst.add_arc(1, Arc::new(3, 0, TropicalWeight::new(2.5), 0))?;
+        fst.add_arc(0, Arc::new(0, 2, TropicalWeight::new(1234567.0), 3))?;
+        fst.add_arc(2, Arc::new(2, 2, TropicalWeight::new(0.0001), 3))?;
+
+        // The output without symbol tables is checked against OpenFST in `tests_openfst`.
+        // Only the label columns of the arcs change when the symbol tables are given.
+        let fstprint_ref: String = fstprint(&fst, None, None)?
+            .lines()
+            .map(|line| -> Fallible<String> {
+                let mut fields: Vec<String> = line.split('\t').map(|f| f.to_string()).collect();
+                if fields.len() >= 4 {
+                    fields[2] = isyms.get_symbol(fields[2].parse()?).unwrap().to_string();
+                    fields[3] = osyms.get_symbol(fields[3].parse()?).unwrap().to_string();
+                }
+                Ok(format!("{}\n", fields.join("\t")))
+            })
+            .collect::<Fallible<_>>()?;
+        assert_eq!(fstprint(&fst, Some(&isyms), Some(&osyms))?, fstprint_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_fstprint_missing_symbol() -> Fallible<()> {
+        let symt = symt!["a"];
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.set_final(1, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(2, 1, TropicalWeight::one(), 1))?;
+
+        assert!(fstprint(&fst, Some(&symt), None).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_print_weight() {
+        assert_eq!(print_weight(1.0), "1");
+        assert_eq!(print_weight(-2.5), "-2.5");
+        assert_eq!(print_weight(0.1), "0.1");
+        assert_eq!(print_weight(123456.0), "123456");
+        assert_eq!(print_weight(1234567.0), "1.23457e+06");
+        assert_eq!(print_weight(0.00001), "1e-05");
+        assert_eq!(print_weight(std::f32::INFINITY), "Infinity");
+    }
+}
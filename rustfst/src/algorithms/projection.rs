@@ -1,4 +1,5 @@
 use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
 
 #[derive(Debug, Clone, PartialEq, PartialOrd, Copy)]
 /// Different types of labels projection in a FST.
@@ -46,22 +47,75 @@ pub enum ProjectType {
 /// # }
 /// ```
 pub fn project<F: ExpandedFst + MutableFst>(fst: &mut F, project_type: ProjectType) {
-    match project_type {
-        ProjectType::ProjectInput => {
-            for state in 0..fst.num_states() {
-                for arc in unsafe { fst.arcs_iter_unchecked_mut(state) } {
-                    arc.olabel = arc.ilabel;
-                }
+    project_assign(fst, project_type)
+}
+
+/// In-place projection of an FST onto its domain or range, `project` being kept as an alias.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::{acceptor, transducer};
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::{project_assign, ProjectType};
+/// # fn main() -> Fallible<()> {
+/// let mut fst : VectorFst<IntegerWeight> = fst![2 => 3];
+/// project_assign(&mut fst, ProjectType::ProjectInput);
+///
+/// assert_eq!(fst, fst![2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn project_assign<F: ExpandedFst + MutableFst>(fst: &mut F, project_type: ProjectType) {
+    to_acceptor(fst, project_type, false)
+}
+
+/// Turns an FST into an acceptor of its input or output language : the labels are projected
+/// and, if `strip_weights` is set, all the non-`zero()` weights (arcs and final states) are
+/// replaced by `one()`, in a single pass over the arcs. The result is the same as `project`
+/// followed by an arc map with `RmWeightMapper`.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::{acceptor, transducer};
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::{to_acceptor, ProjectType};
+/// # fn main() -> Fallible<()> {
+/// let mut fst : VectorFst<IntegerWeight> = fst![2 => 3; 4];
+/// to_acceptor(&mut fst, ProjectType::ProjectOutput, true);
+///
+/// assert_eq!(fst, fst![3]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn to_acceptor<F: ExpandedFst + MutableFst>(
+    fst: &mut F,
+    project_type: ProjectType,
+    strip_weights: bool,
+) {
+    for state in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked_mut(state) } {
+            match project_type {
+                ProjectType::ProjectInput => arc.olabel = arc.ilabel,
+                ProjectType::ProjectOutput => arc.ilabel = arc.olabel,
+            };
+            if strip_weights && !arc.weight.is_zero() {
+                arc.weight = F::W::one();
             }
         }
-        ProjectType::ProjectOutput => {
-            for state in 0..fst.num_states() {
-                for arc in unsafe { fst.arcs_iter_unchecked_mut(state) } {
-                    arc.ilabel = arc.olabel;
+        if strip_weights {
+            if let Some(final_weight) = unsafe { fst.final_weight_unchecked_mut(state) } {
+                if !final_weight.is_zero() {
+                    *final_weight = F::W::one();
                 }
             }
         }
-    };
+    }
 }
 
 #[cfg(test)]
@@ -69,6 +123,7 @@ mod tests {
     use counter::Counter;
     use failure::Fallible;
 
+    use crate::algorithms::arc_mappers::RmWeightMapper;
     use crate::fst_traits::PathsIterator;
     use crate::proptest_fst::proptest_fst;
     use crate::test_data::vector_fst::get_vector_fsts_for_tests;
@@ -148,4 +203,25 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_to_acceptor_generic() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            for project_type in &[ProjectType::ProjectInput, ProjectType::ProjectOutput] {
+                let mut fst_ref = data.fst.clone();
+                project(&mut fst_ref, *project_type);
+                fst_ref.arc_map(&mut RmWeightMapper {})?;
+
+                let mut fst = data.fst.clone();
+                to_acceptor(&mut fst, *project_type, true);
+
+                assert_eq!(
+                    fst, fst_ref,
+                    "Test failing for to_acceptor {:?} on wFST {:?}",
+                    project_type, &data.name
+                );
+            }
+        }
+        Ok(())
+    }
 }
@@ -12,7 +12,7 @@ use crate::algorithms::{
 };
 use crate::fst_impls::VectorFst;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst, MutableFst};
-use crate::semirings::{DivideType, Semiring};
+use crate::semirings::{DivideType, LogWeight, Semiring};
 use crate::semirings::{
     GallicWeightLeft, GallicWeightRight, StringWeightLeft, StringWeightRight,
     WeaklyDivisibleSemiring, WeightQuantize,
@@ -39,7 +39,7 @@ pub fn push_weights<F>(
 ) -> Fallible<()>
 where
     F: Fst + ExpandedFst + MutableFst,
-    F::W: WeaklyDivisibleSemiring,
+    F::W: WeaklyDivisibleSemiring + 'static,
     <<F as CoreFst>::W as Semiring>::ReverseWeight: 'static,
 {
     let dist = shortest_distance(fst, reweight_type == ReweightType::ReweightToInitial)?;
@@ -201,3 +201,168 @@ where
         Ok(fst_convert(ifst))
     }
 }
+
+/// Computes the ⊕-sum of the weights of the arcs leaving a state and of its final weight.
+fn state_total_weight<F: ExpandedFst>(fst: &F, state: usize) -> Fallible<F::W> {
+    let mut total = fst.final_weight(state)?.cloned().unwrap_or_else(F::W::zero);
+    for arc in fst.arcs_iter(state)? {
+        total.plus_assign(&arc.weight)?;
+    }
+    Ok(total)
+}
+
+/// Returns whether an FST over the log semiring is stochastic, i.e whether at each state
+/// the ⊕-sum of the weights of the outgoing arcs and of the final weight is equal to One()
+/// up to `delta`. States with no outgoing mass are ignored.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, LogWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::is_stochastic;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::{Arc, KDELTA};
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, LogWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 1, LogWeight::new(2f32.ln()), s1))?;
+///
+/// assert!(!is_stochastic(&fst, KDELTA)?);
+///
+/// fst.add_arc(s0, Arc::new(2, 2, LogWeight::new(2f32.ln()), s1))?;
+///
+/// assert!(is_stochastic(&fst, KDELTA)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn is_stochastic<F: ExpandedFst<W = LogWeight>>(fst: &F, delta: f32) -> Fallible<bool> {
+    for s in fst.states_iter() {
+        let total = state_total_weight(fst, s)?;
+        if !total.is_zero() && total.value().abs() > delta {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// Pushes the weights of an FST over the log semiring towards the initial state and then
+/// normalizes each state so that the result is stochastic: at each state the ⊕-sum of the
+/// weights of the outgoing arcs and of the final weight is equal to One(). The resulting FST
+/// is a locally normalized model in which the weight of each path is divided by the total
+/// weight of the FST.
+///
+/// The states whose outgoing mass is already equal to One() up to `delta` are left unchanged.
+pub fn push_to_stochastic<F>(fst: &mut F, delta: f32) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst<W = LogWeight>,
+{
+    push_weights(fst, ReweightType::ReweightToInitial, false)?;
+    for s in 0..fst.num_states() {
+        let total = state_total_weight(fst, s)?;
+        if total.is_zero() || total.value().abs() <= delta {
+            continue;
+        }
+        for arc in unsafe { fst.arcs_iter_unchecked_mut(s) } {
+            arc.weight.divide_assign(&total, DivideType::DivideLeft)?;
+        }
+        if let Some(final_weight) = unsafe { fst.final_weight_unchecked_mut(s) } {
+            final_weight.divide_assign(&total, DivideType::DivideLeft)?;
+        }
+    }
+    Ok(())
+}
+
+/// Pushes the weights of an FST over the log semiring towards the initial state, as
+/// `push_weights` with `ReweightToInitial` does, and returns whether the result is stochastic
+/// up to `delta` (see `is_stochastic`).
+///
+/// Only `LogWeight` is supported, as by `is_stochastic`: an FST over the real semiring has to be
+/// converted to the log semiring first.
+///
+/// The pushed FST is stochastic unless the total weight is kept on the start state, i.e.
+/// `remove_total_weight` is false and the total weight is not `one()`, or some arcs lead to
+/// states from which no final state can be reached, their weight being left unchanged.
+pub fn push_weights_stochastic<F>(
+    fst: &mut F,
+    remove_total_weight: bool,
+    delta: f32,
+) -> Fallible<bool>
+where
+    F: MutableFst + ExpandedFst<W = LogWeight>,
+{
+    push_weights(fst, ReweightType::ReweightToInitial, remove_total_weight)?;
+    is_stochastic(fst, delta)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_traits::PathsIterator;
+    use crate::KDELTA;
+
+    #[test]
+    fn test_push_to_stochastic() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(1, LogWeight::new(2.0))?;
+        fst.set_final(2, LogWeight::new(0.7))?;
+        fst.add_arc(0, Arc::new(1, 1, LogWeight::new(0.5), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, LogWeight::new(1.2), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, LogWeight::new(0.3), 2))?;
+        fst.add_arc(0, Arc::new(4, 4, LogWeight::new(3.0), 2))?;
+
+        let paths_before: Vec<_> = fst.paths_iter().collect();
+        let mut total_weight = LogWeight::zero();
+        for path in paths_before.iter() {
+            total_weight.plus_assign(&path.weight)?;
+        }
+
+        assert!(!is_stochastic(&fst, KDELTA)?);
+        push_to_stochastic(&mut fst, KDELTA)?;
+        assert!(is_stochastic(&fst, KDELTA)?);
+
+        let paths_after: Vec<_> = fst.paths_iter().collect();
+        assert_eq!(paths_before.len(), paths_after.len());
+        for (path_before, path_after) in paths_before.iter().zip(paths_after.iter()) {
+            assert_eq!(path_before.ilabels, path_after.ilabels);
+            let expected_weight = path_before
+                .weight
+                .divide(&total_weight, DivideType::DivideLeft)?;
+            assert!((expected_weight.value() - path_after.weight.value()).abs() < 1e-3);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_weights_stochastic() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(1, LogWeight::new(2.0))?;
+        fst.set_final(2, LogWeight::new(0.7))?;
+        fst.add_arc(0, Arc::new(1, 1, LogWeight::new(0.5), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, LogWeight::new(1.2), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, LogWeight::new(0.3), 2))?;
+
+        // The total weight is kept on the start state
+        let mut fst_total = fst.clone();
+        assert!(!push_weights_stochastic(&mut fst_total, false, KDELTA)?);
+
+        let mut fst_normalized = fst.clone();
+        assert!(push_weights_stochastic(&mut fst_normalized, true, KDELTA)?);
+
+        // The mass of an arc leading to a dead end can't be normalized
+        let dead_end = fst.add_state();
+        fst.add_arc(0, Arc::new(4, 4, LogWeight::new(0.5), dead_end))?;
+        assert!(!push_weights_stochastic(&mut fst, true, KDELTA)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::EPS_LABEL;
+
+/// Moves the final weights of an FST onto epsilon arcs leading to a single super-final state
+/// whose final weight is `one()`. Every other state ends up non-final, which is the form
+/// expected by algorithms assuming a single final state. The paths and their weights are
+/// preserved.
+///
+/// An FST whose only final state already has a final weight of `one()` and no out-arcs is left
+/// untouched.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::push_finals_to_arcs;
+/// # use rustfst::fst_traits::{CoreFst, FinalStatesIterator, MutableFst};
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s0, TropicalWeight::new(1.0))?;
+/// fst.set_final(s1, TropicalWeight::new(2.0))?;
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1))?;
+///
+/// push_finals_to_arcs(&mut fst)?;
+///
+/// assert_eq!(fst.final_states_iter().count(), 1);
+/// assert_eq!(fst.final_weight(2)?, Some(&TropicalWeight::one()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn push_finals_to_arcs<F>(fst: &mut F) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+{
+    let finals: Vec<_> = (0..fst.num_states())
+        .filter_map(|s| unsafe { fst.final_weight_unchecked(s) }.map(|w| (s, w.clone())))
+        .collect();
+
+    match finals.as_slice() {
+        [] => return Ok(()),
+        [(s, w)] if w.is_one() && unsafe { fst.num_arcs_unchecked(*s) } == 0 => return Ok(()),
+        _ => {}
+    };
+
+    let super_final = fst.add_state();
+    unsafe { fst.set_final_unchecked(super_final, F::W::one()) };
+
+    for (s, w) in finals {
+        unsafe {
+            fst.add_arc_unchecked(s, Arc::new(EPS_LABEL, EPS_LABEL, w, super_final));
+        }
+        fst.delete_final_weight(s)?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, FinalStatesIterator, PathsIterator};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_push_finals_to_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(1, TropicalWeight::new(0.5))?;
+        fst.set_final(2, TropicalWeight::new(2.0))?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 4, TropicalWeight::new(1.5), 2))?;
+        fst.add_arc(0, Arc::new(5, 6, TropicalWeight::new(3.0), 3))?;
+
+        let paths_ref: Vec<_> = fst.paths_iter().collect();
+
+        push_finals_to_arcs(&mut fst)?;
+
+        let finals: Vec<_> = fst.final_states_iter().map(|s| s.state_id).collect();
+        assert_eq!(finals, vec![4]);
+        assert_eq!(fst.final_weight(4)?, Some(&TropicalWeight::one()));
+
+        let paths: Vec<_> = fst.paths_iter().collect();
+        assert_eq!(paths.len(), paths_ref.len());
+        for path in &paths_ref {
+            assert!(paths.contains(path), "Missing path {:?}", path);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_push_finals_to_arcs_single_final() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.set_final(1, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        let fst_ref = fst.clone();
+
+        push_finals_to_arcs(&mut fst)?;
+
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+}
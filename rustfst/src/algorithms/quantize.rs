@@ -0,0 +1,81 @@
+use failure::Fallible;
+
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::WeightQuantize;
+
+/// Quantizes in place the weights of all the arcs and the final weights of an FST with
+/// `delta`, e.g. to canonicalize the weights before hashing an FST.
+///
+/// Unlike `arc_map` with a `QuantizeMapper`, the weights are quantized with any `delta` in a
+/// single pass over the states, without copying them.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, MutableFst};
+/// # use rustfst::algorithms::quantize_fst;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s0, TropicalWeight::new(1.3))?;
+///
+/// quantize_fst(&mut fst, 0.5)?;
+///
+/// assert_eq!(fst.final_weight(s0)?, Some(&TropicalWeight::new(1.5)));
+/// # Ok(())
+/// # }
+/// ```
+pub fn quantize_fst<F>(fst: &mut F, delta: f32) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+    F::W: WeightQuantize,
+{
+    for state in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked_mut(state) } {
+            arc.weight.quantize_assign(delta)?;
+        }
+        if let Some(final_weight) = unsafe { fst.final_weight_unchecked_mut(state) } {
+            final_weight.quantize_assign(delta)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst};
+    use crate::semirings::{Semiring, TropicalWeight};
+    use crate::Arc;
+
+    #[test]
+    fn test_quantize_fst() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(0.37))?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.13), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(-2.61), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(0.74), 2))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::zero(), 0))?;
+
+        let delta = 0.25;
+        quantize_fst(&mut fst, delta)?;
+
+        let mut weights = vec![];
+        for state in 0..fst.num_states() {
+            weights.extend(fst.arcs_iter(state)?.map(|arc| *arc.weight.value()));
+            weights.extend(fst.final_weight(state)?.map(|w| *w.value()));
+        }
+        assert_eq!(weights, vec![1.25, -2.5, 0.75, std::f32::INFINITY, 0.25]);
+        for w in weights.into_iter().filter(|w| w.is_finite()) {
+            assert_eq!((w / delta).fract(), 0.0);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use crate::StateId;
+
+/// Monotone priority queue over small integer priorities, as used by Dial's algorithm.
+///
+/// The states are stored in `max_step + 1` circular buckets, one per priority. The priority of
+/// an enqueued state must lie between the priority of the last dequeued state and that priority
+/// plus `max_step`, which always holds when the priorities are shortest distances and
+/// `max_step` is the largest arc weight. Both operations are then amortized `O(1)` instead of
+/// the `O(log n)` of a binary heap.
+#[derive(Debug, Clone)]
+pub struct BucketQueue {
+    buckets: Vec<Vec<StateId>>,
+    current: usize,
+    len: usize,
+}
+
+impl BucketQueue {
+    pub fn new(max_step: usize) -> Self {
+        Self {
+            buckets: vec![vec![]; max_step + 1],
+            current: 0,
+            len: 0,
+        }
+    }
+
+    pub fn enqueue(&mut self, state: StateId, priority: usize) {
+        debug_assert!(priority >= self.current && priority - self.current < self.buckets.len());
+        let n_buckets = self.buckets.len();
+        self.buckets[priority % n_buckets].push(state);
+        self.len += 1;
+    }
+
+    /// Removes a state with the lowest priority and returns it along with its priority.
+    pub fn dequeue(&mut self) -> Option<(StateId, usize)> {
+        if self.len == 0 {
+            return None;
+        }
+        let n_buckets = self.buckets.len();
+        loop {
+            if let Some(state) = self.buckets[self.current % n_buckets].pop() {
+                self.len -= 1;
+                return Some((state, self.current));
+            }
+            self.current += 1;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn clear(&mut self) {
+        self.buckets.iter_mut().for_each(|b| b.clear());
+        self.current = 0;
+        self.len = 0;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bucket_queue() {
+        let mut queue = BucketQueue::new(3);
+        assert_eq!(queue.dequeue(), None);
+
+        queue.enqueue(4, 2);
+        queue.enqueue(1, 0);
+        queue.enqueue(2, 3);
+        assert_eq!(queue.dequeue(), Some((1, 0)));
+        queue.enqueue(5, 1);
+        assert_eq!(queue.dequeue(), Some((5, 1)));
+        assert_eq!(queue.dequeue(), Some((4, 2)));
+        queue.enqueue(3, 5);
+        assert_eq!(queue.dequeue(), Some((2, 3)));
+        assert_eq!(queue.is_empty(), false);
+        assert_eq!(queue.dequeue(), Some((3, 5)));
+        assert_eq!(queue.is_empty(), true);
+        assert_eq!(queue.dequeue(), None);
+    }
+}
@@ -1,4 +1,5 @@
 mod auto_queue;
+mod bucket_queue;
 mod fifo_queue;
 mod lifo_queue;
 mod scc_queue;
@@ -8,6 +9,7 @@ mod top_order_queue;
 mod trivial_queue;
 
 pub use self::auto_queue::AutoQueue;
+pub use self::bucket_queue::BucketQueue;
 pub use self::fifo_queue::FifoQueue;
 pub use self::lifo_queue::LifoQueue;
 pub use self::scc_queue::SccQueue;
@@ -3,8 +3,9 @@ use std::collections::HashMap;
 
 use failure::{bail, format_err, Fallible, ResultExt};
 
+use crate::algorithms::{input_labels, output_labels};
 use crate::fst_traits::{ExpandedFst, MutableFst};
-use crate::StateId;
+use crate::{Label, StateId};
 
 fn iterator_to_hashmap<I>(pairs: I) -> Fallible<HashMap<StateId, StateId>>
 where
@@ -28,6 +29,9 @@ where
 /// FST using pairs of the form (old_ID, new_ID); omitted indices are
 /// identity-mapped.
 ///
+/// When the FST is an acceptor and only `ipairs` is given, `opairs` being empty, the output
+/// labels are relabeled with `ipairs` as well so that the FST remains an acceptor.
+///
 /// # Example
 /// ```
 /// #[macro_use] extern crate rustfst;
@@ -53,9 +57,13 @@ where
     let map_ilabels = iterator_to_hashmap(ipairs)
         .with_context(|_| format_err!("Error while creating the HashMap for ipairs"))?;
 
-    let map_olabels = iterator_to_hashmap(opairs)
+    let mut map_olabels = iterator_to_hashmap(opairs)
         .with_context(|_| format_err!("Error while creating the HashMap for opairs"))?;
 
+    if map_olabels.is_empty() && !map_ilabels.is_empty() && fst.is_acceptor() {
+        map_olabels = map_ilabels.clone();
+    }
+
     let states: Vec<_> = fst.states_iter().collect();
     for state_id in states {
         for arc in fst.arcs_iter_mut(state_id)? {
@@ -71,11 +79,61 @@ where
     Ok(())
 }
 
+/// Relabels the input and output labels of an FST so that each of them lies in a contiguous
+/// range `1..=k`, `k` being the number of distinct non-epsilon labels on that side. The order of
+/// the labels is preserved and epsilon is kept at 0.
+///
+/// Returns the mappings from the old input and output labels to the new ones, e.g. to update the
+/// symbol tables.
+///
+/// # Example
+/// ```
+/// #[macro_use] extern crate rustfst;
+/// # use rustfst::utils::transducer;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::compact_labels;
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// let mut fst : VectorFst<IntegerWeight> = fst![12, 40 => 7];
+/// let (imapping, omapping) = compact_labels(&mut fst)?;
+///
+/// assert_eq!(fst, fst![1, 2 => 1]);
+/// assert_eq!(imapping[&40], 2);
+/// assert_eq!(omapping[&7], 1);
+/// # Ok(())
+/// # }
+/// ```
+pub fn compact_labels<F>(fst: &mut F) -> Fallible<(HashMap<Label, Label>, HashMap<Label, Label>)>
+where
+    F: ExpandedFst + MutableFst,
+{
+    let imapping: HashMap<_, _> = input_labels(fst)
+        .into_iter()
+        .enumerate()
+        .map(|(i, l)| (l, i + 1))
+        .collect();
+    let omapping: HashMap<_, _> = output_labels(fst)
+        .into_iter()
+        .enumerate()
+        .map(|(i, l)| (l, i + 1))
+        .collect();
+
+    relabel_pairs(
+        fst,
+        imapping.iter().map(|(l1, l2)| (*l1, *l2)),
+        omapping.iter().map(|(l1, l2)| (*l1, *l2)),
+    )?;
+
+    Ok((imapping, omapping))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::arc::Arc;
     use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, Fst};
     use crate::semirings::{IntegerWeight, Semiring};
 
     #[test]
@@ -117,4 +175,73 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_compact_labels() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, IntegerWeight::one())?;
+        fst.add_arc(s0, Arc::new(30, 0, IntegerWeight::new(2), s1))?;
+        fst.add_arc(s0, Arc::new(7, 1000, IntegerWeight::new(3), s1))?;
+        fst.add_arc(s1, Arc::new(0, 25, IntegerWeight::new(4), s0))?;
+        fst.add_arc(s1, Arc::new(512, 1000, IntegerWeight::new(5), s1))?;
+        let fst_ref = fst.clone();
+
+        let (imapping, omapping) = compact_labels(&mut fst)?;
+
+        assert_eq!(
+            input_labels(&fst).into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 3]
+        );
+        assert_eq!(
+            output_labels(&fst).into_iter().collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert_eq!(imapping[&7], 1);
+        assert_eq!(imapping[&512], 3);
+        assert_eq!(omapping[&25], 1);
+
+        // Epsilons are left untouched
+        assert_eq!(fst.arcs_iter(s0)?.next().unwrap().olabel, 0);
+        assert_eq!(fst.arcs_iter(s1)?.next().unwrap().ilabel, 0);
+
+        let ipairs: Vec<_> = imapping.into_iter().map(|(l1, l2)| (l2, l1)).collect();
+        let opairs: Vec<_> = omapping.into_iter().map(|(l1, l2)| (l2, l1)).collect();
+        relabel_pairs(&mut fst, ipairs, opairs)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_relabel_pairs_acceptor() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s1, IntegerWeight::one())?;
+        fst.add_arc(s0, Arc::new(1, 1, IntegerWeight::new(2), s1))?;
+        fst.add_arc(s0, Arc::new(2, 2, IntegerWeight::new(3), s1))?;
+
+        relabel_pairs(&mut fst, vec![(1, 4)], vec![])?;
+
+        assert!(fst.is_acceptor());
+        let labels: Vec<_> = fst
+            .arcs_iter(s0)?
+            .map(|arc| (arc.ilabel, arc.olabel))
+            .collect();
+        assert_eq!(labels, vec![(4, 4), (2, 2)]);
+
+        // Transducers only get their input labels relabeled
+        fst.add_arc(s1, Arc::new(2, 3, IntegerWeight::one(), s1))?;
+        relabel_pairs(&mut fst, vec![(2, 5)], vec![])?;
+        let labels: Vec<_> = fst
+            .arcs_iter(s0)?
+            .chain(fst.arcs_iter(s1)?)
+            .map(|arc| (arc.ilabel, arc.olabel))
+            .collect();
+        assert_eq!(labels, vec![(4, 4), (5, 2), (5, 3)]);
+        Ok(())
+    }
 }
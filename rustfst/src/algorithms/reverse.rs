@@ -1,8 +1,10 @@
 use failure::Fallible;
 
+use crate::algorithms::ReverseAdjacency;
 use crate::arc::Arc;
 use crate::fst_traits::{ExpandedFst, MutableFst};
 use crate::semirings::Semiring;
+use crate::StateId;
 
 /// Reverses an FST. The reversed result is written to an output mutable FST.
 /// If A transduces string x to y with weight a, then the reverse of A
@@ -20,6 +22,48 @@ where
     F1: ExpandedFst<W = W>,
     F2: MutableFst<W = W::ReverseWeight> + ExpandedFst<W = W::ReverseWeight>,
 {
+    reverse_with_superinitial(ifst, true)
+}
+
+/// Same as `reverse` but, when `require_superinitial` is false and the FST has a single final
+/// state whose final weight is `one()`, that state becomes the start state of the reversed FST
+/// instead of a superinitial state being added. The state ids are then preserved, whereas the
+/// state `s` of the FST becomes the state `s + 1` of the reversed FST when a superinitial state
+/// is added, as it is always numbered 0.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst};
+/// # use rustfst::algorithms::{reverse, reverse_with_superinitial};
+/// # fn main() -> Fallible<()> {
+/// let fst: VectorFst<TropicalWeight> = acceptor(&[1, 2], TropicalWeight::one());
+///
+/// let reversed_fst: VectorFst<TropicalWeight> = reverse_with_superinitial(&fst, false)?;
+/// assert_eq!(reversed_fst.num_states(), 3);
+/// assert_eq!(reversed_fst.start(), Some(2));
+///
+/// let reversed_fst: VectorFst<TropicalWeight> = reverse(&fst)?;
+/// assert_eq!(reversed_fst.num_states(), 4);
+/// # Ok(())
+/// # }
+/// ```
+pub fn reverse_with_superinitial<W, F1, F2>(ifst: &F1, require_superinitial: bool) -> Fallible<F2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W::ReverseWeight> + ExpandedFst<W = W::ReverseWeight>,
+{
+    if !require_superinitial {
+        if let Some(final_state) = single_final_state_with_weight_one(ifst) {
+            return reverse_from_final_state(ifst, final_state);
+        }
+    }
+
     let mut ofst = F2::new();
     ofst.reserve_states(ifst.num_states());
     let istart = ifst.start();
@@ -63,3 +107,192 @@ where
 
     Ok(ofst)
 }
+
+/// Returns the only final state of the FST if its final weight is `one()`.
+fn single_final_state_with_weight_one<F: ExpandedFst>(fst: &F) -> Option<StateId> {
+    let mut final_states = (0..fst.num_states()).filter(|s| unsafe { fst.is_final_unchecked(*s) });
+    match (final_states.next(), final_states.next()) {
+        (Some(s), None) if unsafe { fst.final_weight_unchecked(s) }.unwrap().is_one() => Some(s),
+        _ => None,
+    }
+}
+
+/// Reverses an FST whose only final state is `final_state`, with a final weight of `one()`,
+/// the state ids being preserved.
+fn reverse_from_final_state<W, F1, F2>(ifst: &F1, final_state: StateId) -> Fallible<F2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W::ReverseWeight> + ExpandedFst<W = W::ReverseWeight>,
+{
+    let mut ofst = F2::new();
+    ofst.add_states(ifst.num_states());
+
+    let mut states_arcs = vec![vec![]; ifst.num_states()];
+    for is in 0..ifst.num_states() {
+        for iarc in unsafe { ifst.arcs_iter_unchecked(is) } {
+            let weight = iarc.weight.reverse()?;
+            states_arcs[iarc.nextstate].push(Arc::new(iarc.ilabel, iarc.olabel, weight, is));
+        }
+    }
+    states_arcs
+        .into_iter()
+        .enumerate()
+        .for_each(|(s, arcs)| unsafe { ofst.set_arcs_unchecked(s, arcs) });
+
+    if let Some(istart) = ifst.start() {
+        ofst.set_final(istart, W::ReverseWeight::one())?;
+    }
+    ofst.set_start(final_state)?;
+
+    Ok(ofst)
+}
+
+/// Same as `reverse` but the incoming arcs of the states are taken from `adjacency` instead of
+/// being computed, which saves a pass over the arcs when several backward operations are run on
+/// the same FST. `adjacency` must have been built from `ifst`.
+pub fn reverse_with_adjacency<W, F1, F2>(ifst: &F1, adjacency: &ReverseAdjacency<W>) -> Fallible<F2>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W::ReverseWeight> + ExpandedFst<W = W::ReverseWeight>,
+{
+    ensure!(
+        adjacency.num_states() == ifst.num_states(),
+        "The adjacency has {} states but the FST has {} states",
+        adjacency.num_states(),
+        ifst.num_states()
+    );
+    let mut ofst = F2::new();
+    ofst.reserve_states(ifst.num_states() + 1);
+    let istart = ifst.start();
+    let ostart = ofst.add_state();
+
+    let mut superinitial_arcs = vec![];
+    for is in 0..ifst.num_states() {
+        let os = ofst.add_state();
+        if Some(is) == istart {
+            ofst.set_final(os, W::ReverseWeight::one())?;
+        }
+        if let Some(w) = unsafe { ifst.final_weight_unchecked(is) } {
+            superinitial_arcs.push(Arc::new(0, 0, w.reverse()?, os));
+        }
+
+        let mut arcs = Vec::with_capacity(adjacency.in_arcs(is)?.len());
+        for in_arc in adjacency.in_arcs(is)? {
+            let weight = in_arc.weight.reverse()?;
+            arcs.push(Arc::new(
+                in_arc.ilabel,
+                in_arc.olabel,
+                weight,
+                in_arc.nextstate + 1,
+            ));
+        }
+        unsafe { ofst.set_arcs_unchecked(os, arcs) };
+    }
+    unsafe { ofst.set_arcs_unchecked(ostart, superinitial_arcs) };
+    ofst.set_start(ostart)?;
+
+    Ok(ofst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use counter::Counter;
+
+    use crate::algorithms::isomorphic;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{MutableFst, PathsIterator};
+    use crate::semirings::{IntegerWeight, StringWeightLeft, StringWeightRight, TropicalWeight};
+    use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+
+    #[test]
+    fn test_reverse_with_adjacency_generic() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            let fst = &data.fst;
+            let adjacency = ReverseAdjacency::new(fst);
+
+            let reversed_fst_ref: VectorFst<IntegerWeight> = reverse(fst)?;
+            let reversed_fst: VectorFst<IntegerWeight> = reverse_with_adjacency(fst, &adjacency)?;
+
+            assert_eq!(
+                reversed_fst, reversed_fst_ref,
+                "Test failing for reverse_with_adjacency on wFST {:?}",
+                &data.name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_with_superinitial() -> Fallible<()> {
+        // Single final state with a final weight of one
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(3, 4, TropicalWeight::new(2.0), 1))?;
+        fst.add_arc(1, Arc::new(5, 6, TropicalWeight::new(0.5), 2))?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(3);
+        fst_ref.set_start(2)?;
+        fst_ref.set_final(0, TropicalWeight::one())?;
+        fst_ref.add_arc(1, Arc::new(1, 2, TropicalWeight::new(1.0), 0))?;
+        fst_ref.add_arc(1, Arc::new(3, 4, TropicalWeight::new(2.0), 0))?;
+        fst_ref.add_arc(2, Arc::new(5, 6, TropicalWeight::new(0.5), 1))?;
+
+        let reversed_fst: VectorFst<TropicalWeight> = reverse_with_superinitial(&fst, false)?;
+        assert_eq!(reversed_fst, fst_ref);
+
+        let reversed_fst: VectorFst<TropicalWeight> = reverse_with_superinitial(&fst, true)?;
+        assert_eq!(reversed_fst, reverse(&fst)?);
+        assert_eq!(reversed_fst.num_states(), 4);
+
+        // Several final states : the superinitial state is still needed
+        fst.set_final(1, TropicalWeight::new(3.0))?;
+        let reversed_fst: VectorFst<TropicalWeight> = reverse_with_superinitial(&fst, false)?;
+        assert_eq!(reversed_fst, reverse(&fst)?);
+        assert_eq!(reversed_fst.num_states(), 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_reverse_generic() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            let fst = &data.fst;
+
+            let reversed_fst: VectorFst<IntegerWeight> = reverse(fst)?;
+            let fst_2: VectorFst<IntegerWeight> = reverse(&reversed_fst)?;
+
+            // The superinitial states add epsilon arcs, the paths being unchanged
+            let paths_ref: Counter<_> = fst.paths_iter().collect();
+            let paths: Counter<_> = fst_2.paths_iter().collect();
+            assert_eq!(
+                paths, paths_ref,
+                "Test failing for reverse of reverse on wFST {:?}",
+                &data.name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_reverse_reverse_isomorphic() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, StringWeightLeft::one())?;
+        fst.add_arc(0, Arc::new(1, 2, StringWeightLeft::from(vec![1, 2]), 1))?;
+        fst.add_arc(0, Arc::new(3, 4, StringWeightLeft::from(3), 2))?;
+        fst.add_arc(1, Arc::new(5, 6, StringWeightLeft::from(vec![4, 5]), 2))?;
+
+        let reversed_fst: VectorFst<StringWeightRight> = reverse_with_superinitial(&fst, false)?;
+        let fst_2: VectorFst<StringWeightLeft> = reverse_with_superinitial(&reversed_fst, false)?;
+        assert!(isomorphic(&fst, &fst_2)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::StateId;
+
+/// Incoming arcs of each state of an FST, computed once to be reused across several backward
+/// traversals, e.g. by `backward_shortest_distance` and `reverse_with_adjacency`.
+///
+/// The incoming arcs of a state `s` are stored as arcs whose `nextstate` is the source state of
+/// the arc of the FST, their labels and weight being the ones of that arc. The structure isn't
+/// linked to the FST : it must be rebuilt whenever the FST is modified.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::ReverseAdjacency;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_arc(s0, Arc::new(1, 2, TropicalWeight::new(0.5), s1))?;
+///
+/// let adjacency = ReverseAdjacency::new(&fst);
+/// assert_eq!(adjacency.in_arcs(s1)?, &[Arc::new(1, 2, TropicalWeight::new(0.5), s0)]);
+/// assert!(adjacency.in_arcs(s0)?.is_empty());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReverseAdjacency<W: Semiring> {
+    in_arcs: Vec<Vec<Arc<W>>>,
+}
+
+impl<W: Semiring> ReverseAdjacency<W> {
+    /// Computes the incoming arcs of all the states of an FST.
+    pub fn new<F: ExpandedFst<W = W>>(fst: &F) -> Self {
+        let mut num_in_arcs = vec![0; fst.num_states()];
+        for s in 0..fst.num_states() {
+            for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+                num_in_arcs[arc.nextstate] += 1;
+            }
+        }
+
+        let mut in_arcs: Vec<Vec<_>> = num_in_arcs.into_iter().map(Vec::with_capacity).collect();
+        for s in 0..fst.num_states() {
+            for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+                in_arcs[arc.nextstate].push(Arc::new(
+                    arc.ilabel,
+                    arc.olabel,
+                    arc.weight.clone(),
+                    s,
+                ));
+            }
+        }
+        Self { in_arcs }
+    }
+
+    /// Number of states of the FST the structure has been built from.
+    pub fn num_states(&self) -> usize {
+        self.in_arcs.len()
+    }
+
+    /// Returns the incoming arcs of a state, their `nextstate` being their source state.
+    pub fn in_arcs(&self, state: StateId) -> Fallible<&[Arc<W>]> {
+        self.in_arcs
+            .get(state)
+            .map(|arcs| arcs.as_slice())
+            .ok_or_else(|| format_err!("State {:?} doesn't exist", state))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::ArcIterator;
+    use crate::semirings::IntegerWeight;
+    use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+
+    #[test]
+    fn test_reverse_adjacency_generic() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            let fst = &data.fst;
+            let adjacency = ReverseAdjacency::new(fst);
+            assert_eq!(adjacency.num_states(), fst.num_states());
+
+            let mut arcs_ref = vec![];
+            for s in 0..fst.num_states() {
+                for arc in fst.arcs_iter(s)? {
+                    arcs_ref.push((s, arc.clone()));
+                }
+            }
+
+            let mut arcs = vec![];
+            for s in 0..fst.num_states() {
+                for in_arc in adjacency.in_arcs(s)? {
+                    let arc = Arc::new(in_arc.ilabel, in_arc.olabel, in_arc.weight.clone(), s);
+                    arcs.push((in_arc.nextstate, arc));
+                }
+            }
+
+            let key = |(s, arc): &(StateId, Arc<IntegerWeight>)| {
+                (
+                    *s,
+                    arc.ilabel,
+                    arc.olabel,
+                    arc.nextstate,
+                    *arc.weight.value(),
+                )
+            };
+            arcs_ref.sort_by_key(key);
+            arcs.sort_by_key(key);
+            assert_eq!(arcs, arcs_ref, "Test failing for {:?}", &data.name);
+            assert!(adjacency.in_arcs(fst.num_states()).is_err());
+        }
+        Ok(())
+    }
+}
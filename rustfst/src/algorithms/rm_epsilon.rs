@@ -1,21 +1,27 @@
-use std::collections::HashMap;
+use std::collections::hash_map::{Entry, HashMap};
+use std::collections::VecDeque;
 
 use failure::Fallible;
 use unsafe_unwrap::UnsafeUnwrap;
 
-use crate::algorithms::all_pairs_shortest_distance;
+use crate::algorithms::arc_filters::{
+    ArcFilter, ConfigEpsilonArcFilter, ConfigInputEpsilonArcFilter, EpsilonArcFilter,
+    InputEpsilonArcFilter,
+};
 use crate::algorithms::arc_sum;
 use crate::arc::Arc;
 use crate::fst_traits::{ExpandedFst, FinalStatesIterator, MutableFst};
 use crate::semirings::{Semiring, StarSemiring};
-use crate::EPS_LABEL;
+use crate::{EpsilonConfig, StateId};
 
-// Compute the wFST derived from "fst" by keeping only the epsilon transitions
-fn compute_fst_epsilon<W, F1, F2>(fst: &F1, keep_only_epsilon: bool) -> Fallible<F2>
+// Compute the wFST derived from "fst" by removing the epsilon transitions, the epsilon
+// transitions being the ones kept by "filter"
+fn compute_fst_no_epsilon<W, F1, F2, A>(fst: &F1, filter: &A) -> Fallible<F2>
 where
     W: Semiring,
     F1: ExpandedFst<W = W>,
     F2: MutableFst<W = W> + ExpandedFst<W = W>,
+    A: ArcFilter<W>,
 {
     let mut fst_epsilon = F2::new();
 
@@ -31,11 +37,7 @@ where
     // Second pass to add the arcs
     for old_state_id in fst.states_iter() {
         for old_arc in fst.arcs_iter(old_state_id)? {
-            let a = keep_only_epsilon && old_arc.ilabel == EPS_LABEL && old_arc.olabel == EPS_LABEL;
-            let b =
-                !(old_arc.ilabel == EPS_LABEL && old_arc.olabel == EPS_LABEL || keep_only_epsilon);
-
-            if a || b {
+            if !filter.keep(old_arc) {
                 fst_epsilon.add_arc(
                     mapping_states[&old_state_id],
                     Arc::new(
@@ -101,25 +103,81 @@ where
     F1: ExpandedFst<W = W>,
     F2: MutableFst<W = W> + ExpandedFst<W = W>,
 {
-    let fst_epsilon: F2 = compute_fst_epsilon(fst, true)?;
-    let dists_fst_epsilon = all_pairs_shortest_distance(&fst_epsilon)?;
-
-    let mut eps_closures = vec![vec![]; fst_epsilon.num_states()];
+    rm_epsilon_with_config(fst, &EpsilonConfig::default())
+}
 
-    for p in fst_epsilon.states_iter() {
-        for q in fst_epsilon.states_iter() {
-            if p != q && dists_fst_epsilon[p][q] != W::zero() {
-                eps_closures[p].push((q, &dists_fst_epsilon[p][q]));
-            }
-        }
-    }
+/// Same as `rm_epsilon` but the label treated as epsilon is the one defined
+/// in the `EpsilonConfig` instead of `EPS_LABEL`.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::rm_epsilon_with_config;
+/// # use rustfst::{Arc, EpsilonConfig};
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_arc(s0, Arc::new(99, 99, IntegerWeight::new(2), s1));
+/// fst.set_start(s0).unwrap();
+/// fst.set_final(s1, IntegerWeight::new(3));
+///
+/// let fst_no_epsilon : VectorFst<_> = rm_epsilon_with_config(&fst, &EpsilonConfig::new(99)).unwrap();
+///
+/// let mut fst_no_epsilon_ref = VectorFst::new();
+/// let s0 = fst_no_epsilon_ref.add_state();
+/// fst_no_epsilon_ref.add_state();
+/// fst_no_epsilon_ref.set_start(s0).unwrap();
+/// fst_no_epsilon_ref.set_final(s0, IntegerWeight::new(6));
+/// fst_no_epsilon_ref.set_final(1, IntegerWeight::new(3));
+///
+/// assert_eq!(fst_no_epsilon, fst_no_epsilon_ref);
+/// ```
+pub fn rm_epsilon_with_config<W, F1, F2>(fst: &F1, config: &EpsilonConfig) -> Fallible<F2>
+where
+    W: StarSemiring,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+{
+    // The epsilon arcs are input epsilon arcs : if the latter don't form any cycle, neither do
+    // the former and the post-order of the states along them can be used.
+    let order = post_order_with_filter(fst, &ConfigInputEpsilonArcFilter { config: *config })?;
+    rm_epsilon_with_filter(fst, &ConfigEpsilonArcFilter { config: *config }, order)
+}
 
-    let fst_no_epsilon: F2 = compute_fst_epsilon(fst, false)?;
+/// Epsilon removal, the epsilon arcs being the ones kept by `filter`. If these arcs don't form any
+/// cycle, `order` is a post-order of the states along them and the epsilon closures of all the
+/// states are computed in a single pass.
+fn rm_epsilon_with_filter<W, F1, F2, A>(
+    fst: &F1,
+    filter: &A,
+    order: Option<Vec<StateId>>,
+) -> Fallible<F2>
+where
+    W: StarSemiring,
+    F1: ExpandedFst<W = W>,
+    F2: MutableFst<W = W> + ExpandedFst<W = W>,
+    A: ArcFilter<W>,
+{
+    let fst_no_epsilon: F2 = compute_fst_no_epsilon(fst, filter)?;
 
     let mut output_fst = fst_no_epsilon.clone();
 
+    let mut acyclic_closures = match order {
+        Some(order) => Some(acyclic_epsilon_closures(fst, filter, &order)?),
+        None => None,
+    };
+
     for p in fst_no_epsilon.states_iter() {
-        for (q, w_prime) in &eps_closures[p] {
+        let closure = match &mut acyclic_closures {
+            Some(closures) => std::mem::take(&mut closures[p]),
+            None => epsilon_closure_with_filter(fst, p, filter)?,
+        };
+        for (q, w_prime) in &closure {
+            if *q == p {
+                continue;
+            }
             for arc in fst_no_epsilon.arcs_iter(*q)? {
                 output_fst.add_arc(
                     p,
@@ -149,6 +207,213 @@ where
     Ok(output_fst)
 }
 
+/// Returns the states reachable from `state` through epsilon arcs (both labels being
+/// `EPS_LABEL`) along with the ⊕-sum of the weights of the epsilon paths reaching them, `state`
+/// itself included with the weight of the epsilon cycles going through it, i.e at least `one()`.
+///
+/// The weights are computed as in `single_source_shortest_distance` from `state`, following the
+/// epsilon arcs only : the states are returned in the order in which they are discovered. On an
+/// epsilon cycle, this only terminates if the semiring is k-closed, see `has_epsilon_cycle`.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::epsilon_closure;
+/// # use rustfst::{Arc, EPS_LABEL};
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_arc(s0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(3), s1))?;
+/// fst.add_arc(s0, Arc::new(1, 1, IntegerWeight::new(5), s1))?;
+///
+/// assert_eq!(
+///     epsilon_closure(&fst, s0)?,
+///     vec![(s0, IntegerWeight::one()), (s1, IntegerWeight::new(3))]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn epsilon_closure<F: ExpandedFst>(fst: &F, state: StateId) -> Fallible<Vec<(StateId, F::W)>> {
+    epsilon_closure_with_filter(fst, state, &EpsilonArcFilter {})
+}
+
+fn epsilon_closure_with_filter<F, A>(
+    fst: &F,
+    state: StateId,
+    filter: &A,
+) -> Fallible<Vec<(StateId, F::W)>>
+where
+    F: ExpandedFst,
+    A: ArcFilter<F::W>,
+{
+    // Only the states reachable from `state` are indexed, by order of discovery
+    let mut states = vec![state];
+    let mut positions = HashMap::new();
+    positions.insert(state, 0);
+    let mut d = vec![F::W::one()];
+    let mut r = vec![F::W::one()];
+    let mut in_queue = vec![true];
+    let mut queue = VecDeque::new();
+    queue.push_back(0);
+
+    while let Some(i) = queue.pop_front() {
+        in_queue[i] = false;
+        let r_i = std::mem::replace(&mut r[i], F::W::zero());
+        for arc in fst.arcs_iter(states[i])?.filter(|arc| filter.keep(arc)) {
+            let j = match positions.entry(arc.nextstate) {
+                Entry::Occupied(o) => *o.get(),
+                Entry::Vacant(v) => {
+                    v.insert(states.len());
+                    states.push(arc.nextstate);
+                    d.push(F::W::zero());
+                    r.push(F::W::zero());
+                    in_queue.push(false);
+                    states.len() - 1
+                }
+            };
+            let w = r_i.times(&arc.weight)?;
+            let new_d = d[j].plus(&w)?;
+            if new_d != d[j] {
+                d[j] = new_d;
+                r[j].plus_assign(&w)?;
+                if !in_queue[j] {
+                    in_queue[j] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+    }
+
+    Ok(states
+        .into_iter()
+        .zip(d.into_iter())
+        .filter(|(_, w)| !w.is_zero())
+        .collect())
+}
+
+/// Same as `epsilon_closure_with_filter` for all the states at once, the arcs kept by `filter`
+/// not forming any cycle and `order` being a post-order of the states along them : the closure
+/// of a state is built from the ones of the states its epsilon arcs lead to, computed before.
+fn acyclic_epsilon_closures<F, A>(
+    fst: &F,
+    filter: &A,
+    order: &[StateId],
+) -> Fallible<Vec<Vec<(StateId, F::W)>>>
+where
+    F: ExpandedFst,
+    A: ArcFilter<F::W>,
+{
+    let mut closures = vec![vec![]; fst.num_states()];
+    for &p in order {
+        let mut closure = vec![(p, F::W::one())];
+        let mut positions = HashMap::new();
+        positions.insert(p, 0);
+        for arc in fst.arcs_iter(p)?.filter(|arc| filter.keep(arc)) {
+            for (q, w) in &closures[arc.nextstate] {
+                let w = arc.weight.times(w)?;
+                match positions.entry(*q) {
+                    Entry::Occupied(o) => closure[*o.get()].1.plus_assign(&w)?,
+                    Entry::Vacant(v) => {
+                        v.insert(closure.len());
+                        closure.push((*q, w));
+                    }
+                };
+            }
+        }
+        closure.retain(|(_, w)| !w.is_zero());
+        closures[p] = closure;
+    }
+    Ok(closures)
+}
+
+/// Returns whether an FST contains a cycle made only of input epsilon arcs, found as a back arc of
+/// a depth-first search following these arcs only.
+///
+/// `rm_epsilon` computes the epsilon closures of the states, which is only well-defined on an
+/// epsilon cycle for a k-closed semiring. Without any input epsilon cycle, there is no cycle of
+/// epsilon arcs either : the closures are finite sums whatever the semiring and `rm_epsilon`
+/// computes all of them in a single pass.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::has_epsilon_cycle;
+/// # use rustfst::{Arc, EPS_LABEL};
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.add_arc(s0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::one(), s1))?;
+/// fst.add_arc(s1, Arc::new(1, 1, IntegerWeight::one(), s0))?;
+/// assert!(!has_epsilon_cycle(&fst)?);
+///
+/// fst.add_arc(s1, Arc::new(EPS_LABEL, 2, IntegerWeight::one(), s0))?;
+/// assert!(has_epsilon_cycle(&fst)?);
+/// # Ok(())
+/// # }
+/// ```
+pub fn has_epsilon_cycle<F: ExpandedFst>(fst: &F) -> Fallible<bool> {
+    Ok(post_order_with_filter(fst, &InputEpsilonArcFilter {})?.is_none())
+}
+
+/// Depth-first search following the arcs kept by `filter` only. Returns the states in post-order,
+/// i.e each state after the ones its arcs lead to, or `None` if these arcs form a cycle.
+fn post_order_with_filter<F: ExpandedFst, A: ArcFilter<F::W>>(
+    fst: &F,
+    filter: &A,
+) -> Fallible<Option<Vec<StateId>>> {
+    let num_states = fst.num_states();
+    let mut visited = vec![false; num_states];
+    let mut on_stack = vec![false; num_states];
+    let mut order = Vec::with_capacity(num_states);
+
+    for root in 0..num_states {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        on_stack[root] = true;
+        let mut stack: Vec<(StateId, Vec<StateId>)> = vec![(root, next_states(fst, root, filter)?)];
+        while let Some((state, next)) = stack.last_mut() {
+            match next.pop() {
+                Some(n) if on_stack[n] => return Ok(None),
+                Some(n) if !visited[n] => {
+                    visited[n] = true;
+                    on_stack[n] = true;
+                    let next_n = next_states(fst, n, filter)?;
+                    stack.push((n, next_n));
+                }
+                Some(_) => {}
+                None => {
+                    on_stack[*state] = false;
+                    order.push(*state);
+                    stack.pop();
+                }
+            }
+        }
+    }
+    Ok(Some(order))
+}
+
+fn next_states<F: ExpandedFst, A: ArcFilter<F::W>>(
+    fst: &F,
+    state: StateId,
+    filter: &A,
+) -> Fallible<Vec<StateId>> {
+    Ok(fst
+        .arcs_iter(state)?
+        .filter(|arc| filter.keep(arc))
+        .map(|arc| arc.nextstate)
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use counter::Counter;
@@ -157,9 +422,11 @@ mod tests {
 
     use crate::fst_impls::VectorFst;
     use crate::fst_traits::PathsIterator;
-    use crate::semirings::IntegerWeight;
+    use crate::semirings::{IntegerWeight, TropicalWeight};
     use crate::test_data::vector_fst::get_vector_fsts_for_tests;
 
+    use crate::EPS_LABEL;
+
     use super::*;
 
     // TODO: Add test with epsilon arcs
@@ -188,4 +455,113 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_epsilon_removal_custom_epsilon() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(s2, IntegerWeight::new(7))?;
+        fst.add_arc(s0, Arc::new(99, 99, IntegerWeight::new(2), s1))?;
+        fst.add_arc(s1, Arc::new(3, 4, IntegerWeight::new(3), s2))?;
+        // Label 0 is a regular label here.
+        fst.add_arc(s0, Arc::new(0, 0, IntegerWeight::new(5), s2))?;
+
+        let fst_no_epsilon: VectorFst<IntegerWeight> =
+            rm_epsilon_with_config(&fst, &EpsilonConfig::new(99))?;
+
+        let mut fst_ref = VectorFst::new();
+        let s0 = fst_ref.add_state();
+        let s1 = fst_ref.add_state();
+        let s2 = fst_ref.add_state();
+        fst_ref.set_start(s0)?;
+        fst_ref.set_final(s2, IntegerWeight::new(7))?;
+        fst_ref.add_arc(s0, Arc::new(0, 0, IntegerWeight::new(5), s2))?;
+        fst_ref.add_arc(s0, Arc::new(3, 4, IntegerWeight::new(6), s2))?;
+        fst_ref.add_arc(s1, Arc::new(3, 4, IntegerWeight::new(3), s2))?;
+
+        assert_eq!(fst_no_epsilon, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_has_epsilon_cycle() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, IntegerWeight::one())?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(2), 1))?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(3), 2))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(4), 2))?;
+        // Cycles that aren't only made of input epsilon arcs
+        fst.add_arc(2, Arc::new(1, 1, IntegerWeight::one(), 0))?;
+        fst.add_arc(1, Arc::new(2, EPS_LABEL, IntegerWeight::one(), 1))?;
+        assert!(!has_epsilon_cycle(&fst)?);
+
+        fst.add_arc(2, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::one(), 2))?;
+        assert!(has_epsilon_cycle(&fst)?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_epsilon_closure() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, IntegerWeight::one())?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(2), 1))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(3), 2))?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(5), 2))?;
+        fst.add_arc(2, Arc::new(1, 1, IntegerWeight::new(7), 3))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, 1, IntegerWeight::new(11), 3))?;
+
+        assert_eq!(
+            epsilon_closure(&fst, 0)?,
+            vec![
+                (0, IntegerWeight::one()),
+                (1, IntegerWeight::new(2)),
+                (2, IntegerWeight::new(2 * 3 + 5))
+            ]
+        );
+        assert_eq!(epsilon_closure(&fst, 2)?, vec![(2, IntegerWeight::one())]);
+
+        // Epsilon cycle going through the state 1
+        let mut fst_cycle = VectorFst::new();
+        fst_cycle.add_states(2);
+        fst_cycle.add_arc(
+            0,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(1.0), 1),
+        )?;
+        fst_cycle.add_arc(
+            1,
+            Arc::new(EPS_LABEL, EPS_LABEL, TropicalWeight::new(2.0), 0),
+        )?;
+        assert_eq!(
+            epsilon_closure(&fst_cycle, 1)?,
+            vec![(1, TropicalWeight::one()), (0, TropicalWeight::new(2.0))]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_acyclic_epsilon_closures() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, IntegerWeight::one())?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(2), 1))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(3), 2))?;
+        fst.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::new(5), 2))?;
+        fst.add_arc(2, Arc::new(1, 1, IntegerWeight::new(7), 3))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, 1, IntegerWeight::new(11), 3))?;
+
+        let order = post_order_with_filter(&fst, &InputEpsilonArcFilter {})?.unwrap();
+        let closures = acyclic_epsilon_closures(&fst, &EpsilonArcFilter {}, &order)?;
+        for (s, closure) in closures.iter().enumerate() {
+            assert_eq!(*closure, epsilon_closure(&fst, s)?);
+        }
+        Ok(())
+    }
 }
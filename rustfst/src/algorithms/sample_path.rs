@@ -0,0 +1,151 @@
+use failure::Fallible;
+use rand::Rng;
+
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::{LogWeight, ProbabilityWeight, Semiring};
+use crate::FstPath;
+
+/// Weights that can be interpreted as a probability to sample the paths of an FST.
+pub trait SamplingWeight: Semiring {
+    /// Returns the probability represented by the weight.
+    fn probability(&self) -> f32;
+}
+
+impl SamplingWeight for ProbabilityWeight {
+    fn probability(&self) -> f32 {
+        *self.value()
+    }
+}
+
+impl SamplingWeight for LogWeight {
+    fn probability(&self) -> f32 {
+        (-*self.value()).exp()
+    }
+}
+
+/// Samples a single path of an FST by walking from the start state. At each state, the next
+/// arc or the decision to stop (if the state is final) is chosen with a probability proportional
+/// to its weight, the final weight being used for stopping.
+///
+/// Returns `None` if no path has been completed after `max_len` arcs or if the walk reaches a
+/// state from which it can't continue. Contrary to a randgen, no FST is built.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rand::{rngs::StdRng, SeedableRng};
+/// # use rustfst::semirings::{Semiring, ProbabilityWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::algorithms::sample_path;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, ProbabilityWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 2, ProbabilityWeight::new(0.5), s1))?;
+///
+/// let mut rng = StdRng::from_seed([3; 32]);
+/// let path = sample_path(&fst, &mut rng, 10)?.unwrap();
+///
+/// assert_eq!(path.ilabels, vec![1]);
+/// assert_eq!(path.olabels, vec![2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn sample_path<F, R>(fst: &F, rng: &mut R, max_len: usize) -> Fallible<Option<FstPath<F::W>>>
+where
+    F: ExpandedFst,
+    F::W: SamplingWeight,
+    R: Rng,
+{
+    let mut state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(None),
+    };
+    let mut path = FstPath::default();
+
+    for _ in 0..=max_len {
+        let final_weight = fst.final_weight(state)?;
+        let final_proba = final_weight.map_or(0.0, |w| w.probability());
+        let total = fst
+            .arcs_iter(state)?
+            .fold(final_proba, |acc, arc| acc + arc.weight.probability());
+        if total <= 0.0 {
+            return Ok(None);
+        }
+
+        let mut threshold = rng.gen::<f32>() * total;
+        if let Some(final_weight) = final_weight {
+            if threshold < final_proba {
+                path.add_weight(final_weight)?;
+                return Ok(Some(path));
+            }
+            threshold -= final_proba;
+        }
+
+        // Rounding errors might let the threshold go over the last arc.
+        let mut chosen_arc = None;
+        for arc in fst.arcs_iter(state)? {
+            let proba = arc.weight.probability();
+            if proba > 0.0 {
+                chosen_arc = Some(arc);
+            }
+            if threshold < proba {
+                break;
+            }
+            threshold -= proba;
+        }
+        let arc = match chosen_arc {
+            Some(arc) => arc,
+            None => return Ok(None),
+        };
+        path.add_to_path(arc.ilabel, arc.olabel, &arc.weight)?;
+        state = arc.nextstate;
+    }
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{MutableFst, PathsIterator};
+
+    #[test]
+    fn test_sample_path() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(1, ProbabilityWeight::new(0.2))?;
+        fst.set_final(3, ProbabilityWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, ProbabilityWeight::new(0.3), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, ProbabilityWeight::new(0.7), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, ProbabilityWeight::new(0.8), 3))?;
+        fst.add_arc(2, Arc::new(4, 4, ProbabilityWeight::new(0.5), 3))?;
+        fst.add_arc(2, Arc::new(5, 5, ProbabilityWeight::new(0.5), 3))?;
+
+        let paths: Vec<_> = fst.paths_iter().collect();
+
+        let mut rng_1 = StdRng::from_seed([7; 32]);
+        let mut rng_2 = StdRng::from_seed([7; 32]);
+        for _ in 0..20 {
+            let path_1 = sample_path(&fst, &mut rng_1, 10)?.unwrap();
+            let path_2 = sample_path(&fst, &mut rng_2, 10)?.unwrap();
+            assert_eq!(path_1, path_2);
+            assert!(paths.contains(&path_1));
+        }
+
+        // The walk is stopped before reaching a final state.
+        assert_eq!(sample_path(&fst, &mut rng_1, 0)?, None);
+        Ok(())
+    }
+}
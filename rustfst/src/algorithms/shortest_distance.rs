@@ -1,12 +1,17 @@
-use std::collections::VecDeque;
+use std::any::TypeId;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, VecDeque};
 
 use failure::Fallible;
 use unsafe_unwrap::UnsafeUnwrap;
 
+use crate::algorithms::determinize::{tropical_value, tropical_weight};
+use crate::algorithms::queues::BucketQueue;
 use crate::algorithms::reverse as reverse_f;
+use crate::algorithms::{condensation, ReverseAdjacency};
 use crate::fst_impls::VectorFst;
-use crate::fst_traits::{CoreFst, ExpandedFst};
-use crate::semirings::{Semiring, SemiringProperties};
+use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst};
+use crate::semirings::{Semiring, SemiringProperties, TropicalWeight};
 use crate::StateId;
 
 /// This operation computes the shortest distance from the state `state_id` to every state.
@@ -43,6 +48,20 @@ pub fn single_source_shortest_distance<F: ExpandedFst>(
     fst: &F,
     state_id: StateId,
 ) -> Fallible<Vec<<F as CoreFst>::W>> {
+    single_source_shortest_distance_with_equal(fst, state_id, |w1, w2| Ok(w1 == w2))
+}
+
+/// Computes the shortest distance from `state_id` to every state, the distance of a state
+/// being updated as long as `weight_equal` says that it has changed.
+fn single_source_shortest_distance_with_equal<F, WE>(
+    fst: &F,
+    state_id: StateId,
+    weight_equal: WE,
+) -> Fallible<Vec<<F as CoreFst>::W>>
+where
+    F: ExpandedFst,
+    WE: Fn(&F::W, &F::W) -> Fallible<bool>,
+{
     let mut d = vec![];
     let mut r = vec![];
 
@@ -73,7 +92,7 @@ pub fn single_source_shortest_distance<F: ExpandedFst>(
                     d.push(<F as CoreFst>::W::zero());
                     r.push(<F as CoreFst>::W::zero());
                 }
-                if d[nextstate] != d[nextstate].plus(&r2.times(&arc.weight)?)? {
+                if !weight_equal(&d[nextstate], &d[nextstate].plus(&r2.times(&arc.weight)?)?)? {
                     d[nextstate] = d[nextstate].plus(&r2.times(&arc.weight)?)?;
                     r[nextstate] = r[nextstate].plus(&r2.times(&arc.weight)?)?;
                     if !queue.contains(&nextstate) {
@@ -87,12 +106,31 @@ pub fn single_source_shortest_distance<F: ExpandedFst>(
     Ok(d)
 }
 
-pub fn _shortest_distance<F: ExpandedFst>(fst: &F) -> Fallible<Vec<<F as CoreFst>::W>> {
+pub fn _shortest_distance<F: ExpandedFst>(fst: &F) -> Fallible<Vec<<F as CoreFst>::W>>
+where
+    <F as CoreFst>::W: 'static,
+{
+    if TypeId::of::<F::W>() == TypeId::of::<TropicalWeight>() {
+        if let (Some(start_state), Ok(max_weight)) = (fst.start(), dial_max_weight(fst)) {
+            return Ok(dial_padded_distances(fst, start_state, max_weight));
+        }
+    }
+    _shortest_distance_with_equal(fst, |w1, w2| Ok(w1 == w2))
+}
+
+fn _shortest_distance_with_equal<F, WE>(
+    fst: &F,
+    weight_equal: WE,
+) -> Fallible<Vec<<F as CoreFst>::W>>
+where
+    F: ExpandedFst,
+    WE: Fn(&F::W, &F::W) -> Fallible<bool>,
+{
     if !F::W::properties().contains(SemiringProperties::RIGHT_SEMIRING) {
         bail!("ShortestDistance: Weight needs to be right distributive");
     }
     if let Some(start_state) = fst.start() {
-        return single_source_shortest_distance(fst, start_state);
+        return single_source_shortest_distance_with_equal(fst, start_state, weight_equal);
     }
     Ok(vec![])
 }
@@ -101,6 +139,9 @@ pub fn _shortest_distance<F: ExpandedFst>(fst: &F) -> Fallible<Vec<<F as CoreFst
 /// The shortest distance from `p` to `q` is the ⊕-sum of the weights
 /// of all the paths between `p` and `q`.
 ///
+/// Over the tropical semiring, Dial's algorithm is used when the arc weights are all
+/// non-negative integers, see `dial_shortest_distance`.
+///
 /// # Example
 /// ```
 /// # use rustfst::semirings::{Semiring, IntegerWeight};
@@ -129,6 +170,7 @@ pub fn _shortest_distance<F: ExpandedFst>(fst: &F) -> Fallible<Vec<<F as CoreFst
 /// ```
 pub fn shortest_distance<F: ExpandedFst>(fst: &F, reverse: bool) -> Fallible<Vec<<F as CoreFst>::W>>
 where
+    <F as CoreFst>::W: 'static,
     <<F as CoreFst>::W as Semiring>::ReverseWeight: 'static,
 {
     if !reverse {
@@ -136,26 +178,975 @@ where
     } else {
         let rfst: VectorFst<_> = reverse_f(fst)?;
         let rdistance = _shortest_distance(&rfst)?;
-        let mut distance = vec![];
-        while distance.len() < (rdistance.len() - 1) {
-            // TODO: Need to find a better to say that W::ReverseWeight::ReverseWeight == W
-            let rw = rdistance[distance.len() + 1].reverse()?;
-            distance.push(
-                unsafe {
-                    std::mem::transmute::<
-                    &<<<F as CoreFst>::W as Semiring>::ReverseWeight as Semiring>::ReverseWeight,
-                    &<F as CoreFst>::W,
-                >(&rw)
+        reverse_distance::<F>(&rdistance)
+    }
+}
+
+/// Same as `shortest_distance` but the distance of a state stops being updated once it is
+/// unchanged according to `approx_equal` with `delta`. `shortest_distance` does the same with `KDELTA` for
+/// the weights whose equality is approximate.
+pub fn shortest_distance_with_delta<F: ExpandedFst>(
+    fst: &F,
+    reverse: bool,
+    delta: f32,
+) -> Fallible<Vec<<F as CoreFst>::W>>
+where
+    <<F as CoreFst>::W as Semiring>::ReverseWeight: 'static,
+{
+    if !reverse {
+        _shortest_distance_with_equal(fst, |w1, w2| Ok(w1.approx_equal(w2, delta)))
+    } else {
+        let rfst: VectorFst<_> = reverse_f(fst)?;
+        let rdistance =
+            _shortest_distance_with_equal(&rfst, |w1, w2| Ok(w1.approx_equal(w2, delta)))?;
+        reverse_distance::<F>(&rdistance)
+    }
+}
+
+/// Computes the shortest distance from the initial state to every state of an FST over the
+/// tropical semiring with a Bellman-Ford relaxation. It converges on FSTs containing cycles as
+/// long as no cycle has a negative total weight, in which case the shortest distance would
+/// diverge and an error is returned.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::tropical_shortest_distance;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+///
+/// fst.set_start(s0).unwrap();
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(2.0), s1)).unwrap();
+/// fst.add_arc(s1, Arc::new(2, 2, TropicalWeight::new(-1.0), s0)).unwrap();
+///
+/// let dists = tropical_shortest_distance(&fst).unwrap();
+/// assert_eq!(dists, vec![TropicalWeight::one(), TropicalWeight::new(2.0)]);
+///
+/// fst.add_arc(s1, Arc::new(2, 2, TropicalWeight::new(-3.0), s0)).unwrap();
+/// assert!(tropical_shortest_distance(&fst).is_err());
+/// ```
+pub fn tropical_shortest_distance<F>(fst: &F) -> Fallible<Vec<TropicalWeight>>
+where
+    F: ExpandedFst<W = TropicalWeight>,
+{
+    let num_states = fst.num_states();
+    let mut d = vec![std::f32::INFINITY; num_states];
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+    d[start_state] = 0.0;
+
+    // Without negative cycles, the distances are stable after num_states - 1 passes. A change
+    // during the last pass thus indicates a negative cycle.
+    for pass in 0..num_states {
+        let mut changed = false;
+        for s in 0..num_states {
+            if d[s] == std::f32::INFINITY {
+                continue;
+            }
+            for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+                let candidate = d[s] + *arc.weight.value();
+                if candidate < d[arc.nextstate] {
+                    d[arc.nextstate] = candidate;
+                    changed = true;
                 }
-                .clone(),
+            }
+        }
+        if !changed {
+            break;
+        }
+        if pass == num_states - 1 {
+            bail!("ShortestDistance: the FST contains a cycle with a negative weight");
+        }
+    }
+
+    Ok(d.into_iter().map(TropicalWeight::new).collect())
+}
+
+/// Computes the shortest distance from the initial state to every state of an FST over the
+/// tropical semiring whose arc weights are all small non-negative integers, with Dial's
+/// algorithm. The states are popped from a `BucketQueue` with one bucket per distance, which is
+/// faster than the binary heap of a shortest-first discipline as long as the largest arc weight
+/// stays small. The queue holds one circular bucket per value between 0 and the largest arc
+/// weight : above 2^16 buckets, a binary heap is used instead. An error is returned if an arc
+/// weight is negative, not an integer or larger than 2^24, above which the integers can't all
+/// be represented by an `f32`. A distance overflowing `usize` is treated as unreachable.
+///
+/// `shortest_distance` calls it on the tropical FSTs whose arc weights are all accepted.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::dial_shortest_distance;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+///
+/// fst.set_start(s0).unwrap();
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(5.0), s2)).unwrap();
+/// fst.add_arc(s0, Arc::new(2, 2, TropicalWeight::new(1.0), s1)).unwrap();
+/// fst.add_arc(s1, Arc::new(3, 3, TropicalWeight::new(2.0), s2)).unwrap();
+///
+/// let dists = dial_shortest_distance(&fst).unwrap();
+/// assert_eq!(
+///     dists,
+///     vec![TropicalWeight::one(), TropicalWeight::new(1.0), TropicalWeight::new(3.0)]
+/// );
+///
+/// fst.add_arc(s1, Arc::new(4, 4, TropicalWeight::new(0.5), s2)).unwrap();
+/// assert!(dial_shortest_distance(&fst).is_err());
+/// ```
+pub fn dial_shortest_distance<F>(fst: &F) -> Fallible<Vec<TropicalWeight>>
+where
+    F: ExpandedFst<W = TropicalWeight>,
+{
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+    let max_weight = dial_max_weight(fst)?;
+    let d = dial_distances(fst, start_state, max_weight);
+    Ok(dial_tropical_distances(d))
+}
+
+/// Largest arc weight accepted by `dial_shortest_distance`, the integers above 2^24 not all
+/// being representable by an `f32`.
+const DIAL_MAX_WEIGHT: f32 = 16_777_216.0;
+
+/// Largest arc weight of a tropical FST, or an error if an arc weight can't be handled by
+/// `dial_shortest_distance`. The arcs weighted by `zero()` are ignored.
+fn dial_max_weight<F>(fst: &F) -> Fallible<usize>
+where
+    F: ExpandedFst,
+    F::W: 'static,
+{
+    let mut max_weight = 0;
+    for s in 0..fst.num_states() {
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            if arc.weight.is_zero() {
+                continue;
+            }
+            let w = tropical_value(&arc.weight);
+            ensure!(
+                w >= 0.0 && w.fract() == 0.0,
+                "ShortestDistance: the arc weight {} is not a non-negative integer",
+                w
+            );
+            ensure!(
+                w <= DIAL_MAX_WEIGHT,
+                "ShortestDistance: the arc weight {} is larger than {}",
+                w,
+                DIAL_MAX_WEIGHT
             );
+            max_weight = max_weight.max(w as usize);
+        }
+    }
+    Ok(max_weight)
+}
+
+/// Converts the integer distances of Dial's algorithm to tropical weights.
+fn dial_tropical_distances<W: Semiring + 'static>(d: Vec<usize>) -> Vec<W> {
+    d.into_iter()
+        .map(|v| {
+            if v == std::usize::MAX {
+                W::zero()
+            } else {
+                tropical_weight(v as f32)
+            }
+        })
+        .collect()
+}
+
+/// Distances of Dial's algorithm laid out as the ones of `_shortest_distance_with_equal`, which
+/// stops at the last state reached by an arc leaving a reachable state.
+fn dial_padded_distances<F>(fst: &F, start_state: StateId, max_weight: usize) -> Vec<F::W>
+where
+    F: ExpandedFst,
+    F::W: 'static,
+{
+    let mut d = dial_distances(fst, start_state, max_weight);
+    let mut len = start_state + 1;
+    for (s, ds) in d.iter().enumerate() {
+        if *ds != std::usize::MAX {
+            for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+                len = len.max(arc.nextstate + 1);
+            }
+        }
+    }
+    d.truncate(len);
+    dial_tropical_distances(d)
+}
+
+/// Integer distances of Dial's algorithm, `usize::MAX` for the unreachable states. The
+/// `BucketQueue` is only used up to `DIAL_MAX_BUCKETS` buckets.
+fn dial_distances<F>(fst: &F, start_state: StateId, max_weight: usize) -> Vec<usize>
+where
+    F: ExpandedFst,
+    F::W: 'static,
+{
+    if max_weight < DIAL_MAX_BUCKETS {
+        dial_distances_with_queue(fst, start_state, BucketQueue::new(max_weight))
+    } else {
+        dial_distances_with_queue(fst, start_state, BinaryHeap::new())
+    }
+}
+
+/// Maximum number of buckets of the `BucketQueue` of `dial_shortest_distance`.
+const DIAL_MAX_BUCKETS: usize = 1 << 16;
+
+/// Monotone priority queue over integer distances used by `dial_shortest_distance`.
+trait DistanceQueue {
+    fn enqueue(&mut self, state: StateId, distance: usize);
+    fn dequeue(&mut self) -> Option<(StateId, usize)>;
+}
+
+impl DistanceQueue for BucketQueue {
+    fn enqueue(&mut self, state: StateId, distance: usize) {
+        BucketQueue::enqueue(self, state, distance)
+    }
+
+    fn dequeue(&mut self) -> Option<(StateId, usize)> {
+        BucketQueue::dequeue(self)
+    }
+}
+
+impl DistanceQueue for BinaryHeap<Reverse<(usize, StateId)>> {
+    fn enqueue(&mut self, state: StateId, distance: usize) {
+        self.push(Reverse((distance, state)))
+    }
+
+    fn dequeue(&mut self) -> Option<(StateId, usize)> {
+        self.pop()
+            .map(|Reverse((distance, state))| (state, distance))
+    }
+}
+
+fn dial_distances_with_queue<F, Q>(fst: &F, start_state: StateId, mut queue: Q) -> Vec<usize>
+where
+    F: ExpandedFst,
+    F::W: 'static,
+    Q: DistanceQueue,
+{
+    let mut d = vec![std::usize::MAX; fst.num_states()];
+    d[start_state] = 0;
+    queue.enqueue(start_state, 0);
+
+    while let Some((s, ds)) = queue.dequeue() {
+        // A state is enqueued again each time its distance decreases, only the entry
+        // holding its final distance is processed.
+        if ds > d[s] {
+            continue;
+        }
+        for arc in unsafe { fst.arcs_iter_unchecked(s) } {
+            if arc.weight.is_zero() {
+                continue;
+            }
+            // A distance overflowing usize is treated as unreachable.
+            let candidate = match ds.checked_add(tropical_value(&arc.weight) as usize) {
+                Some(c) => c,
+                None => continue,
+            };
+            if candidate < d[arc.nextstate] {
+                d[arc.nextstate] = candidate;
+                queue.enqueue(arc.nextstate, candidate);
+            }
+        }
+    }
+    d
+}
+
+/// Computes the shortest distance from the initial state to every state by iterating products
+/// of the distance vector with the transition matrix of the FST, whose coefficient `(p, q)` is
+/// the ⊕-sum of the weights of the arcs from `p` to `q`, until a fixpoint is reached.
+///
+/// The `i`-th iteration accounts for the paths of up to `i` arcs, so the fixpoint is reached
+/// within `num_states` iterations on acyclic FSTs and on cyclic ones over idempotent semirings
+/// without negative cycles. An error is returned if it isn't. The matrix holds `num_states²`
+/// weights, which is only meant for small and dense FSTs, e.g. to validate the results of
+/// `shortest_distance`.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::shortest_distance_dense;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+///
+/// fst.set_start(s0).unwrap();
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(18), s1));
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(21), s2));
+/// fst.add_arc(s1, Arc::new(32, 23, IntegerWeight::new(55), s2));
+///
+/// let dists = shortest_distance_dense(&fst).unwrap();
+///
+/// assert_eq!(dists, vec![
+///     IntegerWeight::one(),
+///     IntegerWeight::new(18),
+///     IntegerWeight::new(21 + 18*55),
+/// ]);
+/// ```
+pub fn shortest_distance_dense<F: ExpandedFst>(fst: &F) -> Fallible<Vec<F::W>> {
+    if !F::W::properties().contains(SemiringProperties::RIGHT_SEMIRING) {
+        bail!("ShortestDistance: Weight needs to be right distributive");
+    }
+    let num_states = fst.num_states();
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+
+    let mut matrix = vec![vec![F::W::zero(); num_states]; num_states];
+    for (p, row) in matrix.iter_mut().enumerate() {
+        for arc in unsafe { fst.arcs_iter_unchecked(p) } {
+            row[arc.nextstate].plus_assign(&arc.weight)?;
+        }
+    }
+
+    let mut d = vec![F::W::zero(); num_states];
+    d[start_state] = F::W::one();
+    for _ in 0..num_states {
+        let mut next_d = vec![F::W::zero(); num_states];
+        next_d[start_state] = F::W::one();
+        for (d_p, row) in d.iter().zip(matrix.iter()) {
+            if d_p.is_zero() {
+                continue;
+            }
+            for (next_d_q, w) in next_d.iter_mut().zip(row.iter()) {
+                if !w.is_zero() {
+                    next_d_q.plus_assign(&d_p.times(w)?)?;
+                }
+            }
+        }
+        if next_d == d {
+            return Ok(d);
+        }
+        d = next_d;
+    }
+
+    bail!(
+        "ShortestDistance: no fixpoint reached after {} iterations",
+        num_states
+    )
+}
+
+/// Strongly connected components of an FST, grouped by level : the components of a level only
+/// have incoming arcs from the components of the previous levels.
+struct Condensation {
+    /// Component of each state.
+    scc: Vec<usize>,
+    /// States of each component.
+    members: Vec<Vec<StateId>>,
+    /// Position of each state among the states of its component.
+    index: Vec<usize>,
+    /// Components of each level.
+    levels: Vec<Vec<usize>>,
+}
+
+impl Condensation {
+    fn new<F: ExpandedFst>(fst: &F) -> Self {
+        let (scc, dag) = condensation(fst);
+        let n_sccs = dag.num_states();
+        let scc: Vec<_> = scc.into_iter().map(|v| v as usize).collect();
+
+        let mut members = vec![vec![]; n_sccs];
+        let mut index = vec![0; scc.len()];
+        for (s, c) in scc.iter().enumerate() {
+            index[s] = members[*c].len();
+            members[*c].push(s);
+        }
+
+        // The components are numbered in topological order.
+        let mut level = vec![0; n_sccs];
+        for c in 0..n_sccs {
+            for arc in unsafe { dag.arcs_iter_unchecked(c) } {
+                if level[arc.nextstate] <= level[c] {
+                    level[arc.nextstate] = level[c] + 1;
+                }
+            }
+        }
+        let mut levels = vec![vec![]; level.iter().max().map_or(0, |l| l + 1)];
+        for (c, l) in level.into_iter().enumerate() {
+            levels[l].push(c);
         }
-        Ok(distance)
+
+        Self {
+            scc,
+            members,
+            index,
+            levels,
+        }
+    }
+}
+
+/// Distances of the states of a component and weights flowing out of it.
+struct SccDistances<W> {
+    distances: Vec<(StateId, W)>,
+    outgoing: Vec<(StateId, W)>,
+}
+
+/// Computes the distances of the states of the component `c` from the distances accumulated
+/// on its states by the previous levels.
+fn scc_shortest_distance<F: ExpandedFst>(
+    fst: &F,
+    condensation: &Condensation,
+    c: usize,
+    d_in: &[F::W],
+) -> Fallible<SccDistances<F::W>> {
+    let members = &condensation.members[c];
+    let mut d: Vec<_> = members.iter().map(|s| d_in[*s].clone()).collect();
+    let mut r = d.clone();
+    let mut queue: VecDeque<_> = (0..members.len()).filter(|i| !d[*i].is_zero()).collect();
+    let mut enqueued: Vec<_> = d.iter().map(|w| !w.is_zero()).collect();
+
+    while let Some(i) = queue.pop_front() {
+        enqueued[i] = false;
+        let r2 = std::mem::replace(&mut r[i], F::W::zero());
+        for arc in unsafe { fst.arcs_iter_unchecked(members[i]) } {
+            if condensation.scc[arc.nextstate] != c {
+                continue;
+            }
+            let j = condensation.index[arc.nextstate];
+            let w = r2.times(&arc.weight)?;
+            let new_d = d[j].plus(&w)?;
+            if d[j] != new_d {
+                d[j] = new_d;
+                r[j].plus_assign(&w)?;
+                if !enqueued[j] {
+                    enqueued[j] = true;
+                    queue.push_back(j);
+                }
+            }
+        }
+    }
+
+    let mut outgoing = vec![];
+    for (i, s) in members.iter().enumerate() {
+        if d[i].is_zero() {
+            continue;
+        }
+        for arc in unsafe { fst.arcs_iter_unchecked(*s) } {
+            if condensation.scc[arc.nextstate] != c {
+                outgoing.push((arc.nextstate, d[i].times(&arc.weight)?));
+            }
+        }
+    }
+
+    Ok(SccDistances {
+        distances: members.iter().cloned().zip(d).collect(),
+        outgoing,
+    })
+}
+
+fn merge_scc_distances<W: Semiring>(d: &mut [W], scc_distances: SccDistances<W>) -> Fallible<()> {
+    for (s, w) in scc_distances.distances {
+        d[s] = w;
+    }
+    for (s, w) in scc_distances.outgoing {
+        d[s].plus_assign(w)?;
+    }
+    Ok(())
+}
+
+/// Same as `shortest_distance` from the initial state but computed component by component, in
+/// topological order of the strongly connected components. The distances inside a component
+/// only depend on the weights entering it, which bounds the work of the relaxation to the size
+/// of each component on FSTs made of many small components such as large lattices.
+///
+/// A distance is returned for every state, the unreachable ones getting `zero()`. The weights
+/// are summed in a different order than in `shortest_distance`, which can lead to
+/// tiny differences on floating point weights that aren't idempotent. With the `rayon` feature,
+/// see `par_shortest_distance_scc` to process the independent components in parallel.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::shortest_distance_scc;
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+///
+/// fst.set_start(s0).unwrap();
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(18), s1));
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(21), s2));
+/// fst.add_arc(s1, Arc::new(32, 23, IntegerWeight::new(55), s2));
+///
+/// let dists = shortest_distance_scc(&fst).unwrap();
+///
+/// assert_eq!(dists, vec![
+///     IntegerWeight::one(),
+///     IntegerWeight::new(18),
+///     IntegerWeight::new(21 + 18*55),
+/// ]);
+/// ```
+pub fn shortest_distance_scc<F: ExpandedFst>(fst: &F) -> Fallible<Vec<F::W>> {
+    if !F::W::properties().contains(SemiringProperties::RIGHT_SEMIRING) {
+        bail!("ShortestDistance: Weight needs to be right distributive");
+    }
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+
+    let condensation = Condensation::new(fst);
+    let mut d = vec![F::W::zero(); fst.num_states()];
+    d[start_state] = F::W::one();
+    for level in &condensation.levels {
+        for c in level {
+            let scc_distances = scc_shortest_distance(fst, &condensation, *c, &d)?;
+            merge_scc_distances(&mut d, scc_distances)?;
+        }
+    }
+    Ok(d)
+}
+
+/// Same as `shortest_distance_scc` but the components of the same level, which don't depend on
+/// each other, are processed in parallel.
+///
+/// Only available with the `rayon` feature.
+#[cfg(feature = "rayon")]
+pub fn par_shortest_distance_scc<F>(fst: &F) -> Fallible<Vec<F::W>>
+where
+    F: ExpandedFst + Sync,
+    F::W: Send + Sync,
+{
+    use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
+
+    if !F::W::properties().contains(SemiringProperties::RIGHT_SEMIRING) {
+        bail!("ShortestDistance: Weight needs to be right distributive");
     }
+    let start_state = match fst.start() {
+        Some(s) => s,
+        None => return Ok(vec![]),
+    };
+
+    let condensation = Condensation::new(fst);
+    let mut d = vec![F::W::zero(); fst.num_states()];
+    d[start_state] = F::W::one();
+    for level in &condensation.levels {
+        let level_distances = level
+            .par_iter()
+            .map(|c| scc_shortest_distance(fst, &condensation, *c, &d))
+            .collect::<Fallible<Vec<_>>>()?;
+        for scc_distances in level_distances {
+            merge_scc_distances(&mut d, scc_distances)?;
+        }
+    }
+    Ok(d)
+}
+
+/// Computes the shortest distance from every state to the final states, i.e. the ⊕-sum of the
+/// weights of the paths from a state to a final state, final weight included. This is the same
+/// as `shortest_distance` with `reverse` set to true, the incoming arcs of the states being
+/// taken from `adjacency` instead of reversing the FST. `adjacency` must have been built from
+/// `fst`.
+///
+/// # Example
+/// ```
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::{backward_shortest_distance, ReverseAdjacency};
+/// # use rustfst::Arc;
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// let s2 = fst.add_state();
+///
+/// fst.set_start(s0).unwrap();
+/// fst.set_final(s2, IntegerWeight::new(2)).unwrap();
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(18), s1));
+/// fst.add_arc(s0, Arc::new(32, 23, IntegerWeight::new(21), s2));
+/// fst.add_arc(s1, Arc::new(32, 23, IntegerWeight::new(55), s2));
+///
+/// let adjacency = ReverseAdjacency::new(&fst);
+/// let dists = backward_shortest_distance(&fst, &adjacency).unwrap();
+///
+/// assert_eq!(dists, vec![
+///     IntegerWeight::new(21 * 2 + 18 * 55 * 2),
+///     IntegerWeight::new(55 * 2),
+///     IntegerWeight::new(2),
+/// ]);
+/// ```
+pub fn backward_shortest_distance<F: ExpandedFst>(
+    fst: &F,
+    adjacency: &ReverseAdjacency<F::W>,
+) -> Fallible<Vec<F::W>> {
+    if !F::W::properties().contains(SemiringProperties::LEFT_SEMIRING) {
+        bail!("ShortestDistance: Weight needs to be left distributive");
+    }
+    ensure!(
+        adjacency.num_states() == fst.num_states(),
+        "The adjacency has {} states but the FST has {} states",
+        adjacency.num_states(),
+        fst.num_states()
+    );
+
+    let mut d = vec![F::W::zero(); fst.num_states()];
+    let mut r = vec![F::W::zero(); fst.num_states()];
+    let mut enqueued = vec![false; fst.num_states()];
+    let mut queue = VecDeque::new();
+    for s in 0..fst.num_states() {
+        if let Some(final_weight) = unsafe { fst.final_weight_unchecked(s) } {
+            d[s] = final_weight.clone();
+            r[s] = final_weight.clone();
+            enqueued[s] = true;
+            queue.push_back(s);
+        }
+    }
+
+    while let Some(t) = queue.pop_front() {
+        enqueued[t] = false;
+        let r2 = std::mem::replace(&mut r[t], F::W::zero());
+        for in_arc in adjacency.in_arcs(t)? {
+            let s = in_arc.nextstate;
+            let w = in_arc.weight.times(&r2)?;
+            let new_d = d[s].plus(&w)?;
+            if d[s] != new_d {
+                d[s] = new_d;
+                r[s].plus_assign(&w)?;
+                if !enqueued[s] {
+                    enqueued[s] = true;
+                    queue.push_back(s);
+                }
+            }
+        }
+    }
+    Ok(d)
+}
+
+/// Computes the ⊕-sum of the weights of all the accepting paths of an FST, final weights
+/// included, i.e. its total weight. Over the log semiring, this is the opposite of the log of
+/// the total probability of the FST. The sum is the shortest distance from the start state to the
+/// final states, which only exists for k-closed semirings when the FST is cyclic. `zero()` is
+/// returned for an FST without start state.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, ProbabilityWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::shortest_distance_to_final;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, ProbabilityWeight::new(0.5))?;
+/// fst.add_arc(s0, Arc::new(1, 1, ProbabilityWeight::new(0.25), s1))?;
+/// fst.add_arc(s0, Arc::new(2, 2, ProbabilityWeight::new(0.75), s1))?;
+///
+/// assert_eq!(shortest_distance_to_final(&fst)?, ProbabilityWeight::new(0.5));
+/// # Ok(())
+/// # }
+/// ```
+pub fn shortest_distance_to_final<F: ExpandedFst>(fst: &F) -> Fallible<F::W> {
+    let start = match fst.start() {
+        Some(start) => start,
+        None => return Ok(F::W::zero()),
+    };
+    let adjacency = ReverseAdjacency::new(fst);
+    let mut distance = backward_shortest_distance(fst, &adjacency)?;
+    Ok(distance.swap_remove(start))
+}
+
+/// Turns the distances computed on the reversed FST into distances on the original FST.
+fn reverse_distance<F: ExpandedFst>(
+    rdistance: &[<<F as CoreFst>::W as Semiring>::ReverseWeight],
+) -> Fallible<Vec<<F as CoreFst>::W>> {
+    let mut distance = vec![];
+    while distance.len() < (rdistance.len() - 1) {
+        // TODO: Need to find a better to say that W::ReverseWeight::ReverseWeight == W
+        let rw = rdistance[distance.len() + 1].reverse()?;
+        distance.push(
+            unsafe {
+                std::mem::transmute::<
+                    &<<<F as CoreFst>::W as Semiring>::ReverseWeight as Semiring>::ReverseWeight,
+                    &<F as CoreFst>::W,
+                >(&rw)
+            }
+            .clone(),
+        );
+    }
+    Ok(distance)
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_properties::FstProperties;
+    use crate::fst_traits::{MutableFst, PathsIterator};
+    use crate::semirings::IntegerWeight;
+    use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+
+    #[test]
+    fn test_tropical_shortest_distance_cycles() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, TropicalWeight::new(0.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::new(1.5), 0))?;
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(0.5), 3))?;
+        fst.add_arc(0, Arc::new(6, 6, TropicalWeight::new(5.0), 3))?;
+
+        let dists = tropical_shortest_distance(&fst)?;
+        assert_eq!(
+            dists,
+            vec![
+                TropicalWeight::new(0.0),
+                TropicalWeight::new(1.0),
+                TropicalWeight::new(3.0),
+                TropicalWeight::new(3.5)
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_tropical_shortest_distance_negative_cycle() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, TropicalWeight::new(-2.0), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, TropicalWeight::new(0.5), 1))?;
+
+        assert!(tropical_shortest_distance(&fst).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_dial_shortest_distance() -> Fallible<()> {
+        // 5x5 grid with integer weights, each cell being linked to its four neighbours
+        let n = 5;
+        let mut fst = VectorFst::new();
+        fst.add_states(n * n);
+        fst.set_start(0)?;
+        fst.set_final(n * n - 1, TropicalWeight::one())?;
+        for i in 0..n {
+            for j in 0..n {
+                let s = i * n + j;
+                let w = ((i * 7 + j * 3) % 5) as f32;
+                if j + 1 < n {
+                    fst.add_arc(s, Arc::new(1, 1, TropicalWeight::new(w), s + 1))?;
+                    fst.add_arc(s + 1, Arc::new(2, 2, TropicalWeight::new(w + 1.0), s))?;
+                }
+                if i + 1 < n {
+                    fst.add_arc(s, Arc::new(3, 3, TropicalWeight::new(4.0 - w), s + n))?;
+                    fst.add_arc(s + n, Arc::new(4, 4, TropicalWeight::new(w), s))?;
+                }
+            }
+        }
+
+        let dists = dial_shortest_distance(&fst)?;
+        assert_eq!(dists, tropical_shortest_distance(&fst)?);
+        assert_eq!(dists, padded_shortest_distance(&fst)?);
+
+        // Too many buckets for a large arc weight, a binary heap is used
+        let mut large_fst = fst.clone();
+        large_fst.add_arc(0, Arc::new(5, 5, TropicalWeight::new(1e6), n * n - 1))?;
+        assert_eq!(dial_shortest_distance(&large_fst)?, dists);
+
+        // shortest_distance stops at the last state reached, as the generic algorithm does
+        let mut padded_fst = fst.clone();
+        padded_fst.add_states(3);
+        padded_fst.add_arc(0, Arc::new(6, 6, TropicalWeight::zero(), n * n + 1))?;
+        let padded_dists = shortest_distance(&padded_fst, false)?;
+        assert_eq!(padded_dists.len(), n * n + 2);
+        assert_eq!(
+            padded_dists,
+            _shortest_distance_with_equal(&padded_fst, |w1, w2| Ok(w1 == w2))?
+        );
+
+        // Arc weights above 2^24 are rejected, shortest_distance then uses the generic algorithm
+        let mut huge_fst = fst.clone();
+        huge_fst.add_arc(0, Arc::new(5, 5, TropicalWeight::new(1e30), n * n - 1))?;
+        huge_fst.add_arc(1, Arc::new(5, 5, TropicalWeight::new(1e30), 0))?;
+        assert!(dial_shortest_distance(&huge_fst).is_err());
+        assert_eq!(shortest_distance(&huge_fst, false)?, dists);
+
+        fst.add_arc(3, Arc::new(5, 5, TropicalWeight::new(-1.0), 4))?;
+        assert!(dial_shortest_distance(&fst).is_err());
+        Ok(())
+    }
+
+    /// `shortest_distance` stops at the last state reached.
+    fn padded_shortest_distance<F: ExpandedFst>(fst: &F) -> Fallible<Vec<F::W>>
+    where
+        F::W: 'static,
+        <F::W as Semiring>::ReverseWeight: 'static,
+    {
+        let mut d = shortest_distance(fst, false)?;
+        d.resize(fst.num_states(), F::W::zero());
+        Ok(d)
+    }
+
+    fn multi_scc_fst() -> Fallible<VectorFst<TropicalWeight>> {
+        // Components {0}, {1, 2}, {3, 4}, {5} and {6}, the last one being unreachable
+        let mut fst = VectorFst::new();
+        fst.add_states(7);
+        fst.set_start(0)?;
+        fst.set_final(5, TropicalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(4.0), 3))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(0.5), 2))?;
+        fst.add_arc(2, Arc::new(4, 4, TropicalWeight::new(0.25), 1))?;
+        fst.add_arc(2, Arc::new(5, 5, TropicalWeight::new(2.0), 5))?;
+        fst.add_arc(3, Arc::new(6, 6, TropicalWeight::new(1.0), 4))?;
+        fst.add_arc(4, Arc::new(7, 7, TropicalWeight::new(1.0), 3))?;
+        fst.add_arc(4, Arc::new(8, 8, TropicalWeight::new(0.5), 5))?;
+        fst.add_arc(6, Arc::new(9, 9, TropicalWeight::new(1.0), 5))?;
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_shortest_distance_scc() -> Fallible<()> {
+        let fst = multi_scc_fst()?;
+        assert_eq!(
+            shortest_distance_scc(&fst)?,
+            padded_shortest_distance(&fst)?
+        );
+        assert_eq!(
+            shortest_distance_scc(&fst)?,
+            vec![
+                TropicalWeight::new(0.0),
+                TropicalWeight::new(1.0),
+                TropicalWeight::new(1.5),
+                TropicalWeight::new(4.0),
+                TropicalWeight::new(5.0),
+                TropicalWeight::new(3.5),
+                TropicalWeight::zero(),
+            ]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_distance_scc_generic() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            if !data.fst.properties()?.contains(FstProperties::ACYCLIC) {
+                continue;
+            }
+            assert_eq!(
+                shortest_distance_scc(&data.fst)?,
+                padded_shortest_distance(&data.fst)?,
+                "Test failing for shortest_distance_scc on wFST {:?}",
+                &data.name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_distance_dense() -> Fallible<()> {
+        let fst = multi_scc_fst()?;
+        assert_eq!(
+            shortest_distance_dense(&fst)?,
+            padded_shortest_distance(&fst)?
+        );
+
+        for data in get_vector_fsts_for_tests() {
+            if !data.fst.properties()?.contains(FstProperties::ACYCLIC) {
+                continue;
+            }
+            assert_eq!(
+                shortest_distance_dense(&data.fst)?,
+                padded_shortest_distance(&data.fst)?,
+                "Test failing for shortest_distance_dense on wFST {:?}",
+                &data.name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_backward_shortest_distance() -> Fallible<()> {
+        let fst = multi_scc_fst()?;
+        let adjacency = ReverseAdjacency::new(&fst);
+
+        let mut dists_ref = shortest_distance(&fst, true)?;
+        dists_ref.resize(fst.num_states(), TropicalWeight::zero());
+        assert_eq!(backward_shortest_distance(&fst, &adjacency)?, dists_ref);
+
+        for data in get_vector_fsts_for_tests() {
+            if !data.fst.properties()?.contains(FstProperties::ACYCLIC) {
+                continue;
+            }
+            let adjacency = ReverseAdjacency::new(&data.fst);
+            let mut dists_ref = shortest_distance(&data.fst, true)?;
+            dists_ref.resize(data.fst.num_states(), IntegerWeight::zero());
+            assert_eq!(
+                backward_shortest_distance(&data.fst, &adjacency)?,
+                dists_ref,
+                "Test failing for backward_shortest_distance on wFST {:?}",
+                &data.name
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_shortest_distance_scc() -> Fallible<()> {
+        let fst = multi_scc_fst()?;
+        assert_eq!(
+            par_shortest_distance_scc(&fst)?,
+            shortest_distance_scc(&fst)?
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_shortest_distance_to_final() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(2, IntegerWeight::new(2))?;
+        fst.set_final(3, IntegerWeight::new(3))?;
+        fst.add_arc(0, Arc::new(1, 1, IntegerWeight::new(2), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, IntegerWeight::new(5), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, IntegerWeight::new(7), 2))?;
+        fst.add_arc(1, Arc::new(4, 4, IntegerWeight::new(11), 3))?;
+        fst.add_arc(2, Arc::new(5, 5, IntegerWeight::new(13), 3))?;
+
+        let mut total_ref = IntegerWeight::zero();
+        for path in fst.paths_iter() {
+            total_ref.plus_assign(&path.weight)?;
+        }
+
+        assert_eq!(shortest_distance_to_final(&fst)?, total_ref);
+        assert_eq!(
+            shortest_distance_to_final(&VectorFst::<IntegerWeight>::new())?,
+            IntegerWeight::zero()
+        );
+        Ok(())
+    }
+
     //    use super::*;
     //    use crate::fst_traits::StateIterator;
     //    use crate::semirings::{IntegerWeight, Semiring};
@@ -14,6 +14,31 @@ use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, MutableFst};
 use crate::semirings::{Semiring, SemiringProperties, WeaklyDivisibleSemiring, WeightQuantize};
 use crate::Arc;
 use crate::StateId;
+
+/// Options of `shortest_path_with_config`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShortestPathConfig<W: Semiring> {
+    /// Number of paths to return.
+    pub nshortest: usize,
+    /// Whether the paths must have distinct input and output strings.
+    pub unique: bool,
+    /// Beam of the search of the n shortest paths : the hypotheses whose weight is worse than
+    /// the weight of the best path times `beam` are discarded, which bounds the memory used on
+    /// large lattices. The best path is always returned. Not used when `nshortest` is 1.
+    pub beam: Option<W>,
+}
+
+impl<W: Semiring> ShortestPathConfig<W> {
+    /// Creates a configuration returning the `nshortest` best paths without any beam.
+    pub fn new(nshortest: usize, unique: bool) -> Self {
+        Self {
+            nshortest,
+            unique,
+            beam: None,
+        }
+    }
+}
+
 pub fn shortest_path<FI, FO>(ifst: &FI, nshortest: usize, unique: bool) -> Fallible<FO>
 where
     FI: ExpandedFst + MutableFst,
@@ -23,6 +48,55 @@ where
         + From<<<FI as CoreFst>::W as Semiring>::ReverseWeight>,
     <<FI as CoreFst>::W as Semiring>::ReverseWeight: WeightQuantize + WeaklyDivisibleSemiring,
 {
+    shortest_path_with_config(ifst, ShortestPathConfig::new(nshortest, unique))
+}
+
+/// Same as `shortest_path` with the options of `config`.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::{MutableFst, PathsIterator};
+/// # use rustfst::algorithms::{shortest_path_with_config, ShortestPathConfig};
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1, TropicalWeight::one())?;
+/// fst.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(1.0), s1))?;
+/// fst.add_arc(s0, Arc::new(2, 2, TropicalWeight::new(2.0), s1))?;
+/// fst.add_arc(s0, Arc::new(3, 3, TropicalWeight::new(5.0), s1))?;
+///
+/// let config = ShortestPathConfig {
+///     beam: Some(TropicalWeight::new(1.5)),
+///     ..ShortestPathConfig::new(3, false)
+/// };
+/// let paths: VectorFst<TropicalWeight> = shortest_path_with_config(&fst, config)?;
+/// assert_eq!(paths.paths_iter().count(), 2);
+/// # Ok(())
+/// # }
+/// ```
+pub fn shortest_path_with_config<FI, FO>(
+    ifst: &FI,
+    config: ShortestPathConfig<FI::W>,
+) -> Fallible<FO>
+where
+    FI: ExpandedFst + MutableFst,
+    FO: ExpandedFst<W = FI::W> + MutableFst<W = FI::W>,
+    FI::W: 'static
+        + Into<<<FI as CoreFst>::W as Semiring>::ReverseWeight>
+        + From<<<FI as CoreFst>::W as Semiring>::ReverseWeight>,
+    <<FI as CoreFst>::W as Semiring>::ReverseWeight: WeightQuantize + WeaklyDivisibleSemiring,
+{
+    let ShortestPathConfig {
+        nshortest,
+        unique,
+        beam,
+    } = config;
     if nshortest == 0 {
         return Ok(FO::new());
     }
@@ -55,14 +129,14 @@ where
     let mut distance_2 = vec![d];
     distance_2.append(&mut distance);
     if !unique {
-        n_shortest_path(&rfst, &distance_2, nshortest)
+        n_shortest_path(&rfst, &distance_2, nshortest, beam.as_ref())
     } else {
         let distance_2_reversed: Vec<<<FI as CoreFst>::W as Semiring>::ReverseWeight> =
             distance_2.into_iter().map(|v| v.into()).collect();
         let (dfst, distance_3_reversed): (VectorFst<_>, _) =
             determinize_with_distance(&rfst, &distance_2_reversed)?;
         let distance_3: Vec<_> = distance_3_reversed.into_iter().map(|v| v.into()).collect();
-        n_shortest_path(&dfst, &distance_3, nshortest)
+        n_shortest_path(&dfst, &distance_3, nshortest, beam.as_ref())
     }
 }
 
@@ -239,7 +313,12 @@ impl<'a, 'b, W: Semiring> ShortestPathCompare<'a, 'b, W> {
     }
 }
 
-fn n_shortest_path<W, FI, FO>(ifst: &FI, distance: &[W], nshortest: usize) -> Fallible<FO>
+fn n_shortest_path<W, FI, FO>(
+    ifst: &FI,
+    distance: &[W],
+    nshortest: usize,
+    beam: Option<&W>,
+) -> Fallible<FO>
 where
     W: Semiring + 'static,
     FI: ExpandedFst<W = W::ReverseWeight> + MutableFst<W = W::ReverseWeight>,
@@ -261,6 +340,11 @@ where
         return Ok(ofst);
     }
     let istart = istart.unwrap();
+    // Hypotheses whose weight is worse than the limit are discarded
+    let limit = match beam {
+        Some(beam) => Some(distance[istart].times(beam)?),
+        None => None,
+    };
     let ostart = ofst.add_state();
     ofst.set_start(ostart)?;
     let final_state = ofst.add_state();
@@ -307,6 +391,10 @@ where
                 rarc.nextstate,
             );
             let weight = p.1.times(&arc.weight)?;
+            let pweight = distance.get(arc.nextstate).cloned().unwrap_or_else(W::zero);
+            if !within_beam(limit.as_ref(), &pweight.times(&weight)?)? {
+                continue;
+            }
             let next = ofst.add_state();
             pairs.borrow_mut().push((Some(arc.nextstate), weight));
             arc.nextstate = state;
@@ -316,8 +404,8 @@ where
         let final_weight = ifst.final_weight(p.0.unwrap())?;
         if let Some(_final_weight) = final_weight {
             let r_final_weight: W = hack_convert_reverse_reverse(_final_weight.reverse()?);
-            if !r_final_weight.is_zero() {
-                let weight = p.1.times(&r_final_weight)?;
+            let weight = p.1.times(&r_final_weight)?;
+            if !r_final_weight.is_zero() && within_beam(limit.as_ref(), &weight)? {
                 let next = ofst.add_state();
                 pairs.borrow_mut().push((None, weight));
                 ofst.add_arc(next, Arc::new(0, 0, r_final_weight, state))?;
@@ -330,3 +418,57 @@ where
 
     Ok(ofst)
 }
+
+fn within_beam<W: Semiring>(limit: Option<&W>, weight: &W) -> Fallible<bool> {
+    match limit {
+        Some(limit) => Ok(!natural_less(limit, weight)?),
+        None => Ok(true),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::TropicalWeight;
+    use crate::FstPath;
+
+    fn shortest_paths(
+        fst: &VectorFst<TropicalWeight>,
+        beam: Option<f32>,
+    ) -> Fallible<Vec<FstPath<TropicalWeight>>> {
+        let config = ShortestPathConfig {
+            beam: beam.map(TropicalWeight::new),
+            ..ShortestPathConfig::new(10, false)
+        };
+        let paths_fst: VectorFst<_> = shortest_path_with_config(fst, config)?;
+        Ok(paths_fst.paths_iter().collect())
+    }
+
+    #[test]
+    fn test_shortest_path_beam() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(0.5))?;
+        fst.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, TropicalWeight::new(3.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 3, TropicalWeight::new(0.5), 2))?;
+        fst.add_arc(1, Arc::new(4, 4, TropicalWeight::new(1.5), 2))?;
+        fst.add_arc(0, Arc::new(5, 5, TropicalWeight::new(10.0), 2))?;
+
+        let best_path = FstPath::new(vec![1, 3], vec![1, 3], TropicalWeight::new(2.0));
+
+        let paths = shortest_paths(&fst, None)?;
+        assert_eq!(paths.len(), 5);
+
+        let paths = shortest_paths(&fst, Some(2.5))?;
+        assert_eq!(paths.len(), 3);
+        assert!(paths.contains(&best_path));
+
+        let paths = shortest_paths(&fst, Some(0.0))?;
+        assert_eq!(paths, vec![best_path]);
+        Ok(())
+    }
+}
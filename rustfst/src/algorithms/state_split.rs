@@ -0,0 +1,239 @@
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_traits::{ExpandedFst, MutableFst};
+use crate::semirings::Semiring;
+use crate::{Label, StateId};
+
+/// Destination of an arc leaving a state created by a `StateSplitter`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitNextState {
+    /// A state of the input FST, the arc being redirected to the state replacing it, i.e. the
+    /// head of its replacement states when it is split.
+    Original(StateId),
+    /// One of the replacement states of the state being split, given by its position.
+    Split(usize),
+}
+
+/// Arc leaving a state created by a `StateSplitter`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitArc<W: Semiring> {
+    /// Input label.
+    pub ilabel: Label,
+    /// Output label.
+    pub olabel: Label,
+    /// Weight.
+    pub weight: W,
+    /// Destination of the arc.
+    pub nextstate: SplitNextState,
+}
+
+impl<W: Semiring> SplitArc<W> {
+    /// Creates a new arc.
+    pub fn new(ilabel: Label, olabel: Label, weight: W, nextstate: SplitNextState) -> Self {
+        SplitArc {
+            ilabel,
+            olabel,
+            weight,
+            nextstate,
+        }
+    }
+}
+
+/// State created by a `StateSplitter` in place of a state of the input FST.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SplitState<W: Semiring> {
+    /// Final weight of the state, `None` if it is not final.
+    pub final_weight: Option<W>,
+    /// Arcs leaving the state.
+    pub arcs: Vec<SplitArc<W>>,
+}
+
+/// The StateSplitter interface defines how a state is replaced by several states.
+pub trait StateSplitter<W: Semiring> {
+    /// Returns the states replacing `state`, given its final weight and its arcs, or `None` to
+    /// keep it as it is. The first replacement state is the head of the split : the arcs
+    /// reaching `state`, and the start state if `state` is the start state, are redirected to it.
+    fn split_state(
+        &mut self,
+        state: StateId,
+        final_weight: Option<&W>,
+        arcs: &[Arc<W>],
+    ) -> Fallible<Option<Vec<SplitState<W>>>>;
+}
+
+/// Replaces the states of an FST by the states returned by a `StateSplitter`, e.g. to turn a
+/// state whose final weight can't be represented in a single state into a chain of states.
+///
+/// The states are renumbered so that the replacement states of a state follow each other, in the
+/// order of the states of the input FST. The arcs of the input FST reaching a state which has been
+/// split are redirected to the head of its replacement states. An error is returned if the
+/// splitter returns an empty list of states or an arc to a replacement state which doesn't exist.
+pub fn state_split<F, S>(fst: &mut F, splitter: &mut S) -> Fallible<()>
+where
+    F: MutableFst + ExpandedFst,
+    S: StateSplitter<F::W>,
+{
+    let num_states = fst.num_states();
+
+    let mut splits = Vec::with_capacity(num_states);
+    for state in 0..num_states {
+        let arcs: Vec<_> = unsafe { fst.arcs_iter_unchecked(state) }.cloned().collect();
+        let final_weight = unsafe { fst.final_weight_unchecked(state) };
+        let split = splitter.split_state(state, final_weight, &arcs)?;
+        if let Some(split_states) = &split {
+            ensure!(
+                !split_states.is_empty(),
+                "The state {} is split into no state",
+                state
+            );
+        }
+        splits.push(split);
+    }
+
+    // Id of the state, or of the head of the replacement states, replacing each state
+    let mut heads = Vec::with_capacity(num_states);
+    let mut num_new_states = 0;
+    for split in &splits {
+        heads.push(num_new_states);
+        num_new_states += split.as_ref().map_or(1, |split_states| split_states.len());
+    }
+
+    let mut ofst = F::new();
+    ofst.add_states(num_new_states);
+    if let Some(start) = fst.start() {
+        ofst.set_start(heads[start])?;
+    }
+
+    for (state, split) in splits.into_iter().enumerate() {
+        let head = heads[state];
+        match split {
+            None => {
+                for arc in unsafe { fst.arcs_iter_unchecked(state) } {
+                    let mut arc = arc.clone();
+                    arc.nextstate = heads[arc.nextstate];
+                    ofst.add_arc(head, arc)?;
+                }
+                if let Some(final_weight) = unsafe { fst.final_weight_unchecked(state) } {
+                    ofst.set_final(head, final_weight.clone())?;
+                }
+            }
+            Some(split_states) => {
+                let num_split_states = split_states.len();
+                for (idx, split_state) in split_states.into_iter().enumerate() {
+                    for arc in split_state.arcs {
+                        let nextstate = match arc.nextstate {
+                            SplitNextState::Original(s) => *heads
+                                .get(s)
+                                .ok_or_else(|| format_err!("The state {} doesn't exist", s))?,
+                            SplitNextState::Split(i) => {
+                                ensure!(
+                                    i < num_split_states,
+                                    "The state {} is split into {} states, got an arc to the \
+                                     replacement state {}",
+                                    state,
+                                    num_split_states,
+                                    i
+                                );
+                                head + i
+                            }
+                        };
+                        ofst.add_arc(
+                            head + idx,
+                            Arc::new(arc.ilabel, arc.olabel, arc.weight, nextstate),
+                        )?;
+                    }
+                    if let Some(final_weight) = split_state.final_weight {
+                        ofst.set_final(head + idx, final_weight)?;
+                    }
+                }
+            }
+        }
+    }
+
+    *fst = ofst;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::semirings::StringWeightLeft;
+    use crate::EPS_LABEL;
+
+    /// Splits the states whose final weight holds several labels into a chain of states, one
+    /// label being moved to an epsilon arc at each step.
+    struct FinalStringSplitter {}
+
+    impl StateSplitter<StringWeightLeft> for FinalStringSplitter {
+        fn split_state(
+            &mut self,
+            _state: StateId,
+            final_weight: Option<&StringWeightLeft>,
+            arcs: &[Arc<StringWeightLeft>],
+        ) -> Fallible<Option<Vec<SplitState<StringWeightLeft>>>> {
+            let labels = match final_weight {
+                Some(w) if w.len_labels() > 1 => w.value().unwrap_labels().clone(),
+                _ => return Ok(None),
+            };
+
+            let mut split_states = vec![];
+            for (idx, label) in labels[..labels.len() - 1].iter().enumerate() {
+                split_states.push(SplitState {
+                    final_weight: None,
+                    arcs: vec![SplitArc::new(
+                        EPS_LABEL,
+                        EPS_LABEL,
+                        StringWeightLeft::from(*label),
+                        SplitNextState::Split(idx + 1),
+                    )],
+                });
+            }
+            split_states.push(SplitState {
+                final_weight: Some(StringWeightLeft::from(*labels.last().unwrap())),
+                arcs: vec![],
+            });
+            split_states[0].arcs.extend(arcs.iter().map(|arc| {
+                SplitArc::new(
+                    arc.ilabel,
+                    arc.olabel,
+                    arc.weight.clone(),
+                    SplitNextState::Original(arc.nextstate),
+                )
+            }));
+            Ok(Some(split_states))
+        }
+    }
+
+    #[test]
+    fn test_state_split() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(1, StringWeightLeft::from(vec![3, 4]))?;
+        fst.set_final(2, StringWeightLeft::one())?;
+        fst.add_arc(0, Arc::new(1, 1, StringWeightLeft::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, StringWeightLeft::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, StringWeightLeft::one(), 1))?;
+
+        state_split(&mut fst, &mut FinalStringSplitter {})?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(4);
+        fst_ref.set_start(0)?;
+        fst_ref.add_arc(0, Arc::new(1, 1, StringWeightLeft::one(), 1))?;
+        fst_ref.add_arc(
+            1,
+            Arc::new(EPS_LABEL, EPS_LABEL, StringWeightLeft::from(3), 2),
+        )?;
+        fst_ref.add_arc(1, Arc::new(2, 2, StringWeightLeft::one(), 3))?;
+        fst_ref.set_final(2, StringWeightLeft::from(4))?;
+        fst_ref.add_arc(3, Arc::new(3, 3, StringWeightLeft::one(), 1))?;
+        fst_ref.set_final(3, StringWeightLeft::one())?;
+
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+}
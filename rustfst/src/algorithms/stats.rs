@@ -0,0 +1,112 @@
+use crate::fst_traits::ExpandedFst;
+use crate::semirings::Semiring;
+use crate::StateId;
+
+/// Returns the ids of the final states of an FST, in increasing order.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::final_states;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// fst.add_states(3);
+/// fst.set_final(2, TropicalWeight::one())?;
+/// fst.set_final(0, TropicalWeight::new(1.0))?;
+///
+/// assert_eq!(final_states(&fst), vec![0, 2]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn final_states<F: ExpandedFst>(fst: &F) -> Vec<StateId> {
+    (0..fst.num_states())
+        .filter(|s| unsafe { fst.is_final_unchecked(*s) })
+        .collect()
+}
+
+/// Returns the final weights of all the states of an FST, indexed by state id, the weight of a
+/// non-final state being `zero()`.
+///
+/// # Example
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::MutableFst;
+/// # use rustfst::algorithms::final_weights_dense;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = VectorFst::new();
+/// fst.add_states(2);
+/// fst.set_final(1, TropicalWeight::new(1.0))?;
+///
+/// assert_eq!(
+///     final_weights_dense(&fst),
+///     vec![TropicalWeight::zero(), TropicalWeight::new(1.0)]
+/// );
+/// # Ok(())
+/// # }
+/// ```
+pub fn final_weights_dense<F: ExpandedFst>(fst: &F) -> Vec<F::W> {
+    (0..fst.num_states())
+        .map(|s| unsafe { fst.final_weight_unchecked(s) }.map_or_else(F::W::zero, |w| w.clone()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::IntegerWeight;
+
+    #[test]
+    fn test_final_states() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(5);
+        fst.set_start(0)?;
+        fst.set_final(4, IntegerWeight::new(2))?;
+        fst.set_final(1, IntegerWeight::one())?;
+        fst.set_final(3, IntegerWeight::new(5))?;
+        fst.add_arc(0, Arc::new(1, 1, IntegerWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, IntegerWeight::one(), 3))?;
+        fst.add_arc(3, Arc::new(3, 3, IntegerWeight::one(), 4))?;
+
+        assert_eq!(final_states(&fst), vec![1, 3, 4]);
+
+        fst.delete_final_weight(3)?;
+        assert_eq!(final_states(&fst), vec![1, 4]);
+        assert!(final_states(&VectorFst::<IntegerWeight>::new()).is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_final_weights_dense() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(1, IntegerWeight::new(3))?;
+        fst.set_final(3, IntegerWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, IntegerWeight::one(), 1))?;
+        fst.add_arc(1, Arc::new(2, 2, IntegerWeight::one(), 2))?;
+        fst.add_arc(2, Arc::new(3, 3, IntegerWeight::one(), 3))?;
+
+        assert_eq!(
+            final_weights_dense(&fst),
+            vec![
+                IntegerWeight::zero(),
+                IntegerWeight::new(3),
+                IntegerWeight::zero(),
+                IntegerWeight::one()
+            ]
+        );
+        assert!(final_weights_dense(&VectorFst::<IntegerWeight>::new()).is_empty());
+        Ok(())
+    }
+}
@@ -1,16 +1,28 @@
 use std::collections::HashMap;
 
-use failure::{format_err, Fallible};
+use failure::{bail, format_err, Fallible};
 
+use crate::algorithms::{input_labels, output_labels, relabel_pairs};
 use crate::arc::Arc;
+use crate::fst_properties::FstProperties;
 use crate::fst_traits::{CoreFst, ExpandedFst, FinalStatesIterator, MutableFst};
 use crate::semirings::Semiring;
-use crate::StateId;
+use crate::{StateId, SymbolTable, EPS_LABEL};
 
 /// Performs the union of two wFSTs. If A transduces string `x` to `y` with weight `a`
 /// and `B` transduces string `w` to `v` with weight `b`, then their union transduces `x` to `y`
 /// with weight `a` and `w` to `v` with weight `b`.
 ///
+/// When both FSTs are input-deterministic and their input alphabets are disjoint, the arcs leaving
+/// their start states are copied to the new start state instead of being reached through epsilon
+/// arcs, so that the union stays input-deterministic. The former start states are kept even if
+/// they are no longer accessible.
+///
+/// The output is fully determined by the inputs : state `0` is the new start state, followed by
+/// the states of `fst_1` and then those of `fst_2` in their original order. The arcs leaving the
+/// new start state come from `fst_1` first, and every other state keeps its arcs in their
+/// original order.
+///
 /// # Example
 /// ```
 /// # #[macro_use] extern crate rustfst;
@@ -52,8 +64,13 @@ where
     let mapping_states_fst_1 = fst_out.add_fst(fst_1)?;
     let mapping_states_fst_2 = fst_out.add_fst(fst_2)?;
 
-    add_epsilon_arc_to_initial_state(fst_1, &mapping_states_fst_1, &mut fst_out)?;
-    add_epsilon_arc_to_initial_state(fst_2, &mapping_states_fst_2, &mut fst_out)?;
+    if can_merge_initial_states(fst_1, fst_2)? {
+        copy_arcs_to_initial_state(fst_1, &mapping_states_fst_1, &mut fst_out)?;
+        copy_arcs_to_initial_state(fst_2, &mapping_states_fst_2, &mut fst_out)?;
+    } else {
+        add_epsilon_arc_to_initial_state(fst_1, &mapping_states_fst_1, &mut fst_out)?;
+        add_epsilon_arc_to_initial_state(fst_2, &mapping_states_fst_2, &mut fst_out)?;
+    }
 
     set_new_final_states(fst_1, &mapping_states_fst_1, &mut fst_out)?;
     set_new_final_states(fst_2, &mapping_states_fst_2, &mut fst_out)?;
@@ -61,6 +78,68 @@ where
     Ok(fst_out)
 }
 
+/// Performs the union of two wFSTs whose labels are given by two different symbol tables, used for
+/// both the input and the output labels. The second table is merged into the first one and the
+/// labels of the second FST are mapped to the merged table before the union, so that a symbol is
+/// given the same label in both FSTs. Returns the union along with the merged table.
+///
+/// # Example
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::utils::acceptor;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::PathsIterator;
+/// # use rustfst::algorithms::union_aligned;
+/// # use rustfst::SymbolTable;
+/// # fn main() -> Fallible<()> {
+/// let symt_a = symt!["a", "b"];
+/// let symt_b = symt!["b"];
+///
+/// let fst_a : VectorFst<IntegerWeight> = acceptor(&[symt_a.get_label("b").unwrap()], IntegerWeight::one());
+/// let fst_b : VectorFst<IntegerWeight> = acceptor(&[symt_b.get_label("b").unwrap()], IntegerWeight::one());
+///
+/// let (fst_res, symt) = union_aligned(&fst_a, &symt_a, &fst_b, &symt_b)?;
+///
+/// assert_eq!(symt, symt_a);
+/// assert!(fst_res.paths_iter().all(|p| p.ilabels == vec![symt.get_label("b").unwrap()]));
+/// # Ok(())
+/// # }
+/// ```
+pub fn union_aligned<W, F>(
+    fst_1: &F,
+    symt_1: &SymbolTable,
+    fst_2: &F,
+    symt_2: &SymbolTable,
+) -> Fallible<(F, SymbolTable)>
+where
+    W: Semiring,
+    F: ExpandedFst<W = W> + MutableFst<W = W>,
+{
+    let mut symt = symt_1.clone();
+    symt.add_table(symt_2);
+
+    for label in input_labels(fst_2).union(&output_labels(fst_2)) {
+        if !symt_2.contains_label(*label) {
+            bail!(
+                "union_aligned : label {} is missing from the symbol table",
+                label
+            );
+        }
+    }
+
+    let mut pairs = vec![];
+    for (label, symbol) in symt_2.iter() {
+        // Can't fail as all the symbols of symt_2 have been added to symt
+        pairs.push((*label, symt.get_label(symbol.as_str()).unwrap()));
+    }
+    let mut fst_2_aligned = fst_2.clone();
+    relabel_pairs(&mut fst_2_aligned, pairs.clone(), pairs)?;
+
+    Ok((union(fst_1, &fst_2_aligned)?, symt))
+}
+
 fn add_epsilon_arc_to_initial_state<F1, F2>(
     fst: &F1,
     mapping: &HashMap<StateId, StateId>,
@@ -85,6 +164,64 @@ where
     Ok(())
 }
 
+/// Whether the start states of both FSTs can be merged into the new start state while keeping
+/// the union input-deterministic and each path of the inputs a distinct path of the union.
+fn can_merge_initial_states<W, F1, F2>(fst_1: &F1, fst_2: &F2) -> Fallible<bool>
+where
+    W: Semiring,
+    F1: ExpandedFst<W = W>,
+    F2: ExpandedFst<W = W>,
+{
+    let (start_1, start_2) = match (fst_1.start(), fst_2.start()) {
+        (Some(start_1), Some(start_2)) => (start_1, start_2),
+        _ => return Ok(false),
+    };
+    if fst_1.is_final(start_1)? && fst_2.is_final(start_2)? {
+        return Ok(false);
+    }
+    // Epsilons are not part of the alphabets
+    if fst_1.arcs_iter(start_1)?.any(|arc| arc.ilabel == EPS_LABEL)
+        && fst_2.arcs_iter(start_2)?.any(|arc| arc.ilabel == EPS_LABEL)
+    {
+        return Ok(false);
+    }
+    if !fst_1.properties()?.contains(FstProperties::I_DETERMINISTIC)
+        || !fst_2.properties()?.contains(FstProperties::I_DETERMINISTIC)
+    {
+        return Ok(false);
+    }
+    Ok(input_labels(fst_1).is_disjoint(&input_labels(fst_2)))
+}
+
+fn copy_arcs_to_initial_state<F1, F2>(
+    fst: &F1,
+    mapping: &HashMap<StateId, StateId>,
+    fst_out: &mut F2,
+) -> Fallible<()>
+where
+    F1: ExpandedFst,
+    F2: MutableFst<W = F1::W>,
+{
+    let start_state = fst_out.start().unwrap();
+    if let Some(old_start_state_fst) = fst.start() {
+        for arc in fst.arcs_iter(old_start_state_fst)? {
+            fst_out.add_arc(
+                start_state,
+                Arc::new(
+                    arc.ilabel,
+                    arc.olabel,
+                    arc.weight.clone(),
+                    *mapping.get(&arc.nextstate).unwrap(),
+                ),
+            )?;
+        }
+        if let Some(final_weight) = fst.final_weight(old_start_state_fst)? {
+            fst_out.set_final(start_state, final_weight.clone())?;
+        }
+    }
+    Ok(())
+}
+
 fn set_new_final_states<W, F1, F2>(
     fst: &F1,
     mapping: &HashMap<StateId, StateId>,
@@ -118,7 +255,63 @@ mod tests {
     use crate::fst_impls::VectorFst;
     use crate::fst_traits::PathsIterator;
     use crate::semirings::IntegerWeight;
+    use crate::symt;
     use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+    use crate::utils::acceptor;
+    use crate::FstPath;
+
+    #[test]
+    fn test_union_aligned() -> Fallible<()> {
+        let symt_1 = symt!["a", "b"];
+        let symt_2 = symt!["c", "b", "a"];
+
+        // The sequence "a b" in both FSTs
+        let fst_1: VectorFst<IntegerWeight> = acceptor(&[1, 2], IntegerWeight::new(2));
+        let fst_2: VectorFst<IntegerWeight> = acceptor(&[3, 2], IntegerWeight::new(3));
+        // Only "c" in the second FST
+        let fst_3: VectorFst<IntegerWeight> = acceptor(&[1], IntegerWeight::new(4));
+        let fst_2: VectorFst<IntegerWeight> = union(&fst_2, &fst_3)?;
+
+        let (union_fst, symt) = union_aligned(&fst_1, &symt_1, &fst_2, &symt_2)?;
+
+        assert_eq!(symt.len(), 4);
+        assert_eq!(symt.get_label("a"), Some(1));
+        assert_eq!(symt.get_label("b"), Some(2));
+        let c = symt.get_label("c").unwrap();
+
+        let paths: Counter<_> = union_fst.paths_iter().collect();
+        let mut paths_ref = Counter::new();
+        paths_ref.update(vec![
+            FstPath::new(vec![1, 2], vec![1, 2], IntegerWeight::new(2)),
+            FstPath::new(vec![1, 2], vec![1, 2], IntegerWeight::new(3)),
+            FstPath::new(vec![c], vec![c], IntegerWeight::new(4)),
+        ]);
+        assert_eq!(paths, paths_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_union_disjoint_alphabets() -> Fallible<()> {
+        let fst_1: VectorFst<IntegerWeight> = acceptor(&[1, 2], IntegerWeight::new(2));
+        let fst_2: VectorFst<IntegerWeight> = acceptor(&[3, 1], IntegerWeight::new(3));
+        let fst_3: VectorFst<IntegerWeight> = acceptor(&[4], IntegerWeight::new(4));
+
+        let union_fst: VectorFst<IntegerWeight> = union(&fst_1, &fst_3)?;
+        assert!(union_fst
+            .properties()?
+            .contains(FstProperties::I_DETERMINISTIC));
+        let paths: Counter<_> = union_fst.paths_iter().collect();
+        let mut paths_ref: Counter<_> = fst_1.paths_iter().collect();
+        paths_ref.update(fst_3.paths_iter());
+        assert_eq!(paths, paths_ref);
+
+        // The label 1 is shared
+        let union_fst: VectorFst<IntegerWeight> = union(&fst_1, &fst_2)?;
+        assert!(!union_fst
+            .properties()?
+            .contains(FstProperties::I_DETERMINISTIC));
+        Ok(())
+    }
 
     #[test]
     fn test_union_generic() -> Fallible<()> {
@@ -146,4 +339,43 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_union_output_order() -> Fallible<()> {
+        let fst_1: VectorFst<IntegerWeight> = acceptor(&[1], IntegerWeight::new(2));
+        let mut fst_2: VectorFst<IntegerWeight> = acceptor(&[1], IntegerWeight::new(3));
+        fst_2.add_arc(0, Arc::new(3, 3, IntegerWeight::new(4), 1))?;
+
+        // Overlapping alphabets : epsilon arcs to the former start states
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(5);
+        fst_ref.set_start(0)?;
+        fst_ref.add_arc(1, Arc::new(1, 1, IntegerWeight::one(), 2))?;
+        fst_ref.add_arc(3, Arc::new(1, 1, IntegerWeight::one(), 4))?;
+        fst_ref.add_arc(3, Arc::new(3, 3, IntegerWeight::new(4), 4))?;
+        fst_ref.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::one(), 1))?;
+        fst_ref.add_arc(0, Arc::new(EPS_LABEL, EPS_LABEL, IntegerWeight::one(), 3))?;
+        fst_ref.set_final(2, IntegerWeight::new(2))?;
+        fst_ref.set_final(4, IntegerWeight::new(3))?;
+
+        let fst: VectorFst<_> = union(&fst_1, &fst_2)?;
+        assert_eq!(fst, fst_ref);
+
+        // Disjoint alphabets : the arcs of the start states are copied
+        let fst_2: VectorFst<IntegerWeight> = acceptor(&[2], IntegerWeight::new(3));
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(5);
+        fst_ref.set_start(0)?;
+        fst_ref.add_arc(1, Arc::new(1, 1, IntegerWeight::one(), 2))?;
+        fst_ref.add_arc(3, Arc::new(2, 2, IntegerWeight::one(), 4))?;
+        fst_ref.add_arc(0, Arc::new(1, 1, IntegerWeight::one(), 2))?;
+        fst_ref.add_arc(0, Arc::new(2, 2, IntegerWeight::one(), 4))?;
+        fst_ref.set_final(2, IntegerWeight::new(2))?;
+        fst_ref.set_final(4, IntegerWeight::new(3))?;
+
+        let fst: VectorFst<_> = union(&fst_1, &fst_2)?;
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
 }
@@ -69,12 +69,7 @@ macro_rules! impl_weight_converter_gallic {
                     bail!("Unrepresentable weight : {:?}", &arc);
                 }
 
-                let new_arc = Arc {
-                    ilabel: arc.ilabel,
-                    olabel: extracted_l,
-                    weight: extracted_w,
-                    nextstate: arc.nextstate,
-                };
+                let new_arc = Arc::new(arc.ilabel, extracted_l, extracted_w, arc.nextstate);
                 Ok(new_arc)
             }
 
@@ -0,0 +1,99 @@
+use failure::Fallible;
+
+use crate::algorithms::{FinalArc, MapFinalAction, WeightConverter};
+use crate::semirings::{ProductWeight, Semiring, TropicalWeight};
+use crate::Arc;
+
+/// Mapper that combines the acoustic and language model scores of a lattice into a single
+/// tropical weight `am_scale * am + lm_scale * lm`. The input weights are `ProductWeight`s
+/// whose first component is the acoustic score and the second one the language model score.
+pub struct LogLinearMapper {
+    pub lm_scale: f32,
+    pub am_scale: f32,
+}
+
+impl LogLinearMapper {
+    fn map_weight(&self, weight: &ProductWeight<TropicalWeight, TropicalWeight>) -> TropicalWeight {
+        let am = *weight.value1().value();
+        let lm = *weight.value2().value();
+        if am == f32::INFINITY || lm == f32::INFINITY {
+            return TropicalWeight::zero();
+        }
+        TropicalWeight::new(self.am_scale * am + self.lm_scale * lm)
+    }
+}
+
+impl WeightConverter<ProductWeight<TropicalWeight, TropicalWeight>, TropicalWeight>
+    for LogLinearMapper
+{
+    fn arc_map(
+        &mut self,
+        arc: &Arc<ProductWeight<TropicalWeight, TropicalWeight>>,
+    ) -> Fallible<Arc<TropicalWeight>> {
+        Ok(Arc::new(
+            arc.ilabel,
+            arc.olabel,
+            self.map_weight(&arc.weight),
+            arc.nextstate,
+        ))
+    }
+
+    fn final_arc_map(
+        &mut self,
+        final_arc: &FinalArc<ProductWeight<TropicalWeight, TropicalWeight>>,
+    ) -> Fallible<FinalArc<TropicalWeight>> {
+        Ok(FinalArc {
+            ilabel: final_arc.ilabel,
+            olabel: final_arc.olabel,
+            weight: self.map_weight(&final_arc.weight),
+        })
+    }
+
+    fn final_action(&self) -> MapFinalAction {
+        MapFinalAction::MapNoSuperfinal
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::algorithms::weight_convert;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{ArcIterator, CoreFst, MutableFst};
+
+    #[test]
+    fn test_log_linear_mapper() -> Fallible<()> {
+        let mut fst = VectorFst::<ProductWeight<TropicalWeight, TropicalWeight>>::new();
+        let s0 = fst.add_state();
+        let s1 = fst.add_state();
+        fst.set_start(s0)?;
+        fst.set_final(
+            s1,
+            (TropicalWeight::new(1.0), TropicalWeight::new(2.0)).into(),
+        )?;
+        fst.add_arc(
+            s0,
+            Arc::new(
+                3,
+                4,
+                (TropicalWeight::new(10.0), TropicalWeight::new(4.0)).into(),
+                s1,
+            ),
+        )?;
+
+        let mut mapper = LogLinearMapper {
+            lm_scale: 0.5,
+            am_scale: 2.0,
+        };
+        let combined_fst: VectorFst<TropicalWeight> = weight_convert(&fst, &mut mapper)?;
+
+        let arcs: Vec<_> = combined_fst.arcs_iter(s0)?.cloned().collect();
+        assert_eq!(arcs, vec![Arc::new(3, 4, TropicalWeight::new(22.0), s1)]);
+        assert_eq!(
+            combined_fst.final_weight(s1)?,
+            Some(&TropicalWeight::new(3.0))
+        );
+        Ok(())
+    }
+}
@@ -1,7 +1,9 @@
 mod from_gallic_mapper;
+mod log_linear_mapper;
 mod simple_weight_converter;
 mod to_gallic_converter;
 
 pub use self::from_gallic_mapper::FromGallicConverter;
+pub use self::log_linear_mapper::LogLinearMapper;
 pub use self::simple_weight_converter::SimpleWeightConverter;
 pub use self::to_gallic_converter::ToGallicConverter;
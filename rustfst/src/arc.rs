@@ -2,8 +2,11 @@ use crate::semirings::Semiring;
 use crate::{Label, StateId};
 
 /// Structure representing a transition from a state to another state in a FST.
+///
+/// An arc can carry an opaque payload of type `M` (alignment index, model id...) which is not
+/// part of the weight. By default there is no payload.
 #[derive(Debug, Clone, PartialEq)]
-pub struct Arc<W: Semiring> {
+pub struct Arc<W: Semiring, M = ()> {
     /// Input label.
     pub ilabel: Label,
     /// Output label.
@@ -12,6 +15,8 @@ pub struct Arc<W: Semiring> {
     pub weight: W,
     /// ID of the next state.
     pub nextstate: StateId,
+    /// Auxiliary payload. The algorithms merging arcs drop it.
+    pub payload: M,
 }
 
 impl<W: Semiring> Arc<W> {
@@ -37,6 +42,7 @@ impl<W: Semiring> Arc<W> {
             olabel,
             weight,
             nextstate,
+            payload: (),
         }
     }
 
@@ -62,3 +68,34 @@ impl<W: Semiring> Arc<W> {
         self.nextstate = arc.nextstate;
     }
 }
+
+impl<W: Semiring, M> Arc<W, M> {
+    /// Creates a new Arc carrying a payload.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rustfst::Arc;
+    /// # use rustfst::semirings::{BooleanWeight, Semiring};
+    /// let arc = Arc::with_payload(0, 1, BooleanWeight::one(), 2, 42u32);
+    ///
+    /// assert_eq!(arc.nextstate, 2);
+    /// assert_eq!(arc.payload, 42);
+    /// ```
+    #[inline]
+    pub fn with_payload(
+        ilabel: Label,
+        olabel: Label,
+        weight: W,
+        nextstate: StateId,
+        payload: M,
+    ) -> Self {
+        Arc {
+            ilabel,
+            olabel,
+            weight,
+            nextstate,
+            payload,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+use crate::{Label, EPS_LABEL};
+
+/// Struct to configure which label is treated as epsilon by the epsilon-sensitive algorithms.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EpsilonConfig {
+    /// Label representing the epsilon transition.
+    pub label: Label,
+}
+
+impl EpsilonConfig {
+    /// Creates a new `EpsilonConfig` treating `label` as epsilon.
+    pub fn new(label: Label) -> Self {
+        Self { label }
+    }
+
+    /// Returns true if `label` is the epsilon label.
+    #[inline]
+    pub fn is_epsilon(&self, label: Label) -> bool {
+        label == self.label
+    }
+}
+
+impl Default for EpsilonConfig {
+    fn default() -> Self {
+        Self { label: EPS_LABEL }
+    }
+}
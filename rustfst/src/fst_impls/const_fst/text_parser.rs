@@ -37,12 +37,12 @@ impl<W: 'static + Semiring<Type = f32>> TextParser for ConstFst<W> {
             let mut noepsilons = 0;
             const_arcs.extend(arcs_iterator.map(|v| {
                 debug_assert_eq!(_state, v.state);
-                let arc = Arc {
-                    ilabel: v.ilabel,
-                    olabel: v.olabel,
-                    weight: v.weight.map(W::new).unwrap_or_else(W::one),
-                    nextstate: v.nextstate,
-                };
+                let arc = Arc::new(
+                    v.ilabel,
+                    v.olabel,
+                    v.weight.map(W::new).unwrap_or_else(W::one),
+                    v.nextstate,
+                );
                 if arc.ilabel == EPS_LABEL {
                     niepsilons += 1;
                 }
@@ -1,5 +1,7 @@
 pub(crate) mod const_fst;
+mod unweighted_vector_fst;
 pub(crate) mod vector_fst;
 
 pub use self::const_fst::ConstFst;
-pub use self::vector_fst::VectorFst;
+pub use self::unweighted_vector_fst::{UnweightedArc, UnweightedVectorFst};
+pub use self::vector_fst::{ArcsIter, ArcsIterMut, VectorFst};
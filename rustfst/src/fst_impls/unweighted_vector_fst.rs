@@ -0,0 +1,257 @@
+use std::slice::Iter as IterSlice;
+
+use failure::Fallible;
+
+use crate::fst_impls::vector_fst::VectorFstState;
+use crate::fst_impls::VectorFst;
+use crate::semirings::{BooleanWeight, Semiring};
+use crate::{Arc, Label, StateId};
+
+/// Compact counterpart of `VectorFst<BooleanWeight>` whose arcs don't store any weight.
+///
+/// An arc is either there or not and a state either final or not, which is all the information
+/// held by a `BooleanWeight`. As the FST traits hand out references to `Arc` objects, which
+/// contain a weight, this structure doesn't implement them: it is meant to store large
+/// unweighted FSTs and to be converted from and to a `VectorFst<BooleanWeight>` to run the
+/// algorithms.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_impls::{UnweightedVectorFst, VectorFst};
+/// # use rustfst::semirings::BooleanWeight;
+/// # fn main() -> Fallible<()> {
+/// let mut fst = UnweightedVectorFst::new();
+/// let s0 = fst.add_state();
+/// let s1 = fst.add_state();
+/// fst.set_start(s0)?;
+/// fst.set_final(s1)?;
+/// fst.add_arc(s0, 1, 1, s1)?;
+///
+/// let vector_fst: VectorFst<BooleanWeight> = fst.into();
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, PartialEq, Clone, Default)]
+pub struct UnweightedVectorFst {
+    states: Vec<UnweightedVectorFstState>,
+    start_state: Option<StateId>,
+}
+
+#[derive(Debug, PartialEq, Clone, Default)]
+struct UnweightedVectorFstState {
+    is_final: bool,
+    arcs: Vec<UnweightedArc>,
+}
+
+/// Arc of an `UnweightedVectorFst`.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct UnweightedArc {
+    /// Input label.
+    pub ilabel: Label,
+    /// Output label.
+    pub olabel: Label,
+    /// ID of the next state.
+    pub nextstate: StateId,
+}
+
+impl UnweightedVectorFst {
+    /// Creates an empty FST.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the ID of the start state, if any.
+    pub fn start(&self) -> Option<StateId> {
+        self.start_state
+    }
+
+    /// Returns the number of states.
+    pub fn num_states(&self) -> usize {
+        self.states.len()
+    }
+
+    /// Adds a new state and returns its id.
+    pub fn add_state(&mut self) -> StateId {
+        self.states.push(UnweightedVectorFstState::default());
+        self.states.len() - 1
+    }
+
+    /// Sets the start state.
+    pub fn set_start(&mut self, state_id: StateId) -> Fallible<()> {
+        ensure!(
+            self.states.get(state_id).is_some(),
+            "The state {:?} doesn't exist",
+            state_id
+        );
+        self.start_state = Some(state_id);
+        Ok(())
+    }
+
+    /// Makes a state final.
+    pub fn set_final(&mut self, state_id: StateId) -> Fallible<()> {
+        self.state_mut(state_id)?.is_final = true;
+        Ok(())
+    }
+
+    /// Returns whether a state is final.
+    pub fn is_final(&self, state_id: StateId) -> Fallible<bool> {
+        Ok(self.state(state_id)?.is_final)
+    }
+
+    /// Adds an arc leaving the state `source`.
+    pub fn add_arc(
+        &mut self,
+        source: StateId,
+        ilabel: Label,
+        olabel: Label,
+        nextstate: StateId,
+    ) -> Fallible<()> {
+        self.state_mut(source)?.arcs.push(UnweightedArc {
+            ilabel,
+            olabel,
+            nextstate,
+        });
+        Ok(())
+    }
+
+    /// Returns an iterator over the arcs leaving a state.
+    pub fn arcs_iter(&self, state_id: StateId) -> Fallible<IterSlice<UnweightedArc>> {
+        Ok(self.state(state_id)?.arcs.iter())
+    }
+
+    fn state(&self, state_id: StateId) -> Fallible<&UnweightedVectorFstState> {
+        self.states
+            .get(state_id)
+            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))
+    }
+
+    fn state_mut(&mut self, state_id: StateId) -> Fallible<&mut UnweightedVectorFstState> {
+        self.states
+            .get_mut(state_id)
+            .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))
+    }
+}
+
+impl From<VectorFst<BooleanWeight>> for UnweightedVectorFst {
+    /// Arcs and final weights set to `zero()` are dropped as they don't accept anything.
+    fn from(ifst: VectorFst<BooleanWeight>) -> Self {
+        let states = ifst
+            .states
+            .into_iter()
+            .map(|s| UnweightedVectorFstState {
+                is_final: s.final_weight.map_or(false, |w| !w.is_zero()),
+                arcs: s
+                    .arcs
+                    .into_iter()
+                    .filter(|arc| !arc.weight.is_zero())
+                    .map(|arc| UnweightedArc {
+                        ilabel: arc.ilabel,
+                        olabel: arc.olabel,
+                        nextstate: arc.nextstate,
+                    })
+                    .collect(),
+            })
+            .collect();
+        UnweightedVectorFst {
+            states,
+            start_state: ifst.start_state,
+        }
+    }
+}
+
+impl From<UnweightedVectorFst> for VectorFst<BooleanWeight> {
+    fn from(ifst: UnweightedVectorFst) -> Self {
+        let states = ifst
+            .states
+            .into_iter()
+            .map(|s| VectorFstState {
+                final_weight: if s.is_final {
+                    Some(BooleanWeight::one())
+                } else {
+                    None
+                },
+                arcs: s
+                    .arcs
+                    .into_iter()
+                    .map(|arc| {
+                        Arc::new(arc.ilabel, arc.olabel, BooleanWeight::one(), arc.nextstate)
+                    })
+                    .collect(),
+            })
+            .collect();
+        VectorFst {
+            states,
+            start_state: ifst.start_state,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::{ArcIterator, CoreFst, ExpandedFst, MutableFst, PathsIterator};
+    use crate::semirings::IntegerWeight;
+    use crate::test_data::vector_fst::get_vector_fsts_for_tests;
+    use crate::FstPath;
+
+    fn to_boolean_fst(ifst: &VectorFst<IntegerWeight>) -> Fallible<VectorFst<BooleanWeight>> {
+        let mut fst = VectorFst::new();
+        fst.add_states(ifst.num_states());
+        if let Some(start) = ifst.start() {
+            fst.set_start(start)?;
+        }
+        for s in 0..ifst.num_states() {
+            if ifst.is_final(s)? {
+                fst.set_final(s, BooleanWeight::one())?;
+            }
+            for arc in ifst.arcs_iter(s)? {
+                fst.add_arc(
+                    s,
+                    Arc::new(arc.ilabel, arc.olabel, BooleanWeight::one(), arc.nextstate),
+                )?;
+            }
+        }
+        Ok(fst)
+    }
+
+    #[test]
+    fn test_unweighted_vector_fst_paths() -> Fallible<()> {
+        for data in get_vector_fsts_for_tests() {
+            let fst = to_boolean_fst(&data.fst)?;
+            let fst_unweighted: UnweightedVectorFst = fst.clone().into();
+            let fst_converted: VectorFst<BooleanWeight> = fst_unweighted.into();
+
+            let paths_ref: Vec<FstPath<BooleanWeight>> = fst.paths_iter().collect();
+            let paths: Vec<FstPath<BooleanWeight>> = fst_converted.paths_iter().collect();
+            assert_eq!(paths, paths_ref, "Test failing for {:?}", data.name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_unweighted_vector_fst_drops_zero_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(2);
+        fst.set_start(0)?;
+        fst.set_final(1, BooleanWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, BooleanWeight::one(), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, BooleanWeight::zero(), 1))?;
+
+        let fst_unweighted: UnweightedVectorFst = fst.into();
+
+        assert_eq!(fst_unweighted.start(), Some(0));
+        assert!(fst_unweighted.is_final(1)?);
+        assert_eq!(
+            fst_unweighted.arcs_iter(0)?.cloned().collect::<Vec<_>>(),
+            vec![UnweightedArc {
+                ilabel: 1,
+                olabel: 1,
+                nextstate: 1
+            }]
+        );
+        Ok(())
+    }
+}
@@ -8,9 +8,12 @@ use crate::StateId;
 ///
 /// All states are stored in a vector of states.
 /// In each state, there is a vector of arcs containing the outgoing transitions.
+///
+/// The arcs can carry a payload of type `M`, kept by `add_arc` and `arcs_iter`. The algorithms
+/// are only available for FSTs without payload.
 #[derive(Debug, PartialEq, Clone)]
-pub struct VectorFst<W: Semiring> {
-    pub(crate) states: Vec<VectorFstState<W>>,
+pub struct VectorFst<W: Semiring, M = ()> {
+    pub(crate) states: Vec<VectorFstState<W, M>>,
     pub(crate) start_state: Option<StateId>,
 }
 
@@ -18,26 +21,38 @@ pub struct VectorFst<W: Semiring> {
 // and num_output_epsilons inside the data structure as it would mean having to maintain them
 // when the object is modified. Which is not trivial with the MutableArcIterator API for instance.
 // Same goes for ArcMap. For not-mutable fst however, it is usefull.
-#[derive(Debug, Clone, PartialEq, Default)]
-pub(crate) struct VectorFstState<W: Semiring> {
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct VectorFstState<W: Semiring, M = ()> {
     pub(crate) final_weight: Option<W>,
-    pub(crate) arcs: Vec<Arc<W>>,
+    pub(crate) arcs: Vec<Arc<W, M>>,
 }
 
-impl<W: Semiring> VectorFstState<W> {
+impl<W: Semiring, M> Default for VectorFstState<W, M> {
+    fn default() -> Self {
+        Self {
+            final_weight: None,
+            arcs: vec![],
+        }
+    }
+}
+
+impl<W: Semiring, M> VectorFstState<W, M> {
     pub fn num_arcs(&self) -> usize {
         self.arcs.len()
     }
 }
 
 impl<W: Semiring> VectorFstState<W> {
-    pub fn num_input_epsilons(&self) -> usize {
-        let filter = InputEpsilonArcFilter {};
+    /// Counts the arcs leaving the state that are kept by `filter`.
+    pub fn num_arcs_matching<FI: ArcFilter<W>>(&self, filter: &FI) -> usize {
         self.arcs.iter().filter(|v| filter.keep(v)).count()
     }
 
+    pub fn num_input_epsilons(&self) -> usize {
+        self.num_arcs_matching(&InputEpsilonArcFilter {})
+    }
+
     pub fn num_output_epsilons(&self) -> usize {
-        let filter = OutputEpsilonArcFilter {};
-        self.arcs.iter().filter(|v| filter.keep(v)).count()
+        self.num_arcs_matching(&OutputEpsilonArcFilter {})
     }
 }
@@ -1,8 +1,12 @@
+use std::fmt::Debug;
+
 use crate::fst_impls::VectorFst;
 use crate::fst_traits::ExpandedFst;
 use crate::semirings::Semiring;
 
-impl<W: 'static + Semiring> ExpandedFst for VectorFst<W> {
+impl<W: 'static + Semiring, M: 'static + Clone + PartialEq + Debug> ExpandedFst<M>
+    for VectorFst<W, M>
+{
     fn num_states(&self) -> usize {
         self.states.len()
     }
@@ -1,3 +1,5 @@
+use std::fmt::Debug;
+
 use failure::Fallible;
 
 use crate::fst_impls::VectorFst;
@@ -5,9 +7,9 @@ use crate::fst_traits::{CoreFst, Fst};
 use crate::semirings::Semiring;
 use crate::StateId;
 
-impl<W: 'static + Semiring> Fst for VectorFst<W> {}
+impl<W: 'static + Semiring, M: 'static + Clone + PartialEq + Debug> Fst<M> for VectorFst<W, M> {}
 
-impl<W: 'static + Semiring> CoreFst for VectorFst<W> {
+impl<W: 'static + Semiring, M> CoreFst for VectorFst<W, M> {
     type W = W;
     fn start(&self) -> Option<StateId> {
         self.start_state
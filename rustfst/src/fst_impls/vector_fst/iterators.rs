@@ -1,4 +1,4 @@
-use std::slice;
+use std::marker::PhantomData;
 
 use crate::fst_impls::VectorFst;
 use crate::fst_traits::{ArcIterator, MutableArcIterator, StateIterator, FstIterator, FstIteratorMut};
@@ -10,7 +10,7 @@ use failure::Fallible;
 
 use std::ops::Range;
 
-impl<'a, W: 'a + Semiring> StateIterator<'a> for VectorFst<W> {
+impl<'a, W: 'a + Semiring, M: 'a> StateIterator<'a> for VectorFst<W, M> {
     type Iter = Range<StateId>;
     fn states_iter(&'a self) -> Self::Iter {
         (0..self.states.len())
@@ -18,37 +18,196 @@ impl<'a, W: 'a + Semiring> StateIterator<'a> for VectorFst<W> {
 }
 
 
-impl<'a, W: 'static + Semiring> ArcIterator<'a> for VectorFst<W> {
-    type Iter = slice::Iter<'a, Arc<W>>;
+impl<'a, W: 'static + Semiring, M: 'static> ArcIterator<'a, M> for VectorFst<W, M> {
+    type Iter = ArcsIter<'a, W, M>;
     fn arcs_iter(&'a self, state_id: StateId) -> Fallible<Self::Iter> {
         let state = self
             .states
             .get(state_id)
             .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
-        Ok(state.arcs.iter())
+        Ok(ArcsIter::new(&state.arcs))
     }
 
     unsafe fn arcs_iter_unchecked(&'a self, state_id: usize) -> Self::Iter {
-        self.states.get_unchecked(state_id).arcs.iter()
+        ArcsIter::new(&self.states.get_unchecked(state_id).arcs)
     }
 }
 
-impl<'a, W: 'static + Semiring> MutableArcIterator<'a> for VectorFst<W> {
-    type IterMut = slice::IterMut<'a, Arc<W>>;
+impl<'a, W: 'static + Semiring, M: 'static> MutableArcIterator<'a, M> for VectorFst<W, M> {
+    type IterMut = ArcsIterMut<'a, W, M>;
     fn arcs_iter_mut(&'a mut self, state_id: StateId) -> Fallible<Self::IterMut> {
         let state = self
             .states
             .get_mut(state_id)
             .ok_or_else(|| format_err!("State {:?} doesn't exist", state_id))?;
-        Ok(state.arcs.iter_mut())
+        Ok(ArcsIterMut::new(&mut state.arcs))
     }
 
     #[inline]
     unsafe fn arcs_iter_unchecked_mut(&'a mut self, state_id: usize) -> Self::IterMut {
-        self.states.get_unchecked_mut(state_id).arcs.iter_mut()
+        ArcsIterMut::new(&mut self.states.get_unchecked_mut(state_id).arcs)
     }
 }
 
+/// Iterator over the arcs leaving a state of a `VectorFst`. Unlike a plain slice iterator, it
+/// can be moved back to any arc of the state in O(1) with `reset` and `seek`.
+#[derive(Debug)]
+pub struct ArcsIter<'a, W: Semiring, M = ()> {
+    arcs: &'a [Arc<W, M>],
+    pos: usize,
+    end: usize,
+}
+
+impl<'a, W: Semiring, M> Clone for ArcsIter<'a, W, M> {
+    fn clone(&self) -> Self {
+        Self {
+            arcs: self.arcs,
+            pos: self.pos,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a, W: Semiring, M> ArcsIter<'a, W, M> {
+    fn new(arcs: &'a [Arc<W, M>]) -> Self {
+        Self {
+            arcs,
+            pos: 0,
+            end: arcs.len(),
+        }
+    }
+
+    /// Restarts the iteration from the first arc of the state.
+    pub fn reset(&mut self) {
+        self.seek(0);
+    }
+
+    /// Moves the iterator so that the next arc returned is the one at position `index` in the
+    /// list of the arcs of the state. Seeking past the last arc exhausts the iterator.
+    pub fn seek(&mut self, index: usize) {
+        self.pos = index.min(self.arcs.len());
+        self.end = self.arcs.len();
+    }
+
+    /// Returns the position of the next arc that will be returned.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a, W: Semiring, M> Iterator for ArcsIter<'a, W, M> {
+    type Item = &'a Arc<W, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.end {
+            self.pos += 1;
+            Some(&self.arcs[self.pos - 1])
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, W: Semiring, M> DoubleEndedIterator for ArcsIter<'a, W, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.end {
+            self.end -= 1;
+            Some(&self.arcs[self.end])
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, W: Semiring, M> ExactSizeIterator for ArcsIter<'a, W, M> {}
+
+/// Iterator over the mutable arcs leaving a state of a `VectorFst`. As `ArcsIter`, it can be
+/// moved back to any arc of the state in O(1) with `reset` and `seek`.
+#[derive(Debug)]
+pub struct ArcsIterMut<'a, W: Semiring, M = ()> {
+    arcs: *mut Arc<W, M>,
+    len: usize,
+    pos: usize,
+    end: usize,
+    marker: PhantomData<&'a mut [Arc<W, M>]>,
+}
+
+impl<'a, W: Semiring, M> ArcsIterMut<'a, W, M> {
+    fn new(arcs: &'a mut [Arc<W, M>]) -> Self {
+        Self {
+            arcs: arcs.as_mut_ptr(),
+            len: arcs.len(),
+            pos: 0,
+            end: arcs.len(),
+            marker: PhantomData,
+        }
+    }
+
+    /// Restarts the iteration from the first arc of the state.
+    ///
+    /// # Safety
+    ///
+    /// The arcs already returned by the iterator must no longer be used, as they may be returned
+    /// again.
+    pub unsafe fn reset(&mut self) {
+        self.seek(0);
+    }
+
+    /// Moves the iterator so that the next arc returned is the one at position `index` in the
+    /// list of the arcs of the state. Seeking past the last arc exhausts the iterator.
+    ///
+    /// # Safety
+    ///
+    /// The arcs already returned by the iterator must no longer be used, as they may be returned
+    /// again.
+    pub unsafe fn seek(&mut self, index: usize) {
+        self.pos = index.min(self.len);
+        self.end = self.len;
+    }
+
+    /// Returns the position of the next arc that will be returned.
+    pub fn position(&self) -> usize {
+        self.pos
+    }
+}
+
+impl<'a, W: Semiring, M> Iterator for ArcsIterMut<'a, W, M> {
+    type Item = &'a mut Arc<W, M>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.pos < self.end {
+            self.pos += 1;
+            // Each arc is returned at most once between two calls to `reset` or `seek`.
+            Some(unsafe { &mut *self.arcs.add(self.pos - 1) })
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.pos;
+        (len, Some(len))
+    }
+}
+
+impl<'a, W: Semiring, M> DoubleEndedIterator for ArcsIterMut<'a, W, M> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.pos < self.end {
+            self.end -= 1;
+            Some(unsafe { &mut *self.arcs.add(self.end) })
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, W: Semiring, M> ExactSizeIterator for ArcsIterMut<'a, W, M> {}
+
 #[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
 pub struct StateIndex(StateId);
 
@@ -127,6 +286,74 @@ mod tests {
     use crate::fst_traits::{ MutableFst };
     use crate::semirings::{ProbabilityWeight, Semiring};
 
+    #[test]
+    fn test_arcs_iter_reset_seek() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+
+        let arcs: Vec<_> = (1..5)
+            .map(|l| Arc::new(l, l, ProbabilityWeight::one(), s2))
+            .collect();
+        for arc in arcs.iter() {
+            fst.add_arc(s1, arc.clone())?;
+        }
+
+        let mut it = fst.arcs_iter(s1)?;
+        assert_eq!(it.next(), Some(&arcs[0]));
+
+        it.seek(2);
+        assert_eq!(it.position(), 2);
+        assert_eq!(it.by_ref().collect::<Vec<_>>(), vec![&arcs[2], &arcs[3]]);
+        assert_eq!(it.next(), None);
+
+        it.reset();
+        assert_eq!(it.collect::<Vec<_>>(), arcs.iter().collect::<Vec<_>>());
+
+        let mut it = fst.arcs_iter(s1)?;
+        it.seek(10);
+        assert_eq!(it.next(), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_arcs_iter_mut_reset_seek() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+
+        for l in 1..5 {
+            fst.add_arc(s1, Arc::new(l, l, ProbabilityWeight::one(), s2))?;
+        }
+
+        let mut it = fst.arcs_iter_mut(s1)?;
+        assert_eq!(it.len(), 4);
+        it.next().unwrap().olabel = 10;
+
+        unsafe { it.seek(2) };
+        assert_eq!(it.position(), 2);
+        for arc in it.by_ref() {
+            arc.olabel += 20;
+        }
+        assert_eq!(it.next(), None);
+
+        unsafe { it.reset() };
+        assert_eq!(it.position(), 0);
+        it.next_back().unwrap().ilabel = 0;
+        assert_eq!(it.count(), 3);
+
+        let mut it = fst.arcs_iter_mut(s1)?;
+        unsafe { it.seek(10) };
+        assert!(it.next().is_none());
+
+        let labels: Vec<_> = fst
+            .arcs_iter(s1)?
+            .map(|arc| (arc.ilabel, arc.olabel))
+            .collect();
+        assert_eq!(labels, vec![(1, 10), (2, 2), (3, 23), (0, 24)]);
+        Ok(())
+    }
+
     #[test]
     fn test_states_index_iterator() -> Fallible<()> {
         let mut fst = VectorFst::new();
@@ -9,4 +9,10 @@ use crate::fst_traits::{ArcIterator, CoreFst, FinalStatesIterator, StateIterator
 use crate::semirings::Semiring;
 
 add_or_fst!(W, VectorFst<W>);
-display_fst_trait!(W, VectorFst<W>);
+
+impl<W: 'static + Semiring, M: 'static> fmt::Display for VectorFst<W, M> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write_fst!(self, f, true);
+        Ok(())
+    }
+}
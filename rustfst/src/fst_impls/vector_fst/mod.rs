@@ -1,4 +1,5 @@
 pub use self::data_structure::VectorFst;
+pub use self::iterators::{ArcsIter, ArcsIterMut};
 pub(crate) use self::data_structure::VectorFstState;
 
 mod data_structure;
@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::fmt::Debug;
 
 use failure::Fallible;
 
@@ -10,13 +11,15 @@ use crate::semirings::Semiring;
 use crate::{Arc, StateId};
 
 #[inline]
-fn equal_arc<W: Semiring>(arc_1: &Arc<W>, arc_2: &Arc<W>) -> bool {
+fn equal_arc<W: Semiring, M>(arc_1: &Arc<W, M>, arc_2: &Arc<W, M>) -> bool {
     arc_1.ilabel == arc_2.ilabel
         && arc_1.olabel == arc_2.olabel
         && arc_1.nextstate == arc_2.nextstate
 }
 
-impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
+impl<W: 'static + Semiring, M: 'static + Clone + PartialEq + Debug> MutableFst<M>
+    for VectorFst<W, M>
+{
     fn new() -> Self {
         VectorFst {
             states: vec![],
@@ -130,7 +133,7 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
         }
     }
 
-    fn add_arc(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W>) -> Fallible<()> {
+    fn add_arc(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W, M>) -> Fallible<()> {
         self.states
             .get_mut(source)
             .ok_or_else(|| format_err!("State {:?} doesn't exist", source))?
@@ -139,11 +142,11 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
         Ok(())
     }
 
-    unsafe fn add_arc_unchecked(&mut self, source: usize, arc: Arc<Self::W>) {
+    unsafe fn add_arc_unchecked(&mut self, source: usize, arc: Arc<Self::W, M>) {
         self.states.get_unchecked_mut(source).arcs.push(arc)
     }
 
-    unsafe fn set_arcs_unchecked(&mut self, source: usize, arcs: Vec<Arc<Self::W>>) {
+    unsafe fn set_arcs_unchecked(&mut self, source: usize, arcs: Vec<Arc<Self::W, M>>) {
         self.states.get_unchecked_mut(source).arcs = arcs
     }
 
@@ -164,7 +167,7 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
         Ok(())
     }
 
-    fn pop_arcs(&mut self, source: usize) -> Fallible<Vec<Arc<Self::W>>> {
+    fn pop_arcs(&mut self, source: usize) -> Fallible<Vec<Arc<Self::W, M>>> {
         let v = self
             .states
             .get_mut(source)
@@ -175,7 +178,7 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
         Ok(v)
     }
 
-    unsafe fn pop_arcs_unchecked(&mut self, source: usize) -> Vec<Arc<Self::W>> {
+    unsafe fn pop_arcs_unchecked(&mut self, source: usize) -> Vec<Arc<Self::W, M>> {
         self.states
             .get_unchecked_mut(source)
             .arcs
@@ -219,7 +222,7 @@ impl<W: 'static + Semiring> MutableFst for VectorFst<W> {
             .as_mut()
     }
 
-    fn sort_arcs_unchecked<F: Fn(&Arc<Self::W>, &Arc<Self::W>) -> Ordering>(
+    fn sort_arcs_unchecked<F: Fn(&Arc<Self::W, M>, &Arc<Self::W, M>) -> Ordering>(
         &mut self,
         state: StateId,
         f: F,
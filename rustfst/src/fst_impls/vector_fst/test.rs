@@ -1,14 +1,19 @@
 #[cfg(test)]
 mod tests {
+    use std::fs::File;
+    use std::io::{BufReader, Cursor};
+
     use rand::{rngs::StdRng, Rng, SeedableRng};
+    use tempfile::tempdir;
 
     use failure::Fallible;
 
+    use crate::algorithms::arc_filters::ArcFilter;
     use crate::arc::Arc;
-    use crate::fst_impls::VectorFst;
+    use crate::fst_impls::{ConstFst, VectorFst};
     use crate::fst_traits::{
-        ArcIterator, CoreFst, ExpandedFst, FinalStatesIterator, MutableArcIterator, MutableFst,
-        StateIterator, TextParser,
+        ArcIterator, BinaryDeserializer, BinarySerializer, CoreFst, ExpandedFst,
+        FinalStatesIterator, Fst, MutableArcIterator, MutableFst, StateIterator, TextParser,
     };
     use crate::semirings::{ProbabilityWeight, Semiring};
     use crate::test_data::text_fst::get_test_data_for_text_parser;
@@ -136,6 +141,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_arcs_snapshot() -> Fallible<()> {
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+        fst.add_arc(s1, Arc::new(1, 2, ProbabilityWeight::new(0.5), s2))?;
+        fst.add_arc(s1, Arc::new(3, 4, ProbabilityWeight::new(0.25), s3))?;
+        fst.add_arc(s3, Arc::new(5, 6, ProbabilityWeight::new(0.125), s1))?;
+
+        let mut arcs_seq = vec![];
+        for state in fst.states_iter() {
+            for arc in fst.arcs_iter(state)? {
+                arcs_seq.push((state, arc.clone()));
+            }
+        }
+
+        assert_eq!(fst.arcs_snapshot(), arcs_seq);
+        Ok(())
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_par_arcs_sum() -> Fallible<()> {
+        use rayon::iter::ParallelIterator;
+
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+        let states: Vec<_> = (0..100).map(|_| fst.add_state()).collect();
+        for (idx, state) in states.iter().enumerate() {
+            fst.add_arc(
+                *state,
+                Arc::new(1, 1, ProbabilityWeight::new(idx as f32), states[0]),
+            )?;
+        }
+
+        let sum: f32 = fst.par_arcs().map(|(_, arc)| *arc.weight.value()).sum();
+
+        assert_eq!(sum, (0..100).sum::<usize>() as f32);
+        Ok(())
+    }
+
     #[test]
     fn test_final_weight() -> Fallible<()> {
         let mut fst = VectorFst::<ProbabilityWeight>::new();
@@ -200,6 +246,80 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_delete_arcs() -> Fallible<()> {
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s1)?;
+        fst.set_final(s1, ProbabilityWeight::new(0.3))?;
+
+        fst.add_arc(s1, Arc::new(1, 2, ProbabilityWeight::one(), s2))?;
+        fst.add_arc(s1, Arc::new(3, 4, ProbabilityWeight::one(), s1))?;
+        fst.add_arc(s2, Arc::new(5, 6, ProbabilityWeight::one(), s1))?;
+
+        fst.delete_arcs(s1)?;
+
+        assert_eq!(fst.num_states(), 2);
+        assert_eq!(fst.num_arcs(s1)?, 0);
+        assert_eq!(fst.num_arcs(s2)?, 1);
+        assert_eq!(fst.final_weight(s1)?, Some(&ProbabilityWeight::new(0.3)));
+        assert!(fst.delete_arcs(s2 + 1).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_num_arcs_matching() -> Fallible<()> {
+        struct HeavyArcFilter {}
+
+        impl ArcFilter<ProbabilityWeight> for HeavyArcFilter {
+            fn keep(&self, arc: &Arc<ProbabilityWeight>) -> bool {
+                *arc.weight.value() > 0.5
+            }
+        }
+
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+
+        fst.add_arc(s1, Arc::new(0, 1, ProbabilityWeight::new(0.7), s2))?;
+        fst.add_arc(s1, Arc::new(1, 0, ProbabilityWeight::new(0.2), s2))?;
+        fst.add_arc(s1, Arc::new(0, 0, ProbabilityWeight::new(0.9), s1))?;
+        fst.add_arc(s2, Arc::new(2, 2, ProbabilityWeight::new(0.1), s1))?;
+
+        assert_eq!(fst.states[s1].num_arcs_matching(&HeavyArcFilter {}), 2);
+        assert_eq!(fst.states[s2].num_arcs_matching(&HeavyArcFilter {}), 0);
+        assert_eq!(fst.states[s1].num_input_epsilons(), 2);
+        assert_eq!(fst.states[s1].num_output_epsilons(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_properties_queries() -> Fallible<()> {
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s1)?;
+        fst.set_final(s2, ProbabilityWeight::one())?;
+        fst.add_arc(s1, Arc::new(1, 1, ProbabilityWeight::new(0.5), s2))?;
+        fst.add_arc(s1, Arc::new(2, 2, ProbabilityWeight::new(0.5), s2))?;
+
+        assert!(fst.is_acceptor());
+        assert!(fst.is_deterministic()?);
+        assert!(fst.is_acyclic()?);
+        assert!(fst.is_epsilon_free()?);
+
+        fst.add_arc(s1, Arc::new(1, 3, ProbabilityWeight::new(0.5), s2))?;
+        assert!(!fst.is_acceptor());
+        assert!(!fst.is_deterministic()?);
+
+        fst.add_arc(s2, Arc::new(0, 0, ProbabilityWeight::new(0.5), s1))?;
+        assert!(!fst.is_acyclic()?);
+        assert!(!fst.is_epsilon_free()?);
+        Ok(())
+    }
+
     #[test]
     fn test_deleting_twice_same_state() -> Fallible<()> {
         let mut fst1 = VectorFst::<ProbabilityWeight>::new();
@@ -280,6 +400,37 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_parse_text_reader() -> Fallible<()> {
+        for data in get_test_data_for_text_parser() {
+            let name = data.name;
+            let vector_fst_ref = data.vector_fst;
+
+            let reader = BufReader::new(File::open(data.path)?);
+            let vector_fst = VectorFst::<ProbabilityWeight>::from_text_reader(reader)?;
+
+            assert_eq!(
+                vector_fst, vector_fst_ref,
+                "Test failing for test parse text reader for wFST : {}",
+                name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_text_reader_out_of_order() -> Fallible<()> {
+        let text = "0\t3\t1\t2\t0.5\n3\t0.25\n2\t3\t5\t6\n0\t2\t3\t4\t0.75\n";
+        let reader = BufReader::new(Cursor::new(text));
+        let vector_fst = VectorFst::<ProbabilityWeight>::from_text_reader(reader)?;
+
+        let vector_fst_ref = VectorFst::<ProbabilityWeight>::from_text_string(text)?;
+
+        assert_eq!(vector_fst.num_states(), 4);
+        assert_eq!(vector_fst, vector_fst_ref);
+        Ok(())
+    }
+
     #[test]
     fn test_write_read_text() -> Fallible<()> {
         for data in get_test_data_for_text_parser() {
@@ -299,6 +450,105 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_write_read_bin_properties() -> Fallible<()> {
+        let dir = tempdir()?;
+        for data in get_test_data_for_text_parser() {
+            let name = data.name;
+            let vector_fst_ref = data.vector_fst;
+
+            let path_fst_serialized = dir.path().join(format!("{}.fst", name));
+            vector_fst_ref.write(&path_fst_serialized)?;
+
+            let (vector_fst, properties) =
+                VectorFst::<ProbabilityWeight>::read_with_properties(&path_fst_serialized, true)?;
+
+            assert_eq!(vector_fst, vector_fst_ref);
+            assert_eq!(
+                properties,
+                vector_fst_ref.properties()?,
+                "Test failing for test write read properties for wFST : {}",
+                name
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_bin_other_layout() -> Fallible<()> {
+        let dir = tempdir()?;
+        for data in get_test_data_for_text_parser() {
+            let name = data.name;
+            let vector_fst_ref = data.vector_fst;
+            let const_fst_ref: ConstFst<_> = vector_fst_ref.clone().into();
+
+            let path_const_fst = dir.path().join(format!("{}_const.fst", name));
+            const_fst_ref.write(&path_const_fst)?;
+            let vector_fst = VectorFst::<ProbabilityWeight>::read(&path_const_fst)?;
+            assert_eq!(vector_fst, vector_fst_ref, "Test failing for {}", name);
+
+            let path_vector_fst = dir.path().join(format!("{}_vector.fst", name));
+            vector_fst_ref.write(&path_vector_fst)?;
+            let const_fst = ConstFst::<ProbabilityWeight>::read(&path_vector_fst)?;
+            assert_eq!(const_fst, const_fst_ref, "Test failing for {}", name);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_read_bin_unsupported_version() -> Fallible<()> {
+        let dir = tempdir()?;
+        let mut fst = VectorFst::<ProbabilityWeight>::new();
+        let s = fst.add_state();
+        fst.set_start(s)?;
+
+        let path_fst_serialized = dir.path().join("unsupported_version.fst");
+        fst.write(&path_fst_serialized)?;
+
+        // The version follows the magic number, the fst type and the arc type.
+        let mut data = std::fs::read(&path_fst_serialized)?;
+        let version_pos = 4 + (4 + "vector".len()) + (4 + "standard".len());
+        data[version_pos..version_pos + 4].copy_from_slice(&42i32.to_le_bytes());
+        std::fs::write(&path_fst_serialized, data)?;
+
+        let err = VectorFst::<ProbabilityWeight>::read(&path_fst_serialized).unwrap_err();
+        assert!(
+            err.to_string().contains("Unsupported version 42"),
+            "Unexpected error : {}",
+            err
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "flate2")]
+    #[test]
+    fn test_read_bin_gzip() -> Fallible<()> {
+        use flate2::write::GzEncoder;
+        use flate2::Compression;
+        use std::io::Write;
+
+        let dir = tempdir()?;
+        for data in get_test_data_for_text_parser() {
+            let name = data.name;
+            let vector_fst_ref = data.vector_fst;
+
+            let path_fst = dir.path().join(format!("{}.fst", name));
+            vector_fst_ref.write(&path_fst)?;
+
+            let path_fst_gz = dir.path().join(format!("{}.fst.gz", name));
+            let mut encoder = GzEncoder::new(File::create(&path_fst_gz)?, Compression::default());
+            encoder.write_all(&std::fs::read(&path_fst)?)?;
+            encoder.finish()?;
+
+            let vector_fst = VectorFst::<ProbabilityWeight>::read(&path_fst_gz)?;
+            assert_eq!(vector_fst, vector_fst_ref, "Test failing for {}", name);
+
+            let vector_fst = VectorFst::<ProbabilityWeight>::read(&path_fst)?;
+            assert_eq!(vector_fst, vector_fst_ref, "Test failing for {}", name);
+        }
+        Ok(())
+    }
+
     #[test]
     fn test_parse_single_final_state() -> Fallible<()> {
         let parsed_fst = VectorFst::<ProbabilityWeight>::from_text_string("0\tInfinity\n")?;
@@ -312,4 +562,65 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_payload_arcs_round_trip() -> Fallible<()> {
+        let mut fst = VectorFst::<ProbabilityWeight, u32>::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        fst.set_start(s1)?;
+        fst.set_final(s2, ProbabilityWeight::one())?;
+
+        let arc_1 = Arc::with_payload(3, 5, ProbabilityWeight::new(10.0), s2, 7);
+        let arc_2 = Arc {
+            ilabel: 5,
+            olabel: 7,
+            weight: ProbabilityWeight::new(18.0),
+            nextstate: s2,
+            payload: 42,
+        };
+        fst.add_arc(s1, arc_1.clone())?;
+        fst.add_arc(s1, arc_2.clone())?;
+
+        let arcs: Vec<_> = fst.arcs_iter(s1)?.cloned().collect();
+        assert_eq!(arcs, vec![arc_1, arc_2]);
+        assert_eq!(fst.arcs_iter(s2)?.count(), 0);
+        assert_eq!(fst.num_states(), 2);
+
+        for arc in fst.arcs_iter_mut(s1)? {
+            arc.payload += 1;
+        }
+
+        let mut fst_copy = VectorFst::<ProbabilityWeight, u32>::new();
+        fst_copy.add_fst(&fst)?;
+        assert_eq!(
+            fst_copy
+                .arcs_iter(s1)?
+                .map(|arc| arc.payload)
+                .collect::<Vec<_>>(),
+            vec![8, 43]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_add_arc_combining() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+
+        fst.add_arc_combining(s1, Arc::new(3, 5, ProbabilityWeight::new(0.25), s2))?;
+        fst.add_arc_combining(s1, Arc::new(3, 5, ProbabilityWeight::new(0.5), s2))?;
+        fst.add_arc_combining(s1, Arc::new(3, 5, ProbabilityWeight::new(0.5), s1))?;
+
+        let arcs: Vec<_> = fst.arcs_iter(s1)?.cloned().collect();
+        assert_eq!(
+            arcs,
+            vec![
+                Arc::new(3, 5, ProbabilityWeight::new(0.75), s2),
+                Arc::new(3, 5, ProbabilityWeight::new(0.5), s1),
+            ]
+        );
+        Ok(())
+    }
 }
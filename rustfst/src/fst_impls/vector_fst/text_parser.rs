@@ -1,11 +1,13 @@
+use std::io::BufRead;
+
 use failure::Fallible;
 
 use crate::fst_impls::vector_fst::VectorFstState;
 use crate::fst_impls::VectorFst;
-use crate::fst_traits::{MutableFst, TextParser};
-use crate::parsers::text_fst::ParsedTextFst;
+use crate::fst_traits::{CoreFst, ExpandedFst, MutableFst, TextParser};
+use crate::parsers::text_fst::{row_parsed, ParsedTextFst, RowParsed};
 use crate::semirings::Semiring;
-use crate::Arc;
+use crate::{Arc, StateId};
 
 impl<W: 'static + Semiring<Type = f32>> TextParser for VectorFst<W> {
     fn from_parsed_fst_text(parsed_fst_text: ParsedTextFst) -> Fallible<Self> {
@@ -38,3 +40,74 @@ impl<W: 'static + Semiring<Type = f32>> TextParser for VectorFst<W> {
         Ok(fst)
     }
 }
+
+impl<W: 'static + Semiring<Type = f32>> VectorFst<W> {
+    /// Deserializes a wFST in text format from a reader, line by line, without loading
+    /// the whole text in memory. The states are created as soon as they are referenced,
+    /// so lines may occur in any order except the initial state must be on the first line.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::io::{BufReader, Cursor};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::fst_traits::ExpandedFst;
+    /// # use rustfst::semirings::TropicalWeight;
+    /// let text = "0\t1\t12\t25\t0.3\n1\n";
+    /// let fst = VectorFst::<TropicalWeight>::from_text_reader(BufReader::new(Cursor::new(text))).unwrap();
+    ///
+    /// assert_eq!(fst.num_states(), 2);
+    /// ```
+    pub fn from_text_reader<R: BufRead>(reader: R) -> Fallible<Self> {
+        let mut fst = VectorFst::new();
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                continue;
+            }
+            let row = match row_parsed(line) {
+                Ok(("", row)) => row,
+                _ => bail!(
+                    "Error while parsing text fst at line {} : {:?}",
+                    idx + 1,
+                    line
+                ),
+            };
+
+            let state = match &row {
+                RowParsed::Transition(t) => t.state,
+                RowParsed::FinalState(f) => f.state,
+                RowParsed::InfinityFinalState(s) => *s,
+            };
+            grow_states(&mut fst, state);
+            if fst.start().is_none() {
+                fst.set_start(state)?;
+            }
+
+            match row {
+                RowParsed::Transition(t) => {
+                    grow_states(&mut fst, t.nextstate);
+                    let weight = t.weight.map(W::new).unwrap_or_else(W::one);
+                    fst.add_arc(t.state, Arc::new(t.ilabel, t.olabel, weight, t.nextstate))?;
+                }
+                RowParsed::FinalState(f) => {
+                    let weight = f.weight.map(W::new).unwrap_or_else(W::one);
+                    fst.set_final(f.state, weight)?;
+                }
+                RowParsed::InfinityFinalState(_) => {}
+            };
+        }
+
+        Ok(fst)
+    }
+}
+
+/// Adds states to the FST until `state` is a valid state id.
+fn grow_states<W: 'static + Semiring>(fst: &mut VectorFst<W>, state: StateId) {
+    let num_states = fst.num_states();
+    if state >= num_states {
+        fst.add_states(state + 1 - num_states);
+    }
+}
@@ -1,9 +1,15 @@
+use std::cmp::Ordering;
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
 use std::hash::{Hash, Hasher};
 
 use failure::Fallible;
 
-use crate::semirings::Semiring;
-use crate::{Label, EPS_LABEL};
+use crate::algorithms::shortest_distance;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::{CoreFst, Fst, MutableFst};
+use crate::semirings::{Semiring, SemiringProperties};
+use crate::{Arc, Label, EPS_LABEL};
 
 /// Structure representing a path in a FST
 /// (list of input labels, list of output labels and total weight).
@@ -53,6 +59,159 @@ impl<W: Semiring> FstPath<W> {
         self.olabels.extend(other.olabels);
         self.weight.times_assign(other.weight)
     }
+
+    /// Recomputes the weight of the path by traversing `fst` along its labels. The result is the
+    /// sum of the weights of all the successful paths of `fst` having these input and output
+    /// labels, epsilons excluded. It is thus equal to the stored weight when a single path of
+    /// `fst` matches, which is the case for the paths of an unambiguous FST.
+    pub fn recompute_weight<F: Fst<W = W>>(&self, fst: &F) -> Fallible<W>
+    where
+        W: 'static,
+        W::ReverseWeight: 'static,
+    {
+        let start = match fst.start() {
+            Some(s) => s,
+            None => return Ok(W::zero()),
+        };
+
+        // Product of the FST with the labels of the path. A state is made of a state of the FST
+        // and of the number of input and output labels already read.
+        let mut product = VectorFst::new();
+        let mut product_states = HashMap::new();
+        let mut queue = vec![(start, 0, 0)];
+        product_states.insert((start, 0, 0), product.add_state());
+        product.set_start(0)?;
+
+        while let Some((state, ipos, opos)) = queue.pop() {
+            let product_state = product_states[&(state, ipos, opos)];
+            if ipos == self.ilabels.len() && opos == self.olabels.len() {
+                if let Some(final_weight) = fst.final_weight(state)? {
+                    product.set_final(product_state, final_weight.clone())?;
+                }
+            }
+            for arc in fst.arcs_iter(state)? {
+                let next_ipos = match next_position(&self.ilabels, ipos, arc.ilabel) {
+                    Some(pos) => pos,
+                    None => continue,
+                };
+                let next_opos = match next_position(&self.olabels, opos, arc.olabel) {
+                    Some(pos) => pos,
+                    None => continue,
+                };
+                let next = (arc.nextstate, next_ipos, next_opos);
+                let next_product_state = match product_states.entry(next) {
+                    Entry::Occupied(e) => *e.get(),
+                    Entry::Vacant(e) => {
+                        queue.push(next);
+                        *e.insert(product.add_state())
+                    }
+                };
+                product.add_arc(
+                    product_state,
+                    Arc::new(
+                        arc.ilabel,
+                        arc.olabel,
+                        arc.weight.clone(),
+                        next_product_state,
+                    ),
+                )?;
+            }
+        }
+
+        let distances = shortest_distance(&product, false)?;
+        let mut weight = W::zero();
+        for (s, distance) in distances.iter().enumerate() {
+            if let Some(final_weight) = product.final_weight(s)? {
+                weight.plus_assign(distance.times(final_weight)?)?;
+            }
+        }
+        Ok(weight)
+    }
+
+    /// Checks that the stored weight of the path matches the weight recomputed with
+    /// [`recompute_weight`](#method.recompute_weight). Meant to catch bugs in the code
+    /// manipulating paths, e.g. with `debug_assert!(path.check_weight(&fst).is_ok())`.
+    pub fn check_weight<F: Fst<W = W>>(&self, fst: &F) -> Fallible<()>
+    where
+        W: 'static,
+        W::ReverseWeight: 'static,
+    {
+        let weight = self.recompute_weight(fst)?;
+        ensure!(
+            weight == self.weight,
+            "The weight of the path {:?} doesn't match the recomputed weight {:?}",
+            self.weight,
+            weight
+        );
+        Ok(())
+    }
+}
+
+/// Position in `labels` after reading `label` at position `pos`, `None` if it doesn't match.
+fn next_position(labels: &[Label], pos: usize, label: Label) -> Option<usize> {
+    if label == EPS_LABEL {
+        Some(pos)
+    } else if labels.get(pos) == Some(&label) {
+        Some(pos + 1)
+    } else {
+        None
+    }
+}
+
+/// Compares two paths by weight according to the natural order of the semiring : `w1 < w2` iff
+/// `w1 + w2 = w1` and `w1 != w2`. For the tropical semiring, the path with the smallest weight
+/// comes first. This is only a total order for path semirings (see `sort_paths`).
+pub fn by_weight<W: Semiring>(p1: &FstPath<W>, p2: &FstPath<W>) -> Fallible<Ordering> {
+    natural_cmp(&p1.weight, &p2.weight)
+}
+
+/// Compares two weights according to the natural order of the semiring, see `by_weight`.
+pub(crate) fn natural_cmp<W: Semiring>(w1: &W, w2: &W) -> Fallible<Ordering> {
+    if w1 == w2 {
+        Ok(Ordering::Equal)
+    } else if &w1.plus(w2)? == w1 {
+        Ok(Ordering::Less)
+    } else {
+        Ok(Ordering::Greater)
+    }
+}
+
+/// Sorts paths from the best to the worst weight using `by_weight`, e.g. to get an n-best
+/// list out of `paths_iter` on a small acyclic FST. The order of paths with the same weight is
+/// preserved. An error is returned if the semiring doesn't have the path property, its natural
+/// order not being total.
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate rustfst;
+/// # use failure::Fallible;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::{sort_paths, FstPath};
+/// # fn main() -> Fallible<()> {
+/// let mut paths: Vec<FstPath<TropicalWeight>> = vec![fst_path![1; 3.0], fst_path![2; 1.0]];
+/// sort_paths(&mut paths)?;
+///
+/// assert_eq!(paths, vec![fst_path![2; 1.0], fst_path![1; 3.0]]);
+/// # Ok(())
+/// # }
+/// ```
+pub fn sort_paths<W: Semiring>(paths: &mut Vec<FstPath<W>>) -> Fallible<()> {
+    ensure!(
+        W::properties().contains(SemiringProperties::PATH),
+        "Paths can only be sorted by weight over a path semiring"
+    );
+    let mut error = None;
+    paths.sort_by(|p1, p2| {
+        by_weight(p1, p2).unwrap_or_else(|e| {
+            error.get_or_insert(e);
+            Ordering::Equal
+        })
+    });
+    match error {
+        Some(e) => Err(e),
+        None => Ok(()),
+    }
 }
 
 impl<W: Semiring> Default for FstPath<W> {
@@ -77,6 +236,58 @@ impl<W: Semiring + Hash + Eq> Hash for FstPath<W> {
 
 impl<W: Semiring + Hash + Eq> Eq for FstPath<W> {}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::{LogWeight, TropicalWeight};
+
+    #[test]
+    fn test_check_weight() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, TropicalWeight::new(0.5))?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(EPS_LABEL, 3, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(2, Arc::new(4, EPS_LABEL, TropicalWeight::new(3.0), 3))?;
+        fst.add_arc(0, Arc::new(5, 5, TropicalWeight::new(1.5), 3))?;
+
+        for path in fst.paths_iter() {
+            assert_eq!(path.recompute_weight(&fst)?, path.weight);
+            path.check_weight(&fst)?;
+        }
+
+        let mut path = FstPath::new(vec![1, 4], vec![2, 3], TropicalWeight::new(6.5));
+        path.check_weight(&fst)?;
+
+        path.weight = TropicalWeight::new(4.0);
+        assert!(path.check_weight(&fst).is_err());
+
+        path.ilabels = vec![1];
+        assert_eq!(path.recompute_weight(&fst)?, TropicalWeight::zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sort_paths() -> Fallible<()> {
+        let mut paths = vec![
+            FstPath::new(vec![1], vec![1], TropicalWeight::new(2.0)),
+            FstPath::new(vec![2], vec![2], TropicalWeight::new(0.5)),
+            FstPath::new(vec![3], vec![3], TropicalWeight::new(1.0)),
+        ];
+        sort_paths(&mut paths)?;
+
+        let ilabels: Vec<_> = paths.iter().map(|p| p.ilabels[0]).collect();
+        assert_eq!(ilabels, vec![2, 3, 1]);
+
+        let mut paths = vec![FstPath::new(vec![1], vec![1], LogWeight::new(2.0))];
+        assert!(sort_paths(&mut paths).is_err());
+        Ok(())
+    }
+}
+
 /// Creates a Path containing the arguments.
 ///
 /// There are multiple forms to this macro :
@@ -2,6 +2,7 @@ use std::path::Path;
 
 use failure::Fallible;
 
+use crate::fst_properties::FstProperties;
 use crate::fst_traits::ExpandedFst;
 use crate::semirings::Semiring;
 
@@ -9,5 +10,22 @@ pub trait BinaryDeserializer: ExpandedFst
 where
     Self::W: Semiring<Type = f32>,
 {
+    /// Reads an FST from a binary file in the OpenFST format. With the `flate2` feature,
+    /// `VectorFst` also reads gzip-compressed files, which are decompressed transparently.
     fn read<P: AsRef<Path>>(path_bin_fst: P) -> Fallible<Self>;
+
+    /// Reads an FST along with the properties stored in the header of the file, which avoids
+    /// recomputing them. If `verify` is set, an error is raised if the stored properties don't
+    /// hold for the FST.
+    ///
+    /// The default implementation reads the FST with `read` and computes its properties, which
+    /// hold by construction whatever `verify`.
+    fn read_with_properties<P: AsRef<Path>>(
+        path_bin_fst: P,
+        _verify: bool,
+    ) -> Fallible<(Self, FstProperties)> {
+        let fst = Self::read(path_bin_fst)?;
+        let properties = fst.properties()?;
+        Ok((fst, properties))
+    }
 }
@@ -9,11 +9,12 @@ use crate::fst_properties::FstProperties;
 use crate::fst_traits::final_states_iterator::FinalStatesIterator;
 use crate::fst_traits::Fst;
 use crate::semirings::Semiring;
-use crate::DrawingConfig;
+use crate::{Arc, DrawingConfig, StateId};
 
 /// Trait defining the necessary methods that should implement an ExpandedFST e.g
-/// a FST where all the states are already computed and not computed on the fly.
-pub trait ExpandedFst: Fst {
+/// a FST where all the states are already computed and not computed on the fly. `M` is the type
+/// of the payload carried by the arcs.
+pub trait ExpandedFst<M: 'static = ()>: Fst<M> {
     /// Returns the number of states that contains the FST. They are all counted even if some states
     /// are not on a successful path (doesn't perform triming).
     ///
@@ -36,6 +37,50 @@ pub trait ExpandedFst: Fst {
     /// ```
     fn num_states(&self) -> usize;
 
+    /// Returns an owned snapshot of all the arcs of the FST, each one paired with the state it
+    /// is leaving. Arcs are ordered by state, then in the order they are stored.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rustfst::fst_traits::{MutableFst, ExpandedFst};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::semirings::{BooleanWeight, Semiring};
+    /// # use rustfst::Arc;
+    /// let mut fst = VectorFst::<BooleanWeight>::new();
+    /// let s1 = fst.add_state();
+    /// let s2 = fst.add_state();
+    /// fst.add_arc(s1, Arc::new(3, 5, BooleanWeight::one(), s2)).unwrap();
+    ///
+    /// assert_eq!(fst.arcs_snapshot(), vec![(s1, Arc::new(3, 5, BooleanWeight::one(), s2))]);
+    /// ```
+    fn arcs_snapshot(&self) -> Vec<(StateId, Arc<Self::W, M>)>
+    where
+        M: Clone,
+    {
+        let mut arcs = Vec::new();
+        for state in self.states_iter() {
+            for arc in unsafe { self.arcs_iter_unchecked(state) } {
+                arcs.push((state, arc.clone()));
+            }
+        }
+        arcs
+    }
+
+    /// Returns a parallel iterator over a snapshot of all the arcs of the FST, each one
+    /// paired with the state it is leaving. Useful to score the arcs concurrently.
+    ///
+    /// Only available with the `rayon` feature.
+    #[cfg(feature = "rayon")]
+    fn par_arcs(&self) -> rayon::vec::IntoIter<(StateId, Arc<Self::W, M>)>
+    where
+        Self::W: Send,
+        M: Clone + Send,
+    {
+        use rayon::iter::IntoParallelIterator;
+        self.arcs_snapshot().into_par_iter()
+    }
+
     /// Serializes the FST as a text file in a format compatible with OpenFST.
     fn write_text<P: AsRef<Path>>(&self, path_output: P) -> Fallible<()> {
         let buffer = File::create(path_output.as_ref())?;
@@ -94,7 +139,40 @@ pub trait ExpandedFst: Fst {
     }
 
     /// Compute the properties verified by the Fst.
-    fn properties(&self) -> Fallible<FstProperties> {
+    fn properties(&self) -> Fallible<FstProperties>
+    where
+        Self: ExpandedFst,
+    {
         compute_fst_properties(self)
     }
+
+    /// Returns whether no state has two arcs with the same input label.
+    ///
+    /// The properties are not cached : they are computed on each call, in O(|arcs|).
+    fn is_deterministic(&self) -> Fallible<bool>
+    where
+        Self: ExpandedFst,
+    {
+        Ok(compute_fst_properties(self)?.contains(FstProperties::I_DETERMINISTIC))
+    }
+
+    /// Returns whether the FST has no cycle.
+    ///
+    /// The properties are computed on each call, in O(|arcs|).
+    fn is_acyclic(&self) -> Fallible<bool>
+    where
+        Self: ExpandedFst,
+    {
+        Ok(compute_fst_properties(self)?.contains(FstProperties::ACYCLIC))
+    }
+
+    /// Returns whether no arc has both an epsilon input label and an epsilon output label.
+    ///
+    /// The properties are computed on each call, in O(|arcs|).
+    fn is_epsilon_free(&self) -> Fallible<bool>
+    where
+        Self: ExpandedFst,
+    {
+        Ok(compute_fst_properties(self)?.contains(FstProperties::NO_EPSILONS))
+    }
 }
@@ -1,4 +1,4 @@
-use crate::fst_traits::{Fst, StateIterator};
+use crate::fst_traits::{CoreFst, StateIterator};
 use crate::semirings::Semiring;
 use crate::StateId;
 
@@ -17,7 +17,7 @@ pub trait FinalStatesIterator<'f> {
 
 impl<'f, F> FinalStatesIterator<'f> for F
 where
-    F: 'f + Fst,
+    F: 'f + CoreFst + StateIterator<'f>,
 {
     type W = F::W;
     type Iter = StructFinalStatesIterator<'f, F>;
@@ -28,7 +28,7 @@ where
 
 pub struct StructFinalStatesIterator<'f, F>
 where
-    F: 'f + Fst,
+    F: 'f + CoreFst + StateIterator<'f>,
 {
     fst: &'f F,
     it: <F as StateIterator<'f>>::Iter,
@@ -36,7 +36,7 @@ where
 
 impl<'f, F> StructFinalStatesIterator<'f, F>
 where
-    F: 'f + Fst,
+    F: 'f + CoreFst + StateIterator<'f>,
 {
     fn new(fst: &'f F) -> StructFinalStatesIterator<F> {
         StructFinalStatesIterator {
@@ -48,7 +48,7 @@ where
 
 impl<'f, F> Iterator for StructFinalStatesIterator<'f, F>
 where
-    F: 'f + Fst,
+    F: 'f + CoreFst + StateIterator<'f>,
 {
     type Item = FinalState<'f, F::W>;
 
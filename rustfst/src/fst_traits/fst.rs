@@ -3,9 +3,8 @@ use std::fmt::{Debug, Display};
 
 use failure::Fallible;
 
-use crate::algorithms::arc_filters::{ArcFilter, InputEpsilonArcFilter, OutputEpsilonArcFilter};
 use crate::semirings::Semiring;
-use crate::StateId;
+use crate::{EpsilonConfig, StateId, EPS_LABEL};
 
 /// Trait defining necessary methods for a wFST to access start states and final states.
 pub trait CoreFst {
@@ -110,9 +109,16 @@ pub trait CoreFst {
 }
 
 
-/// Trait defining the minimum interface necessary for a wFST.
-pub trait Fst:
-    CoreFst + PartialEq + Clone + for<'a> ArcIterator<'a> + for<'b> StateIterator<'b> + Display + Debug
+/// Trait defining the minimum interface necessary for a wFST. `M` is the type of the payload
+/// carried by the arcs.
+pub trait Fst<M: 'static = ()>:
+    CoreFst
+    + PartialEq
+    + Clone
+    + for<'a> ArcIterator<'a, M>
+    + for<'b> StateIterator<'b>
+    + Display
+    + Debug
 {
     // TODO: Move niepsilons and noepsilons to required methods.
     /// Returns the number of arcs with epsilon input labels leaving a state.
@@ -138,8 +144,10 @@ pub trait Fst:
     /// assert_eq!(fst.num_input_epsilons(s1).unwrap(), 0);
     /// ```
     fn num_input_epsilons(&self, state: StateId) -> Fallible<usize> {
-        let filter = InputEpsilonArcFilter {};
-        Ok(self.arcs_iter(state)?.filter(|v| filter.keep(v)).count())
+        Ok(self
+            .arcs_iter(state)?
+            .filter(|arc| arc.ilabel == EPS_LABEL)
+            .count())
     }
 
     /// Returns the number of arcs with epsilon output labels leaving a state.
@@ -165,8 +173,72 @@ pub trait Fst:
     /// assert_eq!(fst.num_output_epsilons(s1).unwrap(), 0);
     /// ```
     fn num_output_epsilons(&self, state: StateId) -> Fallible<usize> {
-        let filter = OutputEpsilonArcFilter {};
-        Ok(self.arcs_iter(state)?.filter(|v| filter.keep(v)).count())
+        Ok(self
+            .arcs_iter(state)?
+            .filter(|arc| arc.olabel == EPS_LABEL)
+            .count())
+    }
+
+    /// Returns the number of arcs with epsilon input labels leaving a state, epsilon being
+    /// the label defined in the `EpsilonConfig`.
+    ///
+    /// # Example :
+    /// ```
+    /// # use rustfst::fst_traits::{MutableFst, Fst};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::semirings::{Semiring, IntegerWeight};
+    /// # use rustfst::{Arc, EpsilonConfig};
+    /// let mut fst = VectorFst::new();
+    /// let s0 = fst.add_state();
+    /// let s1 = fst.add_state();
+    ///
+    /// fst.add_arc(s0, Arc::new(99, 18, IntegerWeight::one(), s1));
+    /// fst.add_arc(s0, Arc::new(0, 99, IntegerWeight::one(), s1));
+    ///
+    /// let config = EpsilonConfig::new(99);
+    /// assert_eq!(fst.num_input_epsilons_with_config(s0, &config).unwrap(), 1);
+    /// assert_eq!(fst.num_input_epsilons_with_config(s1, &config).unwrap(), 0);
+    /// ```
+    fn num_input_epsilons_with_config(
+        &self,
+        state: StateId,
+        config: &EpsilonConfig,
+    ) -> Fallible<usize> {
+        Ok(self
+            .arcs_iter(state)?
+            .filter(|arc| config.is_epsilon(arc.ilabel))
+            .count())
+    }
+
+    /// Returns the number of arcs with epsilon output labels leaving a state, epsilon being
+    /// the label defined in the `EpsilonConfig`.
+    ///
+    /// # Example :
+    /// ```
+    /// # use rustfst::fst_traits::{MutableFst, Fst};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::semirings::{Semiring, IntegerWeight};
+    /// # use rustfst::{Arc, EpsilonConfig};
+    /// let mut fst = VectorFst::new();
+    /// let s0 = fst.add_state();
+    /// let s1 = fst.add_state();
+    ///
+    /// fst.add_arc(s0, Arc::new(99, 18, IntegerWeight::one(), s1));
+    /// fst.add_arc(s0, Arc::new(0, 99, IntegerWeight::one(), s1));
+    ///
+    /// let config = EpsilonConfig::new(99);
+    /// assert_eq!(fst.num_output_epsilons_with_config(s0, &config).unwrap(), 1);
+    /// assert_eq!(fst.num_output_epsilons_with_config(s1, &config).unwrap(), 0);
+    /// ```
+    fn num_output_epsilons_with_config(
+        &self,
+        state: StateId,
+        config: &EpsilonConfig,
+    ) -> Fallible<usize> {
+        Ok(self
+            .arcs_iter(state)?
+            .filter(|arc| config.is_epsilon(arc.olabel))
+            .count())
     }
 
     /// Returns true if the Fst is an acceptor. False otherwise.
@@ -32,13 +32,14 @@ pub trait StateIterator<'a> {
     fn states_iter(&'a self) -> Self::Iter;
 }
 
-/// Trait to iterate over the outgoing arcs of a particular state in a wFST
-pub trait ArcIterator<'a>: CoreFst
+/// Trait to iterate over the outgoing arcs of a particular state in a wFST. `M` is the type of
+/// the payload carried by the arcs.
+pub trait ArcIterator<'a, M: 'a = ()>: CoreFst
 where
     Self::W: 'a,
 {
     /// Iterator used to iterate over the arcs leaving a state of an FST.
-    type Iter: Iterator<Item = &'a Arc<Self::W>> + Clone;
+    type Iter: Iterator<Item = &'a Arc<Self::W, M>> + Clone;
 
     fn arcs_iter(&'a self, state_id: StateId) -> Fallible<Self::Iter>;
     unsafe fn arcs_iter_unchecked(&'a self, state_id: StateId) -> Self::Iter;
@@ -5,11 +5,13 @@ use failure::Fallible;
 use crate::algorithms::ArcMapper;
 use crate::arc::Arc;
 use crate::fst_traits::{CoreFst, ExpandedFst, Fst};
+use crate::semirings::Semiring;
 use crate::StateId;
 use std::cmp::Ordering;
 
-/// Trait defining the methods to modify a wFST.
-pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
+/// Trait defining the methods to modify a wFST. `M` is the type of the payload carried by the
+/// arcs, `add_arc` and `arcs_iter_mut` keeping it untouched.
+pub trait MutableFst<M: 'static = ()>: Fst<M> + for<'a> MutableArcIterator<'a, M> {
     /// Creates an empty wFST.
     fn new() -> Self;
 
@@ -166,19 +168,85 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
     /// fst.add_arc(s1, Arc::new(3, 5, BooleanWeight::new(true), s2));
     /// assert_eq!(fst.num_arcs(s1).unwrap(), 1);
     /// ```
-    fn add_arc(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W>) -> Fallible<()>;
-    unsafe fn add_arc_unchecked(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W>);
-    unsafe fn set_arcs_unchecked(&mut self, source: StateId, arcs: Vec<Arc<<Self as CoreFst>::W>>);
+    fn add_arc(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W, M>) -> Fallible<()>;
+    unsafe fn add_arc_unchecked(&mut self, source: StateId, arc: Arc<<Self as CoreFst>::W, M>);
+
+    /// Adds an arc to the FST, unless an arc with the same input label, output label and
+    /// destination state already leaves the state `source`. In that case, the weight of the new
+    /// arc is ⊕-summed into the existing one. This keeps the FST free of parallel arcs during an
+    /// incremental construction.
+    /// An error is raised if the state `source` doesn't exist.
+    ///
+    /// Note that each insertion scans all the arcs leaving `source`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rustfst::fst_traits::{CoreFst, MutableFst, ExpandedFst, Fst};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::semirings::{IntegerWeight, Semiring};
+    /// # use rustfst::Arc;
+    /// let mut fst = VectorFst::<IntegerWeight>::new();
+    /// let s1 = fst.add_state();
+    /// let s2 = fst.add_state();
+    ///
+    /// fst.add_arc_combining(s1, Arc::new(3, 5, IntegerWeight::new(2), s2)).unwrap();
+    /// fst.add_arc_combining(s1, Arc::new(3, 5, IntegerWeight::new(4), s2)).unwrap();
+    /// assert_eq!(fst.num_arcs(s1).unwrap(), 1);
+    ///
+    /// fst.add_arc_combining(s1, Arc::new(3, 6, IntegerWeight::new(4), s2)).unwrap();
+    /// assert_eq!(fst.num_arcs(s1).unwrap(), 2);
+    /// ```
+    fn add_arc_combining(
+        &mut self,
+        source: StateId,
+        arc: Arc<<Self as CoreFst>::W, M>,
+    ) -> Fallible<()> {
+        for existing_arc in self.arcs_iter_mut(source)? {
+            if existing_arc.ilabel == arc.ilabel
+                && existing_arc.olabel == arc.olabel
+                && existing_arc.nextstate == arc.nextstate
+            {
+                existing_arc.weight.plus_assign(&arc.weight)?;
+                return Ok(());
+            }
+        }
+        self.add_arc(source, arc)
+    }
+    unsafe fn set_arcs_unchecked(
+        &mut self,
+        source: StateId,
+        arcs: Vec<Arc<<Self as CoreFst>::W, M>>,
+    );
 
     /// Remove the final weight of a specific state.
     fn delete_final_weight(&mut self, source: StateId) -> Fallible<()>;
 
-    /// Deletes all the arcs leaving a state.
+    /// Deletes all the arcs leaving a state. The state itself and its final weight are kept,
+    /// which allows algorithms to rebuild the arcs of a state from scratch.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use rustfst::fst_traits::{CoreFst, MutableFst};
+    /// # use rustfst::fst_impls::VectorFst;
+    /// # use rustfst::semirings::{BooleanWeight, Semiring};
+    /// # use rustfst::Arc;
+    /// let mut fst = VectorFst::<BooleanWeight>::new();
+    /// let s = fst.add_state();
+    /// fst.set_final(s, BooleanWeight::one()).unwrap();
+    /// fst.add_arc(s, Arc::new(1, 2, BooleanWeight::one(), s)).unwrap();
+    ///
+    /// fst.delete_arcs(s).unwrap();
+    ///
+    /// assert_eq!(fst.num_arcs(s).unwrap(), 0);
+    /// assert!(fst.is_final(s).unwrap());
+    /// ```
     fn delete_arcs(&mut self, source: StateId) -> Fallible<()>;
 
     /// Remove all arcs leaving a state and return them.
-    fn pop_arcs(&mut self, source: StateId) -> Fallible<Vec<Arc<Self::W>>>;
-    unsafe fn pop_arcs_unchecked(&mut self, source: StateId) -> Vec<Arc<Self::W>>;
+    fn pop_arcs(&mut self, source: StateId) -> Fallible<Vec<Arc<Self::W, M>>>;
+    unsafe fn pop_arcs_unchecked(&mut self, source: StateId) -> Vec<Arc<Self::W, M>>;
 
     /// Reserve space for storing enough arcs leaving a state.
     fn reserve_arcs(&mut self, source: StateId, additional: usize) -> Fallible<()>;
@@ -197,7 +265,7 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
         state_id: StateId,
     ) -> Option<&mut <Self as CoreFst>::W>;
 
-    fn sort_arcs_unchecked<F: Fn(&Arc<Self::W>, &Arc<Self::W>) -> Ordering>(
+    fn sort_arcs_unchecked<F: Fn(&Arc<Self::W, M>, &Arc<Self::W, M>) -> Ordering>(
         &mut self,
         state: StateId,
         f: F,
@@ -207,10 +275,13 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
 
     unsafe fn sum_arcs_unchecked(&mut self, state: StateId);
 
-    fn add_fst<F: ExpandedFst<W = Self::W>>(
+    fn add_fst<F: ExpandedFst<M, W = Self::W>>(
         &mut self,
         fst_to_add: &F,
-    ) -> Fallible<HashMap<StateId, StateId>> {
+    ) -> Fallible<HashMap<StateId, StateId>>
+    where
+        M: Clone,
+    {
         // Map old states id to new ones
         let mut mapping_states = HashMap::new();
 
@@ -223,15 +294,9 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
         // Second pass to add the arcs
         for old_state_id in fst_to_add.states_iter() {
             for old_arc in fst_to_add.arcs_iter(old_state_id)? {
-                self.add_arc(
-                    mapping_states[&old_state_id],
-                    Arc::new(
-                        old_arc.ilabel,
-                        old_arc.olabel,
-                        old_arc.weight.clone(),
-                        mapping_states[&old_arc.nextstate],
-                    ),
-                )?;
+                let mut new_arc = old_arc.clone();
+                new_arc.nextstate = mapping_states[&old_arc.nextstate];
+                self.add_arc(mapping_states[&old_state_id], new_arc)?;
             }
         }
 
@@ -242,7 +307,10 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
     /// If A transduces string `x` to `y` with weight `a`,
     /// then the closure transduces `x` to `y` with weight `a`,
     /// `xx` to `yy` with weight `a ⊗ a`, `xxx` to `yyy` with weight `a ⊗ a ⊗ a`, etc.
-    fn closure_plus(&mut self) {
+    fn closure_plus(&mut self)
+    where
+        Self: MutableFst,
+    {
         crate::algorithms::closure_plus(self)
     }
 
@@ -251,22 +319,28 @@ pub trait MutableFst: Fst + for<'a> MutableArcIterator<'a> {
     /// then the closure transduces `x` to `y` with weight `a`,
     /// `xx` to `yy` with weight `a ⊗ a`, `xxx` to `yyy` with weight `a ⊗ a ⊗ a`, etc.
     /// The empty string is transduced to itself with weight `1` as well.
-    fn closure_star(&mut self) {
+    fn closure_star(&mut self)
+    where
+        Self: MutableFst,
+    {
         crate::algorithms::closure_star(self)
     }
 
     /// Maps an arc using a `ArcMapper` object.
-    fn arc_map<M: ArcMapper<Self::W>>(&mut self, mapper: &mut M) -> Fallible<()> {
+    fn arc_map<AM: ArcMapper<Self::W>>(&mut self, mapper: &mut AM) -> Fallible<()>
+    where
+        Self: MutableFst,
+    {
         crate::algorithms::arc_map(self, mapper)
     }
 }
 
-/// Iterate over mutable arcs in a wFST.
-pub trait MutableArcIterator<'a>: CoreFst
+/// Iterate over mutable arcs in a wFST. `M` is the type of the payload carried by the arcs.
+pub trait MutableArcIterator<'a, M: 'a = ()>: CoreFst
 where
     Self::W: 'a,
 {
-    type IterMut: Iterator<Item = &'a mut Arc<Self::W>>;
+    type IterMut: Iterator<Item = &'a mut Arc<Self::W, M>>;
     fn arcs_iter_mut(&'a mut self, state_id: StateId) -> Fallible<Self::IterMut>;
     unsafe fn arcs_iter_unchecked_mut(&'a mut self, state_id: StateId) -> Self::IterMut;
 }
@@ -6,10 +6,16 @@ use crate::semirings::Semiring;
 use crate::StateId;
 
 /// Trait to iterate over the paths accepted by an FST.
+///
+/// To keep the enumeration finite on cyclic FSTs, a path never goes twice through the same
+/// state, self-loops included, unless a maximum depth is set with `paths_iter_with_max_depth`.
 pub trait PathsIterator<'a> {
     type W: Semiring;
     type Iter: Iterator<Item = FstPath<Self::W>>;
     fn paths_iter(&'a self) -> Self::Iter;
+    /// Iterates over the paths made of at most `max_depth` arcs. Cycles are followed until
+    /// that depth is reached.
+    fn paths_iter_with_max_depth(&'a self, max_depth: usize) -> Self::Iter;
 }
 
 impl<'a, F> PathsIterator<'a> for F
@@ -21,6 +27,10 @@ where
     fn paths_iter(&'a self) -> Self::Iter {
         StructPathsIterator::new(&self)
     }
+
+    fn paths_iter_with_max_depth(&'a self, max_depth: usize) -> Self::Iter {
+        StructPathsIterator::with_max_depth(&self, max_depth)
+    }
 }
 
 pub struct StructPathsIterator<'a, F>
@@ -28,7 +38,9 @@ where
     F: 'a + Fst,
 {
     fst: &'a F,
-    queue: VecDeque<(StateId, FstPath<F::W>)>,
+    /// State reached, path leading to it and states visited along the path.
+    queue: VecDeque<(StateId, FstPath<F::W>, Vec<StateId>)>,
+    max_depth: Option<usize>,
 }
 
 impl<'a, F> StructPathsIterator<'a, F>
@@ -36,13 +48,35 @@ where
     F: 'a + Fst,
 {
     pub fn new(fst: &'a F) -> Self {
+        Self::init(fst, None)
+    }
+
+    pub fn with_max_depth(fst: &'a F, max_depth: usize) -> Self {
+        Self::init(fst, Some(max_depth))
+    }
+
+    fn init(fst: &'a F, max_depth: Option<usize>) -> Self {
         let mut queue = VecDeque::new();
 
         if let Some(state_start) = fst.start() {
-            queue.push_back((state_start, FstPath::default()));
+            queue.push_back((state_start, FstPath::default(), vec![state_start]));
         }
 
-        StructPathsIterator { fst, queue }
+        StructPathsIterator {
+            fst,
+            queue,
+            max_depth,
+        }
+    }
+
+    /// Returns whether the path can be extended to `nextstate`.
+    fn can_follow(&self, state_id: StateId, nextstate: StateId, visited: &[StateId]) -> bool {
+        match self.max_depth {
+            // The number of arcs of the path is the number of visited states minus one.
+            Some(max_depth) => visited.len() <= max_depth,
+            // Self-loops are the most common cycles, checked without scanning the path.
+            None => nextstate != state_id && !visited.contains(&nextstate),
+        }
     }
 }
 
@@ -54,14 +88,19 @@ where
 
     fn next(&mut self) -> Option<Self::Item> {
         while !self.queue.is_empty() {
-            let (state_id, mut path) = self.queue.pop_front().unwrap();
+            let (state_id, mut path, visited) = self.queue.pop_front().unwrap();
 
             for arc in unsafe { self.fst.arcs_iter_unchecked(state_id) } {
+                if !self.can_follow(state_id, arc.nextstate, &visited) {
+                    continue;
+                }
                 let mut new_path = path.clone();
                 new_path
                     .add_to_path(arc.ilabel, arc.olabel, &arc.weight)
                     .expect("Error add_to_path in PathsIterator");
-                self.queue.push_back((arc.nextstate, new_path));
+                let mut new_visited = visited.clone();
+                new_visited.push(arc.nextstate);
+                self.queue.push_back((arc.nextstate, new_path, new_visited));
             }
 
             if let Some(final_weight) = unsafe { self.fst.final_weight_unchecked(state_id) } {
@@ -233,4 +272,59 @@ mod tests {
 
         assert_eq!(paths_ref, paths);
     }
+
+    #[test]
+    fn test_paths_iterator_self_loop() {
+        let mut fst: VectorFst<IntegerWeight> = VectorFst::new();
+
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+
+        fst.set_start(s1).unwrap();
+        fst.set_final(s2, IntegerWeight::new(5)).unwrap();
+
+        fst.add_arc(s1, Arc::new(1, 1, IntegerWeight::new(2), s2))
+            .unwrap();
+        fst.add_arc(s1, Arc::new(3, 3, IntegerWeight::new(3), s3))
+            .unwrap();
+        fst.add_arc(s3, Arc::new(4, 4, IntegerWeight::new(4), s2))
+            .unwrap();
+        fst.add_arc(s2, Arc::new(2, 2, IntegerWeight::new(7), s2))
+            .unwrap();
+
+        let mut paths_ref = Counter::new();
+        paths_ref.update(vec![FstPath::new(
+            vec![1],
+            vec![1],
+            IntegerWeight::new(2 * 5),
+        )]);
+        paths_ref.update(vec![FstPath::new(
+            vec![3, 4],
+            vec![3, 4],
+            IntegerWeight::new(3 * 4 * 5),
+        )]);
+
+        let paths: Counter<_> = fst.paths_iter().collect();
+        assert_eq!(paths_ref, paths);
+
+        paths_ref.update(vec![FstPath::new(
+            vec![1, 2],
+            vec![1, 2],
+            IntegerWeight::new(2 * 7 * 5),
+        )]);
+        paths_ref.update(vec![FstPath::new(
+            vec![1, 2, 2],
+            vec![1, 2, 2],
+            IntegerWeight::new(2 * 7 * 7 * 5),
+        )]);
+        paths_ref.update(vec![FstPath::new(
+            vec![3, 4, 2],
+            vec![3, 4, 2],
+            IntegerWeight::new(3 * 4 * 7 * 5),
+        )]);
+
+        let paths: Counter<_> = fst.paths_iter_with_max_depth(3).collect();
+        assert_eq!(paths_ref, paths);
+    }
 }
@@ -25,4 +25,18 @@ where
         let parsed_text_fst = ParsedTextFst::from_path(path_text_fst)?;
         Self::from_parsed_fst_text(parsed_text_fst)
     }
+
+    /// Same as `from_text_string` but the arcs are read in the acceptor format of
+    /// `fstcompile --acceptor`, with a single label per arc, when `acceptor` is true.
+    fn from_text_string_with_format(fst_string: &str, acceptor: bool) -> Fallible<Self> {
+        let parsed_text_fst = ParsedTextFst::from_string_with_format(fst_string, acceptor)?;
+        Self::from_parsed_fst_text(parsed_text_fst)
+    }
+
+    /// Same as `read_text` but the arcs are read in the acceptor format of
+    /// `fstcompile --acceptor`, with a single label per arc, when `acceptor` is true.
+    fn read_text_with_format<P: AsRef<Path>>(path_text_fst: P, acceptor: bool) -> Fallible<Self> {
+        let parsed_text_fst = ParsedTextFst::from_path_with_format(path_text_fst, acceptor)?;
+        Self::from_parsed_fst_text(parsed_text_fst)
+    }
 }
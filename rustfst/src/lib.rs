@@ -105,7 +105,8 @@ extern crate serde;
 extern crate serde_json;
 
 pub use crate::drawing_config::DrawingConfig;
-pub use crate::fst_path::FstPath;
+pub use crate::epsilon_config::EpsilonConfig;
+pub use crate::fst_path::{by_weight, sort_paths, FstPath};
 pub use crate::symbol_table::SymbolTable;
 
 pub use self::arc::Arc;
@@ -160,11 +161,15 @@ pub mod semirings;
 pub(crate) mod test_data;
 
 mod drawing_config;
+mod epsilon_config;
 /// Implementation of a successful path inside a wFST.
 mod fst_path;
 mod parsers;
 
 /// A representable float near .001. (Used in Quantize)
+///
+/// Default delta of the algorithms comparing or quantizing weights, the `_with_delta` variants
+/// of these algorithms allowing to override it.
 pub const KDELTA: f32 = 1.0f32 / 1024.0f32;
 
 pub mod prelude {
@@ -10,15 +10,18 @@ use nom::IResult;
 
 use crate::fst_impls::const_fst::ConstState;
 use crate::fst_impls::ConstFst;
+use crate::fst_properties::FstProperties;
 use crate::fst_traits::{BinaryDeserializer, BinarySerializer, ExpandedFst};
-use crate::parsers::bin_fst::fst_header::{FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::fst_header::{
+    header_properties, verify_properties, FstHeader, OpenFstString, FST_EXPANDED, FST_MAGIC_NUMBER,
+};
 use crate::parsers::bin_fst::utils_parsing::{
     parse_final_weight, parse_fst_arc, parse_start_state,
 };
 use crate::parsers::bin_fst::utils_serialization::{write_bin_f32, write_bin_i32};
+use crate::parsers::bin_fst::vector_fst::parse_vector_fst;
 use crate::semirings::Semiring;
 
-static CONST_MIN_FILE_VERSION: i32 = 1;
 static CONST_ALIGNED_FILE_VERSION: i32 = 1;
 static CONST_FILE_VERSION: i32 = 2;
 static CONST_ARCH_ALIGNMENT: usize = 16;
@@ -42,14 +45,30 @@ fn parse_const_state<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8], ConstS
     ))
 }
 
-fn parse_const_fst<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8], ConstFst<W>> {
-    let stream_len = i.len();
+/// Parses the states and arcs of a ConstFst whose header has already been parsed. `stream_len`
+/// is the length of the whole stream, header included, as the aligned version pads the input
+/// relatively to its beginning.
+pub(crate) fn parse_const_fst<W: Semiring<Type = f32>>(
+    i: &[u8],
+    hdr: &FstHeader,
+    stream_len: usize,
+) -> Fallible<ConstFst<W>> {
+    hdr.check("const", &[CONST_ALIGNED_FILE_VERSION, CONST_FILE_VERSION])?;
+    let (_, const_fst) = parse_const_fst_body(i, hdr, stream_len)
+        .map_err(|_| format_err!("Error while parsing binary ConstFst"))?;
+    Ok(const_fst)
+}
 
-    let (mut i, hdr) = FstHeader::parse(i, CONST_MIN_FILE_VERSION)?;
+fn parse_const_fst_body<'a, W: Semiring<Type = f32>>(
+    i: &'a [u8],
+    hdr: &FstHeader,
+    stream_len: usize,
+) -> IResult<&'a [u8], ConstFst<W>> {
     let aligned = hdr.version == CONST_ALIGNED_FILE_VERSION;
     let pos = stream_len - i.len();
 
     // Align input
+    let mut i = i;
     if aligned && hdr.num_states > 0 {
         if pos % CONST_ARCH_ALIGNMENT > 0 {
             i = take(CONST_ARCH_ALIGNMENT - (pos % CONST_ARCH_ALIGNMENT))(i)?.0;
@@ -78,6 +97,13 @@ fn parse_const_fst<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8], ConstFst
 
 impl<W: Semiring<Type = f32> + 'static> BinaryDeserializer for ConstFst<W> {
     fn read<P: AsRef<Path>>(path_bin_fst: P) -> Fallible<Self> {
+        Self::read_with_properties(path_bin_fst, false).map(|(fst, _)| fst)
+    }
+
+    fn read_with_properties<P: AsRef<Path>>(
+        path_bin_fst: P,
+        verify: bool,
+    ) -> Fallible<(Self, FstProperties)> {
         let data = read(path_bin_fst.as_ref()).with_context(|_| {
             format!(
                 "Can't open ConstFst binary file : {:?}",
@@ -85,10 +111,20 @@ impl<W: Semiring<Type = f32> + 'static> BinaryDeserializer for ConstFst<W> {
             )
         })?;
 
-        let (_, parsed_fst) = parse_const_fst(&data)
-            .map_err(|_| format_err!("Error while parsing binary ConstFst"))?;
+        let (i, hdr) = FstHeader::parse(&data)
+            .map_err(|_| format_err!("Error while parsing the header of binary ConstFst"))?;
+        let parsed_fst = match hdr.fst_type() {
+            "const" => parse_const_fst(i, &hdr, data.len())?,
+            "vector" => parse_vector_fst::<W>(i, &hdr)?.into(),
+            fst_type => bail!("Unsupported FST type {:?} for binary ConstFst", fst_type),
+        };
+        let properties = hdr.fst_properties();
+
+        if verify {
+            verify_properties(&parsed_fst, properties)?;
+        }
 
-        Ok(parsed_fst)
+        Ok((parsed_fst, properties))
     }
 }
 
@@ -104,8 +140,7 @@ impl<W: 'static + Semiring<Type = f32>> BinarySerializer for ConstFst<W> {
             version: CONST_FILE_VERSION,
             // TODO: Flags are used to check whether or not a symboltable has to be loaded
             flags: 0i32,
-            // TODO: Once the properties are stored, need to read them. kExpanded
-            properties: 1u64,
+            properties: header_properties(FST_EXPANDED, self.properties()?),
             start: self.start.map(|v| v as i64).unwrap_or(-1),
             num_states: self.num_states() as i64,
             num_arcs: self.arcs.len() as i64,
@@ -6,11 +6,44 @@ use nom::combinator::verify;
 use nom::number::complete::{le_i32, le_i64, le_u64};
 use nom::IResult;
 
+use crate::fst_properties::FstProperties;
+use crate::fst_traits::ExpandedFst;
 use crate::parsers::bin_fst::utils_serialization::{write_bin_i32, write_bin_i64, write_bin_u64};
 
 // Identifies stream data as an FST (and its endianity).
 pub(crate) static FST_MAGIC_NUMBER: i32 = 2_125_659_606;
 
+// Binary properties stored in the lowest bits of the header properties.
+// The FST is an ExpandedFst.
+pub(crate) static FST_EXPANDED: u64 = 0x1;
+// The FST is a MutableFst.
+pub(crate) static FST_MUTABLE: u64 = 0x2;
+// Position of the FstProperties in the header properties, as in OpenFST.
+static FST_PROPERTIES_SHIFT: u64 = 16;
+// Arc types whose weights are stored as single precision floats.
+static SUPPORTED_ARC_TYPES: [&str; 2] = ["standard", "log"];
+
+/// Builds the properties stored in the header from the binary properties and the FstProperties.
+pub(crate) fn header_properties(binary_properties: u64, properties: FstProperties) -> u64 {
+    binary_properties | (u64::from(properties.bits()) << FST_PROPERTIES_SHIFT)
+}
+
+/// Checks that the properties read from a header hold for the FST.
+pub(crate) fn verify_properties<F: ExpandedFst>(
+    fst: &F,
+    properties: FstProperties,
+) -> Fallible<()> {
+    let computed_properties = fst.properties()?;
+    if !computed_properties.contains(properties) {
+        bail!(
+            "The properties stored in the header {:?} don't hold for the FST {:?}",
+            properties,
+            computed_properties
+        );
+    }
+    Ok(())
+}
+
 #[derive(Debug)]
 pub(crate) struct FstHeader {
     pub(crate) magic_number: i32,
@@ -31,11 +64,11 @@ pub(crate) struct OpenFstString {
 }
 
 impl FstHeader {
-    pub(crate) fn parse(i: &[u8], min_file_version: i32) -> IResult<&[u8], FstHeader> {
+    pub(crate) fn parse(i: &[u8]) -> IResult<&[u8], FstHeader> {
         let (i, magic_number) = verify(le_i32, |v: &i32| *v == FST_MAGIC_NUMBER)(i)?;
         let (i, fst_type) = OpenFstString::parse(i)?;
         let (i, arc_type) = OpenFstString::parse(i)?;
-        let (i, version) = verify(le_i32, |v: &i32| *v >= min_file_version)(i)?;
+        let (i, version) = le_i32(i)?;
         let (i, flags) = le_i32(i)?;
         let (i, properties) = le_u64(i)?;
         let (i, start) = le_i64(i)?;
@@ -57,6 +90,36 @@ impl FstHeader {
         ))
     }
 
+    /// Type of the FST stored after the header (e.g `vector` or `const`).
+    pub(crate) fn fst_type(&self) -> &str {
+        &self.fst_type.s
+    }
+
+    /// Checks that the arc type and the version of the header are supported for the layout
+    /// `fst_type`, so that an unsupported file is rejected instead of being misparsed.
+    pub(crate) fn check(&self, fst_type: &str, supported_versions: &[i32]) -> Fallible<()> {
+        ensure!(
+            SUPPORTED_ARC_TYPES.contains(&self.arc_type.s.as_str()),
+            "Unsupported arc type {:?} for binary {} FST, supported arc types : {:?}",
+            self.arc_type.s,
+            fst_type,
+            SUPPORTED_ARC_TYPES
+        );
+        ensure!(
+            supported_versions.contains(&self.version),
+            "Unsupported version {} for binary {} FST, supported versions : {:?}",
+            self.version,
+            fst_type,
+            supported_versions
+        );
+        Ok(())
+    }
+
+    /// FstProperties stored in the header.
+    pub(crate) fn fst_properties(&self) -> FstProperties {
+        FstProperties::from_bits_truncate((self.properties >> FST_PROPERTIES_SHIFT) as u32)
+    }
+
     pub(crate) fn write<W: Write>(&self, file: &mut W) -> Fallible<()> {
         //magic_number: i32,
         write_bin_i32(file, self.magic_number)?;
@@ -33,11 +33,11 @@ pub(crate) fn parse_fst_arc<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8],
     let (i, nextstate) = le_i32(i)?;
     Ok((
         i,
-        Arc {
-            ilabel: ilabel as usize,
-            olabel: olabel as usize,
-            weight: W::new(weight),
-            nextstate: nextstate as usize,
-        },
+        Arc::new(
+            ilabel as usize,
+            olabel as usize,
+            W::new(weight),
+            nextstate as usize,
+        ),
     ))
 }
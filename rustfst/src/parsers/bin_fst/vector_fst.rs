@@ -1,6 +1,8 @@
 use std::fs::read;
 use std::fs::File;
 use std::io::BufWriter;
+#[cfg(feature = "flate2")]
+use std::io::Read;
 use std::path::Path;
 
 use failure::{Fallible, ResultExt};
@@ -8,17 +10,27 @@ use nom::multi::count;
 use nom::number::complete::{le_f32, le_i64};
 use nom::IResult;
 
+use crate::algorithms::fst_convert;
 use crate::fst_impls::vector_fst::VectorFstState;
 use crate::fst_impls::VectorFst;
+use crate::fst_properties::FstProperties;
 use crate::fst_traits::{ArcIterator, BinaryDeserializer, BinarySerializer, CoreFst, ExpandedFst};
-use crate::parsers::bin_fst::fst_header::{FstHeader, OpenFstString, FST_MAGIC_NUMBER};
+use crate::parsers::bin_fst::const_fst::parse_const_fst;
+use crate::parsers::bin_fst::fst_header::{
+    header_properties, verify_properties, FstHeader, OpenFstString, FST_EXPANDED, FST_MAGIC_NUMBER,
+    FST_MUTABLE,
+};
 use crate::parsers::bin_fst::utils_parsing::{
     parse_final_weight, parse_fst_arc, parse_start_state,
 };
 use crate::parsers::bin_fst::utils_serialization::{write_bin_f32, write_bin_i32, write_bin_i64};
 use crate::semirings::Semiring;
 
-static VECTOR_MIN_FILE_VERSION: i32 = 2;
+static VECTOR_FILE_VERSION: i32 = 2;
+
+/// Bytes starting a gzip stream.
+#[cfg(feature = "flate2")]
+static GZIP_MAGIC_NUMBER: [u8; 2] = [0x1f, 0x8b];
 
 #[derive(Debug, PartialEq)]
 struct Transition {
@@ -41,31 +53,69 @@ fn parse_fst_state<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8], VectorFs
     ))
 }
 
-fn parse_fst<W: Semiring<Type = f32>>(i: &[u8]) -> IResult<&[u8], VectorFst<W>> {
-    let (i, header) = FstHeader::parse(i, VECTOR_MIN_FILE_VERSION)?;
-    let (i, states) = count(parse_fst_state, header.num_states as usize)(i)?;
-    Ok((
-        i,
-        VectorFst {
-            start_state: parse_start_state(header.start),
-            states,
-        },
-    ))
+/// Parses the states of a VectorFst whose header has already been parsed.
+pub(crate) fn parse_vector_fst<W: Semiring<Type = f32>>(
+    i: &[u8],
+    header: &FstHeader,
+) -> Fallible<VectorFst<W>> {
+    header.check("vector", &[VECTOR_FILE_VERSION])?;
+    let (_, states) = count(parse_fst_state, header.num_states as usize)(i)
+        .map_err(|_| format_err!("Error while parsing binary VectorFst"))?;
+    Ok(VectorFst {
+        start_state: parse_start_state(header.start),
+        states,
+    })
+}
+
+/// Decompresses the content of a file if it starts with the gzip magic bytes, returns it
+/// unchanged otherwise.
+#[cfg(feature = "flate2")]
+fn gunzip_if_compressed(data: Vec<u8>) -> Fallible<Vec<u8>> {
+    if !data.starts_with(&GZIP_MAGIC_NUMBER) {
+        return Ok(data);
+    }
+    let mut decompressed = vec![];
+    flate2::read::GzDecoder::new(data.as_slice()).read_to_end(&mut decompressed)?;
+    Ok(decompressed)
 }
 
 impl<W: Semiring<Type = f32> + 'static> BinaryDeserializer for VectorFst<W> {
     fn read<P: AsRef<Path>>(path_bin_fst: P) -> Fallible<Self> {
+        Self::read_with_properties(path_bin_fst, false).map(|(fst, _)| fst)
+    }
+
+    fn read_with_properties<P: AsRef<Path>>(
+        path_bin_fst: P,
+        verify: bool,
+    ) -> Fallible<(Self, FstProperties)> {
         let data = read(path_bin_fst.as_ref()).with_context(|_| {
             format!(
                 "Can't open VectorFst binary file : {:?}",
                 path_bin_fst.as_ref()
             )
         })?;
+        #[cfg(feature = "flate2")]
+        let data = gunzip_if_compressed(data).with_context(|_| {
+            format!(
+                "Can't decompress VectorFst binary file : {:?}",
+                path_bin_fst.as_ref()
+            )
+        })?;
+
+        let (i, header) = FstHeader::parse(&data)
+            .map_err(|_| format_err!("Error while parsing the header of binary VectorFst"))?;
+        let parsed_fst = match header.fst_type() {
+            "vector" => parse_vector_fst(i, &header)?,
+            "const" => fst_convert(&parse_const_fst::<W>(i, &header, data.len())?),
+            fst_type => bail!("Unsupported FST type {:?} for binary VectorFst", fst_type),
+        };
+        let properties = header.fst_properties();
 
-        let (_, parsed_fst) =
-            parse_fst(&data).map_err(|_| format_err!("Error while parsing binary VectorFst"))?;
+        if verify {
+            verify_properties(&parsed_fst, properties)?;
+        }
 
-        Ok(parsed_fst)
+        Ok((parsed_fst, properties))
     }
 }
 
@@ -82,11 +132,10 @@ impl<W: 'static + Semiring<Type = f32>> BinarySerializer for VectorFst<W> {
             fst_type: OpenFstString::new("vector"),
             // TODO: This should be generated by the weight type
             arc_type: OpenFstString::new("standard"),
-            version: 2i32,
+            version: VECTOR_FILE_VERSION,
             // TODO: Flags are used to check whether or not a symboltable has to be loaded
             flags: 0i32,
-            // TODO: Once the properties are stored, need to read them
-            properties: 3u64,
+            properties: header_properties(FST_EXPANDED | FST_MUTABLE, self.properties()?),
             start: self.start_state.map(|v| v as i64).unwrap_or(-1),
             num_states: self.num_states() as i64,
             num_arcs: num_arcs as i64,
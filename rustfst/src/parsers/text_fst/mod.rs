@@ -1,4 +1,6 @@
 mod nom_parser;
 mod parsed_text_fst;
 
+pub(crate) use self::nom_parser::row_parsed;
+pub(crate) use self::parsed_text_fst::RowParsed;
 pub use self::parsed_text_fst::{FinalState, ParsedTextFst, Transition};
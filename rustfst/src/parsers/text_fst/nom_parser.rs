@@ -36,6 +36,26 @@ fn transition(i: &str) -> IResult<&str, RowParsed> {
     ))
 }
 
+fn acceptor_transition(i: &str) -> IResult<&str, RowParsed> {
+    let (i, state) = num(i)?;
+    let (i, _) = tab(i)?;
+    let (i, nextstate) = num(i)?;
+    let (i, _) = tab(i)?;
+    let (i, label) = num(i)?;
+    let (i, weight) = optional_weight(i)?;
+
+    Ok((
+        i,
+        RowParsed::Transition(Transition {
+            state,
+            ilabel: label,
+            olabel: label,
+            weight,
+            nextstate,
+        }),
+    ))
+}
+
 fn final_state(i: &str) -> IResult<&str, RowParsed> {
     let (i, state) = num(i)?;
     let (i, weight) = optional_weight(i)?;
@@ -49,10 +69,18 @@ fn infinity_final_state(i: &str) -> IResult<&str, RowParsed> {
     Ok((i, RowParsed::InfinityFinalState(state)))
 }
 
-fn row_parsed(i: &str) -> IResult<&str, RowParsed> {
+pub fn row_parsed(i: &str) -> IResult<&str, RowParsed> {
     alt((transition, infinity_final_state, final_state))(i)
 }
 
-pub fn vec_rows_parsed(i: &str) -> IResult<&str, Vec<RowParsed>> {
-    separated_list(tag("\n"), row_parsed)(i)
+pub fn acceptor_row_parsed(i: &str) -> IResult<&str, RowParsed> {
+    alt((acceptor_transition, infinity_final_state, final_state))(i)
+}
+
+pub fn vec_rows_parsed(i: &str, acceptor: bool) -> IResult<&str, Vec<RowParsed>> {
+    if acceptor {
+        separated_list(tag("\n"), acceptor_row_parsed)(i)
+    } else {
+        separated_list(tag("\n"), row_parsed)(i)
+    }
 }
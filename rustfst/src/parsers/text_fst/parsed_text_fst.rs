@@ -97,8 +97,22 @@ impl ParsedTextFst {
     /// 3	0.67
     /// ```
     pub fn from_string(fst_string: &str) -> Fallible<Self> {
-        let (_, vec_rows_parsed) =
-            vec_rows_parsed(fst_string).map_err(|_| format_err!("Error while parsing text fst"))?;
+        Self::from_string_with_format(fst_string, false)
+    }
+
+    /// Same as `from_string` but, when `acceptor` is true, the arcs are read in the acceptor
+    /// format of OpenFST's `fstcompile --acceptor` : `src dest label [weight]`, the label being
+    /// used both as input and output label.
+    ///
+    /// ## Example:
+    /// ```text
+    /// 0	1	32
+    /// 1	2	45	0.25
+    /// 2	0.67
+    /// ```
+    pub fn from_string_with_format(fst_string: &str, acceptor: bool) -> Fallible<Self> {
+        let (_, vec_rows_parsed) = vec_rows_parsed(fst_string, acceptor)
+            .map_err(|_| format_err!("Error while parsing text fst"))?;
 
         Ok(vec_rows_parsed.into())
     }
@@ -126,8 +140,17 @@ impl ParsedTextFst {
     /// 3	0.67
     /// ```
     pub fn from_path<P: AsRef<Path>>(path_fst_text: P) -> Fallible<Self> {
+        Self::from_path_with_format(path_fst_text, false)
+    }
+
+    /// Same as `from_path` but the arcs are read in the acceptor format when `acceptor` is true,
+    /// see `from_string_with_format`.
+    pub fn from_path_with_format<P: AsRef<Path>>(
+        path_fst_text: P,
+        acceptor: bool,
+    ) -> Fallible<Self> {
         let fst_string = read_to_string(path_fst_text)?;
-        Self::from_string(&fst_string)
+        Self::from_string_with_format(&fst_string, acceptor)
     }
 
     pub fn start(&self) -> Option<StateId> {
@@ -257,4 +280,28 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_parse_text_fst_acceptor() -> Fallible<()> {
+        let parsed_fst =
+            ParsedTextFst::from_string_with_format("0\t1\t12\n1\t2\t25\t0.3\n2\t0.5\n", true)?;
+
+        let mut transitions = vec![];
+        transitions.push(Transition::new(0, 12, 12, None, 1));
+        transitions.push(Transition::new(1, 25, 25, Some(0.3), 2));
+
+        let mut final_states = vec![];
+        final_states.push(FinalState::new(2, Some(0.5)));
+
+        let parsed_fst_ref = ParsedTextFst {
+            start_state: Some(0),
+            transitions,
+            final_states,
+        };
+
+        assert_eq!(parsed_fst, parsed_fst_ref);
+        assert!(parsed_fst.transitions.iter().all(|t| t.ilabel == t.olabel));
+
+        Ok(())
+    }
 }
@@ -11,6 +11,8 @@ static MAX_NUM_STATES: usize = 100;
 static MAX_ILABEL: usize = 100;
 static MAX_OLABEL: usize = 100;
 static MAX_NUM_ARCS: usize = 500;
+static MAX_NUM_STATES_ACYCLIC: usize = 10;
+static MAX_NUM_ARCS_ACYCLIC: usize = 15;
 
 fn proptest_weight() -> impl Strategy<Value = Option<TropicalWeight>> {
     prop_oneof![
@@ -19,7 +21,10 @@ fn proptest_weight() -> impl Strategy<Value = Option<TropicalWeight>> {
     ]
 }
 
-fn proptest_arcs(nstates: usize) -> impl Strategy<Value = Vec<(usize, Arc<TropicalWeight>)>> {
+fn proptest_arcs(
+    nstates: usize,
+    max_num_arcs: usize,
+) -> impl Strategy<Value = Vec<(usize, Arc<TropicalWeight>)>> {
     proptest::collection::vec(
         (
             0..nstates,
@@ -29,19 +34,19 @@ fn proptest_arcs(nstates: usize) -> impl Strategy<Value = Vec<(usize, Arc<Tropic
             0..nstates,
         ),
         // Number of arcs
-        0..MAX_NUM_ARCS,
+        0..max_num_arcs,
     )
     .prop_map(|v| {
         v.into_iter()
             .map(|(state, ilabel, olabel, weight, nextstate)| {
                 (
                     state,
-                    Arc {
+                    Arc::new(
                         ilabel,
                         olabel,
-                        weight: weight.unwrap_or_else(TropicalWeight::one),
+                        weight.unwrap_or_else(TropicalWeight::one),
                         nextstate,
-                    },
+                    ),
                 )
             })
             .collect()
@@ -58,7 +63,7 @@ pub(crate) fn proptest_fst() -> impl Strategy<Value = VectorFst<TropicalWeight>>
                 // Start state.
                 (0..nstates),
                 // List of states : Vec<State, Arc>.
-                proptest_arcs(nstates),
+                proptest_arcs(nstates, MAX_NUM_ARCS),
                 // List of final weight.
                 proptest::collection::vec(proptest_weight(), nstates..=nstates),
             )
@@ -87,3 +92,40 @@ pub(crate) fn proptest_fst() -> impl Strategy<Value = VectorFst<TropicalWeight>>
             fst
         })
 }
+
+/// Small acyclic FSTs whose start state is the state 0, all the arcs going from a state to a
+/// state with a greater id, so that their paths can be enumerated.
+pub(crate) fn proptest_acyclic_fst() -> impl Strategy<Value = VectorFst<TropicalWeight>> {
+    let nstates_strategy = 1..MAX_NUM_STATES_ACYCLIC;
+    nstates_strategy
+        .prop_flat_map(|nstates| {
+            (
+                // Number of states.
+                Just(nstates),
+                // List of states : Vec<State, Arc>.
+                proptest_arcs(nstates, MAX_NUM_ARCS_ACYCLIC),
+                // List of final weight.
+                proptest::collection::vec(proptest_weight(), nstates..=nstates),
+            )
+        })
+        .prop_map(|(nstates, arcs, final_weights)| {
+            let mut fst = VectorFst::new();
+            fst.add_states(nstates);
+            fst.set_start(0).unwrap();
+
+            // Only keep the arcs going forward.
+            for (state, arc) in arcs.into_iter() {
+                if state < arc.nextstate {
+                    unsafe { fst.add_arc_unchecked(state, arc) };
+                }
+            }
+
+            for (idx, final_weight) in final_weights.into_iter().enumerate() {
+                if let Some(final_weight) = final_weight {
+                    unsafe { fst.set_final_unchecked(idx, final_weight) };
+                }
+            }
+
+            fst
+        })
+}
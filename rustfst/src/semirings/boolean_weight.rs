@@ -1,6 +1,8 @@
 use failure::Fallible;
 
-use crate::semirings::{CompleteSemiring, Semiring, SemiringProperties, StarSemiring};
+use crate::semirings::{
+    CompleteSemiring, PathSemiring, Semiring, SemiringProperties, StarSemiring,
+};
 
 /// Boolean semiring: (&, |, false, true).
 #[derive(Clone, Debug, PartialEq, PartialOrd, Default, Eq, Copy, Hash)]
@@ -73,6 +75,16 @@ impl StarSemiring for BooleanWeight {
     }
 }
 
+impl PathSemiring for BooleanWeight {
+    fn path_plus(&self, rhs: &Self) -> (Self, bool) {
+        if rhs.value && !self.value {
+            (*rhs, false)
+        } else {
+            (*self, true)
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -95,4 +107,26 @@ mod tests {
         assert_eq!(b_false.times(&b_false)?, b_false);
         Ok(())
     }
+
+    #[test]
+    fn test_boolean_weight_approx_equal() {
+        let b_true = BooleanWeight::new(true);
+        let b_false = BooleanWeight::new(false);
+
+        assert!(b_true.approx_equal(&b_true, 1.0));
+        assert!(!b_true.approx_equal(&b_false, 1.0));
+    }
+
+    #[test]
+    fn test_boolean_weight_path_plus() -> Fallible<()> {
+        let b_true = BooleanWeight::new(true);
+        let b_false = BooleanWeight::new(false);
+
+        assert_eq!(b_true.path_plus(&b_false), (b_true, true));
+        assert_eq!(b_false.path_plus(&b_true), (b_true, false));
+        assert_eq!(b_true.path_plus(&b_true), (b_true, true));
+        assert_eq!(b_false.path_plus(&b_false), (b_false, true));
+        assert_eq!(b_false.path_plus(&b_true).0, b_false.plus(&b_true)?);
+        Ok(())
+    }
 }
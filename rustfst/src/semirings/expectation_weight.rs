@@ -0,0 +1,145 @@
+use std::fmt;
+use std::fmt::Debug;
+
+use failure::Fallible;
+
+use crate::semirings::{Semiring, SemiringProperties};
+
+/// Expectation semiring over a commutative semiring `W`: the weights are pairs `(a, b)` where
+/// `a` is typically the probability of a path and `b` the probability times a value, e.g. the
+/// length, accumulated along the path.
+///
+/// `(a1, b1) ⊕ (a2, b2) = (a1 ⊕ a2, b1 ⊕ b2)` and
+/// `(a1, b1) ⊗ (a2, b2) = (a1 ⊗ a2, a1 ⊗ b2 ⊕ b1 ⊗ a2)`, so that the ⊕-sum of the weights of a
+/// set of paths holds their total probability and the expectation of the value, not
+/// normalized. For more information : `https://www.aclweb.org/anthology/J08-3004`
+#[derive(Debug, Eq, PartialOrd, PartialEq, Clone, Default, Hash)]
+pub struct ExpectationWeight<W: Semiring> {
+    pub(crate) weight: (W, W),
+}
+
+impl<W: Semiring> fmt::Display for ExpectationWeight<W> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        (&self.value1(), &self.value2()).fmt(f)
+    }
+}
+
+impl<W: Semiring> AsRef<Self> for ExpectationWeight<W> {
+    fn as_ref(&self) -> &ExpectationWeight<W> {
+        &self
+    }
+}
+
+impl<W: Semiring> Semiring for ExpectationWeight<W> {
+    type Type = (W, W);
+    type ReverseWeight = ExpectationWeight<W::ReverseWeight>;
+
+    fn zero() -> Self {
+        Self {
+            weight: (W::zero(), W::zero()),
+        }
+    }
+
+    fn one() -> Self {
+        Self {
+            weight: (W::one(), W::zero()),
+        }
+    }
+
+    fn new(weight: <Self as Semiring>::Type) -> Self {
+        Self { weight }
+    }
+
+    fn plus_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Fallible<()> {
+        self.weight.0.plus_assign(&rhs.as_ref().weight.0)?;
+        self.weight.1.plus_assign(&rhs.as_ref().weight.1)?;
+        Ok(())
+    }
+
+    fn times_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Fallible<()> {
+        let (a2, b2) = &rhs.as_ref().weight;
+        let mut b = self.weight.0.times(b2)?;
+        b.plus_assign(self.weight.1.times(a2)?)?;
+        self.weight.0.times_assign(a2)?;
+        self.weight.1 = b;
+        Ok(())
+    }
+
+    fn value(&self) -> &<Self as Semiring>::Type {
+        &self.weight
+    }
+
+    fn take_value(self) -> <Self as Semiring>::Type {
+        self.weight
+    }
+
+    fn set_value(&mut self, value: <Self as Semiring>::Type) {
+        self.weight = value;
+    }
+
+    fn reverse(&self) -> Fallible<Self::ReverseWeight> {
+        Ok(ExpectationWeight::new((
+            self.value1().reverse()?,
+            self.value2().reverse()?,
+        )))
+    }
+
+    fn approx_equal(&self, other: &Self, delta: f32) -> bool {
+        self.value1().approx_equal(other.value1(), delta)
+            && self.value2().approx_equal(other.value2(), delta)
+    }
+
+    fn properties() -> SemiringProperties {
+        if W::properties().contains(SemiringProperties::COMMUTATIVE) {
+            SemiringProperties::LEFT_SEMIRING
+                | SemiringProperties::RIGHT_SEMIRING
+                | SemiringProperties::COMMUTATIVE
+        } else {
+            SemiringProperties::empty()
+        }
+    }
+}
+
+impl<W: Semiring> ExpectationWeight<W> {
+    pub fn value1(&self) -> &W {
+        &self.weight.0
+    }
+
+    pub fn value2(&self) -> &W {
+        &self.weight.1
+    }
+}
+
+impl<W: Semiring> From<(W, W)> for ExpectationWeight<W> {
+    fn from(t: (W, W)) -> Self {
+        Self::new(t)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::semirings::ProbabilityWeight;
+
+    fn expectation_weight(a: f32, b: f32) -> ExpectationWeight<ProbabilityWeight> {
+        ExpectationWeight::new((ProbabilityWeight::new(a), ProbabilityWeight::new(b)))
+    }
+
+    #[test]
+    fn test_expectation_weight_operations() -> Fallible<()> {
+        let w1 = expectation_weight(0.5, 1.5);
+        let w2 = expectation_weight(0.25, 2.0);
+
+        assert_eq!(w1.plus(&w2)?, expectation_weight(0.75, 3.5));
+        assert_eq!(
+            w1.times(&w2)?,
+            expectation_weight(0.125, 0.5 * 2.0 + 1.5 * 0.25)
+        );
+        assert_eq!(w1.times(&ExpectationWeight::one())?, w1);
+        assert!(w1.times(&ExpectationWeight::zero())?.is_zero());
+        Ok(())
+    }
+}
@@ -76,3 +76,17 @@ impl StarSemiring for IntegerWeight {
         Self::new(i32::max_value())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_integer_weight_power() -> Fallible<()> {
+        let w = IntegerWeight::new(3);
+
+        assert_eq!(w.power(5)?, IntegerWeight::new(243));
+        assert_eq!(w.power(0)?, IntegerWeight::one());
+        Ok(())
+    }
+}
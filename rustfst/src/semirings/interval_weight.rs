@@ -0,0 +1,69 @@
+use crate::semirings::{ProductWeight, Semiring, TropicalWeight};
+
+/// Pair of tropical costs bounding the cost of a path, e.g. when the cost of some arcs is only
+/// known to lie in an interval. `times` adds the bounds componentwise and `plus` keeps the lowest
+/// lower bound and the lowest upper bound : the ⊕-sum over a set of paths bounds the cost of the
+/// best of them.
+pub type IntervalWeight = ProductWeight<TropicalWeight, TropicalWeight>;
+
+impl IntervalWeight {
+    /// Builds a weight from a lower bound and an upper bound on a cost.
+    pub fn from_bounds(lower: f32, upper: f32) -> Self {
+        Self::new((TropicalWeight::new(lower), TropicalWeight::new(upper)))
+    }
+
+    /// Returns the lower bound.
+    pub fn lower(&self) -> f32 {
+        *self.value1().value()
+    }
+
+    /// Returns the upper bound.
+    pub fn upper(&self) -> f32 {
+        *self.value2().value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::algorithms::shortest_distance;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::Arc;
+
+    #[test]
+    fn test_interval_weight_operations() -> Fallible<()> {
+        let w1 = IntervalWeight::from_bounds(1.0, 4.0);
+        let w2 = IntervalWeight::from_bounds(2.0, 3.0);
+
+        assert_eq!(w1.plus(&w2)?, IntervalWeight::from_bounds(1.0, 3.0));
+        assert_eq!(w1.times(&w2)?, IntervalWeight::from_bounds(3.0, 7.0));
+        assert_eq!(w1.plus(&IntervalWeight::zero())?, w1);
+        assert_eq!(w1.times(&IntervalWeight::one())?, w1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_interval_weight_shortest_distance() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(4);
+        fst.set_start(0)?;
+        fst.set_final(3, IntervalWeight::one())?;
+        fst.add_arc(0, Arc::new(1, 1, IntervalWeight::from_bounds(1.0, 5.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 2, IntervalWeight::from_bounds(2.0, 3.0), 2))?;
+        fst.add_arc(1, Arc::new(3, 3, IntervalWeight::from_bounds(0.5, 1.0), 3))?;
+        fst.add_arc(2, Arc::new(4, 4, IntervalWeight::from_bounds(1.0, 2.0), 3))?;
+
+        let distances = shortest_distance(&fst, false)?;
+
+        assert_eq!(distances[1], IntervalWeight::from_bounds(1.0, 5.0));
+        assert_eq!(distances[2], IntervalWeight::from_bounds(2.0, 3.0));
+        // Lower bound through state 1, upper bound through state 2
+        assert_eq!(distances[3].lower(), 1.5);
+        assert_eq!(distances[3].upper(), 5.0);
+        Ok(())
+    }
+}
@@ -69,6 +69,16 @@ impl Semiring for LogWeight {
         Ok(())
     }
 
+    fn power(&self, n: usize) -> Fallible<Self> {
+        if n == 0 {
+            Ok(Self::one())
+        } else if self.is_zero() {
+            Ok(Self::zero())
+        } else {
+            Ok(Self::new(self.value() * n as f32))
+        }
+    }
+
     fn value(&self) -> &Self::Type {
         self.value.as_ref()
     }
@@ -85,6 +95,8 @@ impl Semiring for LogWeight {
         Ok(*self)
     }
 
+    approx_equal_f32!();
+
     fn properties() -> SemiringProperties {
         SemiringProperties::LEFT_SEMIRING
             | SemiringProperties::RIGHT_SEMIRING
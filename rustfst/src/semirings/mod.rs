@@ -1,32 +1,38 @@
 #[macro_use]
 mod semiring;
 mod boolean_weight;
+mod expectation_weight;
 mod gallic_weight;
 mod integer_weight;
+mod interval_weight;
 mod log_weight;
 mod power_weight;
 mod probability_weight;
 mod product_weight;
 mod string_variant;
 mod string_weight;
+mod string_with_cost_weight;
 mod tropical_weight;
 mod union_weight;
 
 pub use self::boolean_weight::BooleanWeight;
+pub use self::expectation_weight::ExpectationWeight;
 pub use self::gallic_weight::{
     GallicWeight, GallicWeightLeft, GallicWeightMin, GallicWeightRestrict, GallicWeightRight,
 };
 pub use self::integer_weight::IntegerWeight;
+pub use self::interval_weight::IntervalWeight;
 pub use self::log_weight::LogWeight;
 pub use self::probability_weight::ProbabilityWeight;
 pub use self::product_weight::ProductWeight;
 pub use self::semiring::{
-    CompleteSemiring, DivideType, Semiring, SemiringProperties, StarSemiring,
+    CompleteSemiring, DivideType, PathSemiring, Semiring, SemiringProperties, StarSemiring,
     WeaklyDivisibleSemiring, WeightQuantize,
 };
 pub(crate) use self::string_variant::StringWeightVariant;
 pub use self::string_weight::{
     StringType, StringWeightLeft, StringWeightRestrict, StringWeightRight,
 };
+pub use self::string_with_cost_weight::StringWithCostWeight;
 pub use self::tropical_weight::TropicalWeight;
 pub use self::union_weight::{UnionWeight, UnionWeightOption};
@@ -64,6 +64,8 @@ impl Semiring for ProbabilityWeight {
         Ok(*self)
     }
 
+    approx_equal_f32!();
+
     fn properties() -> SemiringProperties {
         SemiringProperties::LEFT_SEMIRING
             | SemiringProperties::RIGHT_SEMIRING
@@ -90,6 +90,11 @@ where
         Ok((self.value1().reverse()?, self.value2().reverse()?).into())
     }
 
+    fn approx_equal(&self, other: &Self, delta: f32) -> bool {
+        self.value1().approx_equal(other.value1(), delta)
+            && self.value2().approx_equal(other.value2(), delta)
+    }
+
     fn properties() -> SemiringProperties {
         W1::properties()
             & W2::properties()
@@ -54,6 +54,24 @@ pub trait Semiring:
     }
     fn times_assign<P: AsRef<Self>>(&mut self, rhs: P) -> Fallible<()>;
 
+    /// Computes `self` times itself `n` times, `one()` for `n = 0`, with `O(log n)` calls to
+    /// `times` by exponentiation by squaring.
+    fn power(&self, n: usize) -> Fallible<Self> {
+        let mut result = Self::one();
+        let mut base = self.clone();
+        let mut n = n;
+        while n > 0 {
+            if n % 2 == 1 {
+                result.times_assign(&base)?;
+            }
+            n /= 2;
+            if n > 0 {
+                base = base.times(&base)?;
+            }
+        }
+        Ok(result)
+    }
+
     /// Borrow underneath value.
     fn value(&self) -> &Self::Type;
     /// Move underneath value.
@@ -67,6 +85,12 @@ pub trait Semiring:
     }
     fn reverse(&self) -> Fallible<Self::ReverseWeight>;
 
+    /// Returns whether two weights are equal up to `delta`. Exact by default, the semirings
+    /// over floats override it to compare the weights once quantized with `delta`.
+    fn approx_equal(&self, other: &Self, _delta: f32) -> bool {
+        self == other
+    }
+
     fn properties() -> SemiringProperties;
 }
 
@@ -112,6 +136,15 @@ pub trait StarSemiring: Semiring {
     fn closure(&self) -> Self;
 }
 
+/// Semiring having the path property : `⊕` always selects one of its operands, as the `min` of
+/// the tropical semiring does. The algorithms looking for the best path can then keep track of the
+/// path a weight comes from.
+pub trait PathSemiring: Semiring {
+    /// Returns `self ⊕ rhs` along with whether `self` is the selected operand. `self` is
+    /// selected when both operands are equal.
+    fn path_plus(&self, rhs: &Self) -> (Self, bool);
+}
+
 pub trait WeightQuantize: Semiring {
     fn quantize_assign(&mut self, delta: f32) -> Fallible<()>;
     fn quantize(&self, delta: f32) -> Fallible<Self> {
@@ -136,6 +169,17 @@ macro_rules! impl_quantize_f32 {
     };
 }
 
+macro_rules! approx_equal_f32 {
+    () => {
+        fn approx_equal(&self, other: &Self, delta: f32) -> bool {
+            match (self.quantize(delta), other.quantize(delta)) {
+                (Ok(w1), Ok(w2)) => w1.value() == w2.value(),
+                _ => false,
+            }
+        }
+    };
+}
+
 macro_rules! display_semiring {
     ($semiring:tt) => {
         use std::fmt;
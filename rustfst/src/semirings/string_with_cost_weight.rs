@@ -0,0 +1,70 @@
+use crate::semirings::string_variant::StringWeightVariant;
+use crate::semirings::{ProductWeight, Semiring, StringWeightRestrict, TropicalWeight};
+use crate::Label;
+
+/// Product of an output string and a tropical cost, e.g. the weight of a G2P lattice whose
+/// arcs carry a phoneme sequence along with its cost. `times` concatenates the strings and adds
+/// the costs while `plus` keeps the lowest cost, the strings having to be equal.
+pub type StringWithCostWeight = ProductWeight<StringWeightRestrict, TropicalWeight>;
+
+impl StringWithCostWeight {
+    /// Builds a weight from an output string and a cost.
+    pub fn from_labels(labels: Vec<Label>, cost: f32) -> Self {
+        Self::new((labels.into(), TropicalWeight::new(cost)))
+    }
+
+    /// Builds a weight from a single output label and a cost.
+    pub fn from_label(label: Label, cost: f32) -> Self {
+        Self::new((label.into(), TropicalWeight::new(cost)))
+    }
+
+    /// Returns the output string, `None` being returned for the `zero()` string.
+    pub fn labels(&self) -> Option<&[Label]> {
+        match &self.value1().value {
+            StringWeightVariant::Infinity => None,
+            StringWeightVariant::Labels(l) => Some(l.as_slice()),
+        }
+    }
+
+    /// Returns the cost.
+    pub fn cost(&self) -> f32 {
+        *self.value2().value()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    #[test]
+    fn test_string_with_cost_weight_times() -> Fallible<()> {
+        let w1 = StringWithCostWeight::from_labels(vec![1, 2], 1.5);
+        let w2 = StringWithCostWeight::from_label(3, 2.0);
+
+        let w = w1.times(&w2)?;
+        assert_eq!(w.labels(), Some(&[1, 2, 3][..]));
+        assert_eq!(w.cost(), 3.5);
+        assert_eq!(w1.times(&StringWithCostWeight::one())?, w1);
+        assert!(w1.times(&StringWithCostWeight::zero())?.is_zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_string_with_cost_weight_plus() -> Fallible<()> {
+        let w1 = StringWithCostWeight::from_labels(vec![1, 2], 1.5);
+        let w2 = StringWithCostWeight::from_labels(vec![1, 2], 0.5);
+
+        let w = w1.plus(&w2)?;
+        assert_eq!(w.labels(), Some(&[1, 2][..]));
+        assert_eq!(w.cost(), 0.5);
+        assert_eq!(w1.plus(&StringWithCostWeight::zero())?, w1);
+        assert_eq!(StringWithCostWeight::zero().labels(), None);
+
+        // The restricted string semiring only sums equal strings
+        let w3 = StringWithCostWeight::from_labels(vec![1, 3], 0.5);
+        assert!(w1.plus(&w3).is_err());
+        Ok(())
+    }
+}
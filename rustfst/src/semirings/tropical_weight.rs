@@ -4,7 +4,7 @@ use std::hash::{Hash, Hasher};
 use failure::Fallible;
 
 use crate::semirings::{
-    CompleteSemiring, DivideType, Semiring, SemiringProperties, StarSemiring,
+    CompleteSemiring, DivideType, PathSemiring, Semiring, SemiringProperties, StarSemiring,
     WeaklyDivisibleSemiring, WeightQuantize,
 };
 use crate::KDELTA;
@@ -58,6 +58,16 @@ impl Semiring for TropicalWeight {
         Ok(())
     }
 
+    fn power(&self, n: usize) -> Fallible<Self> {
+        if n == 0 {
+            Ok(Self::one())
+        } else if self.is_zero() {
+            Ok(Self::zero())
+        } else {
+            Ok(Self::new(self.value() * n as f32))
+        }
+    }
+
     fn value(&self) -> &Self::Type {
         &self.value.0
     }
@@ -74,6 +84,8 @@ impl Semiring for TropicalWeight {
         Ok(*self)
     }
 
+    approx_equal_f32!();
+
     fn properties() -> SemiringProperties {
         SemiringProperties::LEFT_SEMIRING
             | SemiringProperties::RIGHT_SEMIRING
@@ -103,6 +115,16 @@ impl StarSemiring for TropicalWeight {
     }
 }
 
+impl PathSemiring for TropicalWeight {
+    fn path_plus(&self, rhs: &Self) -> (Self, bool) {
+        if rhs.value < self.value {
+            (*rhs, false)
+        } else {
+            (*self, true)
+        }
+    }
+}
+
 impl WeaklyDivisibleSemiring for TropicalWeight {
     fn divide_assign(&mut self, rhs: &Self, _divide_type: DivideType) -> Fallible<()> {
         self.value.0 -= rhs.value.0;
@@ -113,3 +135,42 @@ impl WeaklyDivisibleSemiring for TropicalWeight {
 impl_quantize_f32!(TropicalWeight);
 
 partial_eq_and_hash_f32!(TropicalWeight);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tropical_weight_approx_equal() {
+        let w = TropicalWeight::new(1.0);
+
+        assert!(w.approx_equal(&TropicalWeight::new(1.04), 0.1));
+        assert!(!w.approx_equal(&TropicalWeight::new(1.2), 0.1));
+        assert!(!w.approx_equal(&TropicalWeight::new(1.04), 0.01));
+        assert!(TropicalWeight::zero().approx_equal(&TropicalWeight::zero(), 0.1));
+        assert!(!w.approx_equal(&TropicalWeight::zero(), 0.1));
+    }
+
+    #[test]
+    fn test_tropical_weight_power() -> Fallible<()> {
+        let w = TropicalWeight::new(1.5);
+
+        assert_eq!(w.power(3)?, w.times(&w)?.times(&w)?);
+        assert_eq!(w.power(0)?, TropicalWeight::one());
+        assert_eq!(TropicalWeight::zero().power(2)?, TropicalWeight::zero());
+        Ok(())
+    }
+
+    #[test]
+    fn test_tropical_weight_path_plus() -> Fallible<()> {
+        let w1 = TropicalWeight::new(1.0);
+        let w2 = TropicalWeight::new(2.0);
+
+        assert_eq!(w1.path_plus(&w2), (w1, true));
+        assert_eq!(w2.path_plus(&w1), (w1, false));
+        assert_eq!(w2.path_plus(&w2), (w2, true));
+        assert_eq!(w2.path_plus(&TropicalWeight::zero()), (w2, true));
+        assert_eq!(w1.path_plus(&w2).0, w1.plus(&w2)?);
+        Ok(())
+    }
+}
@@ -2,7 +2,7 @@ use failure::{format_err, Fallible};
 
 use serde_derive::{Deserialize, Serialize};
 
-use crate::algorithms::{determinize, isomorphic, DeterminizeType};
+use crate::algorithms::{determinize, determinize_with_delta, isomorphic, DeterminizeType};
 use crate::fst_properties::FstProperties;
 use crate::fst_traits::MutableFst;
 use crate::fst_traits::TextParser;
@@ -11,6 +11,7 @@ use crate::semirings::WeaklyDivisibleSemiring;
 use crate::semirings::WeightQuantize;
 
 use crate::tests_openfst::FstTestData;
+use crate::KDELTA;
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct DeterminizeOperationResult {
@@ -65,6 +66,10 @@ where
                         .properties()?
                         .contains(FstProperties::I_DETERMINISTIC));
                 }
+                let fst_determinized_delta: F =
+                    determinize_with_delta(&fst_raw, determinize_data.det_type.clone(), KDELTA)?;
+                assert_eq!(fst_determinized, &fst_determinized_delta);
+
                 let a = isomorphic(fst_expected, fst_determinized)?;
                 assert!(
                     a,
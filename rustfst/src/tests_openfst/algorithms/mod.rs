@@ -8,6 +8,7 @@ pub mod factor_weight_identity;
 pub mod gallic_encode_decode;
 pub mod inverse;
 pub mod minimize;
+pub mod print;
 pub mod project;
 pub mod properties;
 pub mod push;
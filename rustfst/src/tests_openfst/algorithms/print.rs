@@ -0,0 +1,21 @@
+use failure::Fallible;
+
+use crate::algorithms::fstprint;
+use crate::fst_traits::{ExpandedFst, TextParser};
+use crate::semirings::Semiring;
+
+use crate::tests_openfst::FstTestData;
+
+pub fn test_fstprint<F>(test_data: &FstTestData<F>) -> Fallible<()>
+where
+    F: TextParser + ExpandedFst,
+    F::W: Semiring<Type = f32>,
+{
+    let fstprint_output = fstprint(&test_data.raw, None, None)?;
+    assert_eq!(
+        fstprint_output, test_data.fstprint,
+        "Test failing for fstprint on {:?}",
+        test_data.name
+    );
+    Ok(())
+}
@@ -20,6 +20,19 @@ where
         "{}",
         error_message_fst!(raw_const, parsed_fst_bin, "Deserializer ConstFst Bin")
     );
+
+    let parsed_vector_fst_bin = VectorFst::<W>::read(&test_data.raw_const_bin_path)
+        .with_context(|_| format_err!("Failed parsing ConstFst Bin as VectorFst"))?;
+    assert_eq!(
+        test_data.raw,
+        parsed_vector_fst_bin,
+        "{}",
+        error_message_fst!(
+            test_data.raw,
+            parsed_vector_fst_bin,
+            "Deserializer ConstFst Bin as VectorFst"
+        )
+    );
     Ok(())
 }
 
@@ -43,5 +56,18 @@ where
             "Deserializer ConstFst Aligned Bin"
         )
     );
+
+    let parsed_vector_fst_bin = VectorFst::<W>::read(&test_data.raw_const_aligned_bin_path)
+        .with_context(|_| format_err!("Failed parsing ConstFst Aligned Bin as VectorFst"))?;
+    assert_eq!(
+        test_data.raw,
+        parsed_vector_fst_bin,
+        "{}",
+        error_message_fst!(
+            test_data.raw,
+            parsed_vector_fst_bin,
+            "Deserializer ConstFst Aligned Bin as VectorFst"
+        )
+    );
     Ok(())
 }
@@ -45,6 +45,7 @@ use self::algorithms::{
     encode::{test_encode, test_encode_decode, EncodeOperationResult, EncodeTestData},
     inverse::test_invert,
     minimize::{test_minimize, MinimizeOperationResult, MinimizeTestData},
+    print::test_fstprint,
     project::{test_project_input, test_project_output},
     properties::{parse_fst_properties, test_fst_properties},
     push::{test_push, PushOperationResult, PushTestData},
@@ -126,6 +127,7 @@ pub struct ParsedFstTestData {
     factor_weight_identity: Vec<FwIdentityOperationResult>,
     factor_weight_gallic: Vec<FwGallicOperationResult>,
     push: Vec<PushOperationResult>,
+    fstprint: FstOperationResult,
 }
 
 pub struct FstTestData<F>
@@ -171,6 +173,7 @@ where
     pub factor_weight_identity: Vec<FwIdentityTestData<F>>,
     pub factor_weight_gallic: Vec<FwGallicTestData<F>>,
     pub push: Vec<PushTestData<F>>,
+    pub fstprint: String,
 }
 
 impl<F> FstTestData<F>
@@ -235,6 +238,7 @@ where
                 .map(|v| v.parse())
                 .collect(),
             push: data.push.iter().map(|v| v.parse()).collect(),
+            fstprint: data.fstprint.result.clone(),
         }
     }
 }
@@ -346,6 +350,8 @@ where
 
     test_push(&test_data)?;
 
+    test_fstprint(&test_data)?;
+
     test_const_fst_convert_convert(&test_data)?;
 
     test_vector_fst_text_serialization(&test_data)?;
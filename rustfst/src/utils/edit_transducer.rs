@@ -0,0 +1,98 @@
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::MutableFst;
+use crate::semirings::{Semiring, TropicalWeight};
+use crate::{Label, EPS_LABEL};
+
+/// Builds the one-state Levenshtein transducer over `alphabet`. Each label is mapped to itself
+/// at no cost, to any other label of the alphabet with cost `sub_cost` and to epsilon with cost
+/// `del_cost`. Epsilon is mapped to any label with cost `ins_cost`.
+///
+/// Composing a string with this transducer and then with another string gives all the
+/// alignments between the two strings, the shortest distance being their edit distance.
+///
+/// # Example
+///
+/// ```
+/// # use rustfst::fst_traits::{ArcIterator, CoreFst, ExpandedFst};
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::edit_transducer;
+/// # use rustfst::Arc;
+/// let fst = edit_transducer(&[1, 2], 1.0, 2.0, 3.0);
+///
+/// assert_eq!(fst.num_states(), 1);
+/// assert_eq!(fst.num_arcs(0).unwrap(), 8);
+/// assert!(fst
+///     .arcs_iter(0)
+///     .unwrap()
+///     .any(|arc| *arc == Arc::new(0, 2, TropicalWeight::new(2.0), 0)));
+/// ```
+pub fn edit_transducer(
+    alphabet: &[Label],
+    sub_cost: f32,
+    ins_cost: f32,
+    del_cost: f32,
+) -> VectorFst<TropicalWeight> {
+    let mut fst = VectorFst::new();
+    let s = fst.add_state();
+
+    // Can't fail as the state has just been added
+    fst.set_start(s).unwrap();
+    fst.set_final(s, TropicalWeight::one()).unwrap();
+
+    for &ilabel in alphabet {
+        for &olabel in alphabet {
+            let cost = if ilabel == olabel { 0.0 } else { sub_cost };
+            fst.add_arc(s, Arc::new(ilabel, olabel, TropicalWeight::new(cost), s))
+                .unwrap();
+        }
+        fst.add_arc(
+            s,
+            Arc::new(ilabel, EPS_LABEL, TropicalWeight::new(del_cost), s),
+        )
+        .unwrap();
+        fst.add_arc(
+            s,
+            Arc::new(EPS_LABEL, ilabel, TropicalWeight::new(ins_cost), s),
+        )
+        .unwrap();
+    }
+
+    fst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::algorithms::{compose, shortest_distance};
+    use crate::fst_traits::CoreFst;
+    use crate::utils::acceptor;
+
+    fn alignment_cost(ilabel: Label, olabel: Label) -> Fallible<TropicalWeight> {
+        let edit_fst = edit_transducer(&[1, 2, 3], 1.5, 2.0, 3.0);
+        let input_fst: VectorFst<TropicalWeight> = acceptor(&[ilabel], TropicalWeight::one());
+        let output_fst: VectorFst<TropicalWeight> = acceptor(&[olabel], TropicalWeight::one());
+
+        let fst: VectorFst<_> = compose(&input_fst, &edit_fst)?;
+        let fst: VectorFst<_> = compose(&fst, &output_fst)?;
+        let dists = shortest_distance(&fst, false)?;
+
+        let mut cost = TropicalWeight::zero();
+        for (s, d) in dists.iter().enumerate() {
+            if let Some(final_weight) = fst.final_weight(s)? {
+                cost.plus_assign(d.times(final_weight)?)?;
+            }
+        }
+        Ok(cost)
+    }
+
+    #[test]
+    fn test_edit_transducer() -> Fallible<()> {
+        assert_eq!(alignment_cost(2, 2)?, TropicalWeight::one());
+        assert_eq!(alignment_cost(1, 3)?, TropicalWeight::new(1.5));
+        Ok(())
+    }
+}
@@ -34,6 +34,30 @@ pub fn decode_linear_fst<F: Fst>(fst: &F) -> Fallible<FstPath<F::W>> {
     Ok(path)
 }
 
+/// Generalization of `decode_linear_fst` to FSTs having a few paths : returns all the paths
+/// recognized by the FST, in the order in which `paths_iter` yields them, as long as there are at
+/// most `n` of them. An error is returned otherwise, without enumerating more than `n + 1`
+/// paths.
+///
+/// # Example
+///
+/// ```
+/// # use rustfst::fst_traits::{CoreFst, MutableFst, ExpandedFst};
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{BooleanWeight, Semiring};
+/// # use rustfst::utils::{acceptor, decode_nbest_linear};
+/// let fst : VectorFst<BooleanWeight> = acceptor(&[1, 2], BooleanWeight::one());
+///
+/// assert_eq!(decode_nbest_linear(&fst, 2).unwrap().len(), 1);
+/// ```
+pub fn decode_nbest_linear<F: Fst>(fst: &F, n: usize) -> Fallible<Vec<FstPath<F::W>>> {
+    let paths: Vec<_> = fst.paths_iter().take(n.saturating_add(1)).collect();
+    if paths.len() > n {
+        bail!("The FST has more than {} paths", n)
+    }
+    Ok(paths)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -106,4 +130,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_decode_nbest_linear() -> Fallible<()> {
+        let mut fst = VectorFst::<BooleanWeight>::new();
+        let s1 = fst.add_state();
+        let s2 = fst.add_state();
+        let s3 = fst.add_state();
+        fst.set_start(s1)?;
+        fst.set_final(s3, BooleanWeight::one())?;
+        fst.add_arc(s1, Arc::new(1, 1, BooleanWeight::one(), s2))?;
+        fst.add_arc(s2, Arc::new(2, 2, BooleanWeight::one(), s3))?;
+        fst.add_arc(s2, Arc::new(3, 3, BooleanWeight::one(), s3))?;
+        fst.add_arc(s2, Arc::new(4, 4, BooleanWeight::one(), s3))?;
+
+        let mut paths = decode_nbest_linear(&fst, 3)?;
+        paths.sort_by_key(|p| p.ilabels.clone());
+        let paths_ref: Vec<_> = vec![2, 3, 4]
+            .into_iter()
+            .map(|l| FstPath::new(vec![1, l], vec![1, l], BooleanWeight::one()))
+            .collect();
+        assert_eq!(paths, paths_ref);
+
+        assert!(decode_nbest_linear(&fst, 2).is_err());
+        Ok(())
+    }
 }
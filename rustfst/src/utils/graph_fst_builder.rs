@@ -0,0 +1,185 @@
+use std::iter::once;
+
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_traits::MutableFst;
+use crate::semirings::Semiring;
+use crate::StateId;
+
+/// Builds an FST of any shape, cycles included, out of states and arcs added in any order.
+///
+/// Contrary to `MutableFst`, the states don't have to exist when they are referenced : an id can
+/// be reserved with `reserve_state`, used as the start state, a final state or the source or
+/// destination of an arc, and only created later with `create_state`. The FST is only checked
+/// by `build`, which fails if there is no start state or if a state that has been referenced
+/// has not been created.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::fst_traits::PathsIterator;
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::GraphFstBuilder;
+/// # use rustfst::Arc;
+/// # fn main() -> Fallible<()> {
+/// let mut builder = GraphFstBuilder::new();
+/// let s0 = builder.add_state();
+/// let s1 = builder.reserve_state();
+/// builder.set_start(s0);
+/// builder.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1));
+/// builder.set_final(s1, TropicalWeight::one());
+/// builder.create_state(s1)?;
+///
+/// let fst: VectorFst<TropicalWeight> = builder.build()?;
+/// assert_eq!(fst.paths_iter().count(), 1);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct GraphFstBuilder<W: Semiring> {
+    created: Vec<bool>,
+    start: Option<StateId>,
+    finals: Vec<(StateId, W)>,
+    arcs: Vec<(StateId, Arc<W>)>,
+}
+
+impl<W: Semiring> GraphFstBuilder<W> {
+    /// Creates a builder without any state.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reserves the id of a state which can be referenced right away but has to be created with
+    /// `create_state` before the FST is built.
+    pub fn reserve_state(&mut self) -> StateId {
+        self.created.push(false);
+        self.created.len() - 1
+    }
+
+    /// Reserves the id of a state and creates it.
+    pub fn add_state(&mut self) -> StateId {
+        self.created.push(true);
+        self.created.len() - 1
+    }
+
+    /// Creates a state whose id has been reserved.
+    pub fn create_state(&mut self, state: StateId) -> Fallible<()> {
+        match self.created.get_mut(state) {
+            Some(created) if !*created => {
+                *created = true;
+                Ok(())
+            }
+            Some(_) => bail!("The state {} has already been created", state),
+            None => bail!("The state {} has not been reserved", state),
+        }
+    }
+
+    /// Sets the start state.
+    pub fn set_start(&mut self, state: StateId) {
+        self.start = Some(state);
+    }
+
+    /// Sets the final weight of a state.
+    pub fn set_final(&mut self, state: StateId, weight: W) {
+        self.finals.push((state, weight));
+    }
+
+    /// Adds an arc leaving the state `source`.
+    pub fn add_arc(&mut self, source: StateId, arc: Arc<W>) {
+        self.arcs.push((source, arc));
+    }
+
+    /// Builds the FST, the states keeping their ids. The arcs of a state are in the order in
+    /// which they have been added and a state set final more than once keeps its last final
+    /// weight.
+    pub fn build<F: MutableFst<W = W>>(self) -> Fallible<F> {
+        let start = match self.start {
+            Some(start) => start,
+            None => bail!("The FST has no start state"),
+        };
+
+        let referenced = once(start)
+            .chain(self.finals.iter().map(|(s, _)| *s))
+            .chain(self.arcs.iter().map(|(s, _)| *s))
+            .chain(self.arcs.iter().map(|(_, arc)| arc.nextstate));
+        for state in referenced {
+            ensure!(
+                self.created.get(state) == Some(&true),
+                "The state {} is referenced but has not been created",
+                state
+            );
+        }
+
+        let mut fst = F::new();
+        fst.add_states(self.created.len());
+        fst.set_start(start)?;
+        for (state, weight) in self.finals {
+            fst.set_final(state, weight)?;
+        }
+        for (source, arc) in self.arcs {
+            fst.add_arc(source, arc)?;
+        }
+        Ok(fst)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::{CoreFst, ExpandedFst};
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_graph_fst_builder_cycle() -> Fallible<()> {
+        let mut builder = GraphFstBuilder::new();
+        let s0 = builder.reserve_state();
+        let s1 = builder.reserve_state();
+        builder.add_arc(s1, Arc::new(2, 2, TropicalWeight::new(2.0), s0));
+        builder.add_arc(s0, Arc::new(1, 1, TropicalWeight::new(1.0), s1));
+        builder.set_final(s1, TropicalWeight::new(0.5));
+        builder.set_start(s0);
+        builder.create_state(s1)?;
+        builder.create_state(s0)?;
+
+        let fst: VectorFst<TropicalWeight> = builder.build()?;
+
+        let mut fst_ref = VectorFst::new();
+        fst_ref.add_states(2);
+        fst_ref.set_start(0)?;
+        fst_ref.set_final(1, TropicalWeight::new(0.5))?;
+        fst_ref.add_arc(0, Arc::new(1, 1, TropicalWeight::new(1.0), 1))?;
+        fst_ref.add_arc(1, Arc::new(2, 2, TropicalWeight::new(2.0), 0))?;
+
+        assert_eq!(fst, fst_ref);
+        assert_eq!(fst.num_states(), 2);
+        assert_eq!(fst.start(), Some(0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_graph_fst_builder_uncreated_state() -> Fallible<()> {
+        let mut builder = GraphFstBuilder::new();
+        let s0 = builder.add_state();
+        let s1 = builder.reserve_state();
+        builder.set_start(s0);
+        builder.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), s1));
+
+        assert!(builder.clone().build::<VectorFst<_>>().is_err());
+
+        builder.add_arc(s0, Arc::new(1, 1, TropicalWeight::one(), 5));
+        builder.create_state(s1)?;
+        assert!(builder.clone().build::<VectorFst<_>>().is_err());
+
+        assert!(builder.create_state(s1).is_err());
+        assert!(builder.create_state(5).is_err());
+        assert!(GraphFstBuilder::<TropicalWeight>::new()
+            .build::<VectorFst<_>>()
+            .is_err());
+        Ok(())
+    }
+}
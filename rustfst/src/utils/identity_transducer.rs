@@ -0,0 +1,78 @@
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::MutableFst;
+use crate::semirings::Semiring;
+use crate::Label;
+
+/// Builds the one-state transducer mapping each label of `alphabet` to itself with weight
+/// `weight`. The state being both initial and final, it accepts any sequence of these labels.
+///
+/// Composed with an FST whose output labels belong to the alphabet, it lets all its paths
+/// through, their weights being multiplied by `weight` once per label.
+///
+/// # Example
+///
+/// ```
+/// # use rustfst::fst_traits::{ArcIterator, CoreFst, ExpandedFst};
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::identity_transducer;
+/// # use rustfst::Arc;
+/// let fst = identity_transducer(&[1, 2], TropicalWeight::one());
+///
+/// assert_eq!(fst.num_states(), 1);
+/// assert_eq!(fst.is_final(0).unwrap(), true);
+/// assert_eq!(
+///     fst.arcs_iter(0).unwrap().cloned().collect::<Vec<_>>(),
+///     vec![
+///         Arc::new(1, 1, TropicalWeight::one(), 0),
+///         Arc::new(2, 2, TropicalWeight::one(), 0)
+///     ]
+/// );
+/// ```
+pub fn identity_transducer<W: 'static + Semiring>(alphabet: &[Label], weight: W) -> VectorFst<W> {
+    let mut fst = VectorFst::new();
+    let s = fst.add_state();
+
+    // Can't fail as the state has just been added
+    fst.set_start(s).unwrap();
+    fst.set_final(s, W::one()).unwrap();
+
+    for &label in alphabet {
+        fst.add_arc(s, Arc::new(label, label, weight.clone(), s))
+            .unwrap();
+    }
+
+    fst
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use failure::Fallible;
+
+    use crate::algorithms::compose;
+    use crate::fst_traits::PathsIterator;
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_identity_transducer() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(0.5))?;
+        fst.add_arc(0, Arc::new(1, 2, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 4, TropicalWeight::new(2.0), 2))?;
+        fst.add_arc(0, Arc::new(5, 6, TropicalWeight::new(3.0), 2))?;
+
+        let identity_fst = identity_transducer(&[2, 4, 6], TropicalWeight::one());
+        let composed_fst: VectorFst<_> = compose(&fst, &identity_fst)?;
+
+        let mut paths: Vec<_> = composed_fst.paths_iter().collect();
+        let mut paths_ref: Vec<_> = fst.paths_iter().collect();
+        paths.sort_by_key(|p| p.ilabels.clone());
+        paths_ref.sort_by_key(|p| p.ilabels.clone());
+        assert_eq!(paths, paths_ref);
+        Ok(())
+    }
+}
@@ -0,0 +1,128 @@
+use failure::Fallible;
+
+use crate::arc::Arc;
+use crate::fst_impls::VectorFst;
+use crate::fst_traits::MutableFst;
+use crate::semirings::Semiring;
+use crate::{Label, StateId};
+
+/// Builds an FST from the list of transitions of each state, given as `(ilabel, olabel, weight,
+/// nextstate)` tuples, the states being numbered from 0 in the order of `trans`. This is the
+/// sparse form of a transition matrix, e.g. of an HMM.
+///
+/// All the state references (start state, next states and final states) are checked before the
+/// FST is built and an error is returned if any of them is out of range.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_traits::{CoreFst, ExpandedFst};
+/// # use rustfst::semirings::{Semiring, TropicalWeight};
+/// # use rustfst::utils::from_transition_matrix;
+/// # fn main() -> Fallible<()> {
+/// let trans = vec![
+///     vec![(1, 1, TropicalWeight::new(0.5), 1)],
+///     vec![(2, 2, TropicalWeight::new(1.0), 1)],
+/// ];
+/// let fst = from_transition_matrix(&trans, 0, &[(1, TropicalWeight::one())])?;
+///
+/// assert_eq!(fst.num_states(), 2);
+/// assert_eq!(fst.start(), Some(0));
+/// assert_eq!(fst.final_weight(1)?, Some(&TropicalWeight::one()));
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_transition_matrix<W: 'static + Semiring>(
+    trans: &[Vec<(Label, Label, W, StateId)>],
+    start: StateId,
+    finals: &[(StateId, W)],
+) -> Fallible<VectorFst<W>> {
+    let num_states = trans.len();
+    ensure!(
+        start < num_states,
+        "The start state {} doesn't exist, there are {} states",
+        start,
+        num_states
+    );
+    for (state, arcs) in trans.iter().enumerate() {
+        for (ilabel, olabel, _, nextstate) in arcs {
+            ensure!(
+                *nextstate < num_states,
+                "The transition {} -> {} ({}:{}) leads to a state that doesn't exist",
+                state,
+                nextstate,
+                ilabel,
+                olabel
+            );
+        }
+    }
+    for (state, _) in finals {
+        ensure!(
+            *state < num_states,
+            "The final state {} doesn't exist, there are {} states",
+            state,
+            num_states
+        );
+    }
+
+    let mut fst = VectorFst::new();
+    fst.add_states(num_states);
+    for (state, arcs) in trans.iter().enumerate() {
+        for (ilabel, olabel, weight, nextstate) in arcs {
+            unsafe {
+                fst.add_arc_unchecked(
+                    state,
+                    Arc::new(*ilabel, *olabel, weight.clone(), *nextstate),
+                )
+            };
+        }
+    }
+    unsafe { fst.set_start_unchecked(start) };
+    for (state, weight) in finals {
+        unsafe { fst.set_final_unchecked(*state, weight.clone()) };
+    }
+    Ok(fst)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::semirings::TropicalWeight;
+
+    #[test]
+    fn test_from_transition_matrix() -> Fallible<()> {
+        let trans = vec![
+            vec![
+                (1, 2, TropicalWeight::new(0.5), 1),
+                (3, 3, TropicalWeight::new(1.0), 0),
+            ],
+            vec![(4, 5, TropicalWeight::new(2.0), 0)],
+        ];
+        let fst = from_transition_matrix(&trans, 0, &[(1, TropicalWeight::new(1.5))])?;
+
+        let mut fst_ref = VectorFst::new();
+        let s0 = fst_ref.add_state();
+        let s1 = fst_ref.add_state();
+        fst_ref.set_start(s0)?;
+        fst_ref.set_final(s1, TropicalWeight::new(1.5))?;
+        fst_ref.add_arc(s0, Arc::new(1, 2, TropicalWeight::new(0.5), s1))?;
+        fst_ref.add_arc(s0, Arc::new(3, 3, TropicalWeight::new(1.0), s0))?;
+        fst_ref.add_arc(s1, Arc::new(4, 5, TropicalWeight::new(2.0), s0))?;
+
+        assert_eq!(fst, fst_ref);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_transition_matrix_invalid_state() {
+        let trans = vec![vec![(1, 1, TropicalWeight::one(), 2)], vec![]];
+        assert!(from_transition_matrix(&trans, 0, &[]).is_err());
+
+        let trans = vec![vec![(1, 1, TropicalWeight::one(), 1)], vec![]];
+        assert!(from_transition_matrix(&trans, 2, &[]).is_err());
+        assert!(from_transition_matrix(&trans, 0, &[(2, TropicalWeight::one())]).is_err());
+        assert!(from_transition_matrix(&trans, 0, &[(1, TropicalWeight::one())]).is_ok());
+    }
+}
@@ -1,5 +1,17 @@
+mod edit_transducer;
 mod fst_to_labels;
+mod graph_fst_builder;
+mod identity_transducer;
 mod labels_to_fst;
+mod matrix;
+mod regex;
+mod write_paths;
 
-pub use self::fst_to_labels::decode_linear_fst;
+pub use self::edit_transducer::edit_transducer;
+pub use self::fst_to_labels::{decode_linear_fst, decode_nbest_linear};
+pub use self::graph_fst_builder::GraphFstBuilder;
+pub use self::identity_transducer::identity_transducer;
 pub use self::labels_to_fst::{acceptor, transducer};
+pub use self::matrix::from_transition_matrix;
+pub use self::regex::{from_regex, RegexToken};
+pub use self::write_paths::write_paths;
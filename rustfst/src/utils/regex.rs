@@ -0,0 +1,178 @@
+use std::iter::Peekable;
+use std::slice::Iter;
+
+use failure::Fallible;
+
+use crate::algorithms::{closure_plus, closure_star, concat, union};
+use crate::fst_impls::VectorFst;
+use crate::semirings::{BooleanWeight, Semiring};
+use crate::utils::acceptor;
+use crate::Label;
+
+/// Token of a regular expression over labels, as consumed by [`from_regex`](fn.from_regex.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegexToken {
+    /// Matches a single label.
+    Label(Label),
+    /// Union of the expressions on its left and on its right (`|`).
+    Union,
+    /// Kleene star of the preceding expression (`*`).
+    Star,
+    /// Kleene plus of the preceding expression (`+`).
+    Plus,
+    /// Opens a group (`(`).
+    OpenGroup,
+    /// Closes a group (`)`).
+    CloseGroup,
+}
+
+type Tokens<'a> = Peekable<Iter<'a, RegexToken>>;
+
+/// Compiles a regular expression over labels into an acceptor, accepting exactly the label
+/// sequences matched by the expression. Concatenation is implicit between consecutive
+/// expressions. The star and plus operators bind tighter than concatenation which itself binds
+/// tighter than the union.
+///
+/// The resulting acceptor contains epsilon transitions.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::utils::{from_regex, RegexToken};
+/// # use rustfst::fst_traits::CoreFst;
+/// # fn main() -> Fallible<()> {
+/// // 1(2|3)*
+/// let fst = from_regex(&[
+///     RegexToken::Label(1),
+///     RegexToken::OpenGroup,
+///     RegexToken::Label(2),
+///     RegexToken::Union,
+///     RegexToken::Label(3),
+///     RegexToken::CloseGroup,
+///     RegexToken::Star,
+/// ])?;
+///
+/// assert!(fst.start().is_some());
+/// # Ok(())
+/// # }
+/// ```
+pub fn from_regex(pattern: &[RegexToken]) -> Fallible<VectorFst<BooleanWeight>> {
+    let mut tokens = pattern.iter().peekable();
+    let fst = parse_union(&mut tokens)?;
+    if let Some(token) = tokens.next() {
+        bail!("Unexpected token in regex : {:?}", token);
+    }
+    Ok(fst)
+}
+
+fn parse_union(tokens: &mut Tokens) -> Fallible<VectorFst<BooleanWeight>> {
+    let mut fst = parse_concat(tokens)?;
+    while let Some(RegexToken::Union) = tokens.peek() {
+        tokens.next();
+        let rhs = parse_concat(tokens)?;
+        fst = union(&fst, &rhs)?;
+    }
+    Ok(fst)
+}
+
+fn parse_concat(tokens: &mut Tokens) -> Fallible<VectorFst<BooleanWeight>> {
+    // An empty expression matches the empty sequence
+    let mut fst = acceptor(&[], BooleanWeight::one());
+    while let Some(RegexToken::Label(_)) | Some(RegexToken::OpenGroup) = tokens.peek() {
+        let rhs = parse_repeat(tokens)?;
+        fst = concat(&fst, &rhs)?;
+    }
+    Ok(fst)
+}
+
+fn parse_repeat(tokens: &mut Tokens) -> Fallible<VectorFst<BooleanWeight>> {
+    let mut fst = parse_atom(tokens)?;
+    loop {
+        match tokens.peek() {
+            Some(RegexToken::Star) => closure_star(&mut fst),
+            Some(RegexToken::Plus) => closure_plus(&mut fst),
+            _ => break,
+        };
+        tokens.next();
+    }
+    Ok(fst)
+}
+
+fn parse_atom(tokens: &mut Tokens) -> Fallible<VectorFst<BooleanWeight>> {
+    match tokens.next() {
+        Some(RegexToken::Label(label)) => Ok(acceptor(&[*label], BooleanWeight::one())),
+        Some(RegexToken::OpenGroup) => {
+            let fst = parse_union(tokens)?;
+            match tokens.next() {
+                Some(RegexToken::CloseGroup) => Ok(fst),
+                _ => bail!("Missing closing group in regex"),
+            }
+        }
+        Some(token) => bail!("Unexpected token in regex : {:?}", token),
+        None => bail!("Unexpected end of regex"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    use crate::algorithms::rm_epsilon;
+    use crate::fst_traits::{ArcIterator, CoreFst};
+    use crate::StateId;
+
+    fn accepts(fst: &VectorFst<BooleanWeight>, labels: &[Label]) -> Fallible<bool> {
+        let mut states: HashSet<StateId> = fst.start().into_iter().collect();
+        for label in labels {
+            let mut next_states = HashSet::new();
+            for s in states {
+                for arc in fst.arcs_iter(s)? {
+                    if arc.ilabel == *label {
+                        next_states.insert(arc.nextstate);
+                    }
+                }
+            }
+            states = next_states;
+        }
+        for s in states {
+            if fst.is_final(s)? {
+                return Ok(true);
+            }
+        }
+        Ok(false)
+    }
+
+    #[test]
+    fn test_from_regex() -> Fallible<()> {
+        // a(b|c)*
+        let (a, b, c) = (1, 2, 3);
+        let fst = from_regex(&[
+            RegexToken::Label(a),
+            RegexToken::OpenGroup,
+            RegexToken::Label(b),
+            RegexToken::Union,
+            RegexToken::Label(c),
+            RegexToken::CloseGroup,
+            RegexToken::Star,
+        ])?;
+        let fst: VectorFst<BooleanWeight> = rm_epsilon(&fst)?;
+
+        assert!(accepts(&fst, &[a])?);
+        assert!(accepts(&fst, &[a, b])?);
+        assert!(accepts(&fst, &[a, c, b, b])?);
+        assert!(!accepts(&fst, &[])?);
+        assert!(!accepts(&fst, &[b])?);
+        assert!(!accepts(&fst, &[a, a])?);
+        assert!(!accepts(&fst, &[a, b, a])?);
+        Ok(())
+    }
+
+    #[test]
+    fn test_from_regex_unbalanced_group() {
+        assert!(from_regex(&[RegexToken::OpenGroup, RegexToken::Label(1)]).is_err());
+        assert!(from_regex(&[RegexToken::Label(1), RegexToken::CloseGroup]).is_err());
+    }
+}
@@ -0,0 +1,92 @@
+use std::io::Write;
+
+use failure::Fallible;
+
+use crate::fst_traits::{Fst, PathsIterator};
+use crate::Label;
+
+/// Writes up to `max_paths` paths of an FST to `writer`, one per line, in the order in which
+/// `paths_iter` yields them. A line holds the input labels, the output labels and the weight of
+/// the path, separated by `sep`, the labels themselves being separated by spaces. The paths
+/// are written as they are enumerated, without being collected first.
+///
+/// Returns the number of paths written.
+///
+/// # Example
+///
+/// ```
+/// # use failure::Fallible;
+/// # use rustfst::fst_impls::VectorFst;
+/// # use rustfst::semirings::{Semiring, IntegerWeight};
+/// # use rustfst::utils::{transducer, write_paths};
+/// # fn main() -> Fallible<()> {
+/// let fst : VectorFst<IntegerWeight> = transducer(&[1, 2], &[3], IntegerWeight::new(4));
+///
+/// let mut buffer = vec![];
+/// assert_eq!(write_paths(&fst, &mut buffer, 10, "\t")?, 1);
+/// assert_eq!(String::from_utf8(buffer)?, "1 2\t3\t4\n");
+/// # Ok(())
+/// # }
+/// ```
+pub fn write_paths<F: Fst, Wr: Write>(
+    fst: &F,
+    mut writer: Wr,
+    max_paths: usize,
+    sep: &str,
+) -> Fallible<usize> {
+    let mut n_paths = 0;
+    for path in fst.paths_iter().take(max_paths) {
+        writeln!(
+            writer,
+            "{}{}{}{}{}",
+            join_labels(&path.ilabels),
+            sep,
+            join_labels(&path.olabels),
+            sep,
+            path.weight
+        )?;
+        n_paths += 1;
+    }
+    writer.flush()?;
+    Ok(n_paths)
+}
+
+fn join_labels(labels: &[Label]) -> String {
+    labels
+        .iter()
+        .map(|l| l.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::arc::Arc;
+    use crate::fst_impls::VectorFst;
+    use crate::fst_traits::MutableFst;
+    use crate::semirings::{Semiring, TropicalWeight};
+
+    #[test]
+    fn test_write_paths() -> Fallible<()> {
+        let mut fst = VectorFst::new();
+        fst.add_states(3);
+        fst.set_start(0)?;
+        fst.set_final(2, TropicalWeight::new(0.5))?;
+        fst.add_arc(0, Arc::new(1, 4, TropicalWeight::new(1.0), 1))?;
+        fst.add_arc(0, Arc::new(2, 5, TropicalWeight::new(2.0), 1))?;
+        fst.add_arc(1, Arc::new(3, 6, TropicalWeight::new(0.25), 2))?;
+
+        let mut buffer = vec![];
+        assert_eq!(write_paths(&fst, &mut buffer, 10, " | ")?, 2);
+        let mut lines: Vec<_> = std::str::from_utf8(&buffer)?.lines().collect();
+        lines.sort();
+        assert_eq!(lines, vec!["1 3 | 4 6 | 1.75", "2 3 | 5 6 | 2.75"]);
+
+        let mut buffer = vec![];
+        assert_eq!(write_paths(&fst, &mut buffer, 1, " | ")?, 1);
+        assert_eq!(std::str::from_utf8(&buffer)?.lines().count(), 1);
+        Ok(())
+    }
+}